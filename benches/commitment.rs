@@ -0,0 +1,267 @@
+#[macro_use]
+extern crate criterion;
+
+extern crate halo2;
+use halo2::arithmetic::{best_multiexp, eval_polynomial, Curve, CurveAffine, FieldExt};
+use halo2::pasta::{EqAffine, Fp, Fq};
+use halo2::poly::commitment::{Blind, Params, Proof};
+use halo2::poly::EvaluationDomain;
+use halo2::transcript::{ChallengeScalar, DummyHash, Transcript};
+
+use rand::rngs::OsRng;
+
+use criterion::{BenchmarkId, Criterion};
+
+/// The multiexp that `best_multiexp` replaced, kept here as a baseline: an
+/// unsigned Pippenger multiexp with mixed-addition buckets, on one chunk of the
+/// terms per thread.
+fn baseline_multiexp<C: CurveAffine>(coeffs: &[C::Scalar], bases: &[C]) -> C::Projective {
+    assert_eq!(coeffs.len(), bases.len());
+
+    let num_threads = rayon::current_num_threads();
+    if coeffs.len() > num_threads {
+        let chunk = coeffs.len() / num_threads;
+        let num_chunks = coeffs.chunks(chunk).len();
+        let mut results = vec![C::Projective::zero(); num_chunks];
+        rayon::scope(|scope| {
+            for ((coeffs, bases), acc) in coeffs
+                .chunks(chunk)
+                .zip(bases.chunks(chunk))
+                .zip(results.iter_mut())
+            {
+                scope.spawn(move |_| {
+                    baseline_multiexp_serial(coeffs, bases, acc);
+                });
+            }
+        });
+        results.iter().fold(C::Projective::zero(), |a, b| a + b)
+    } else {
+        let mut acc = C::Projective::zero();
+        baseline_multiexp_serial(coeffs, bases, &mut acc);
+        acc
+    }
+}
+
+fn baseline_multiexp_serial<C: CurveAffine>(
+    coeffs: &[C::Scalar],
+    bases: &[C],
+    acc: &mut C::Projective,
+) {
+    let coeffs: Vec<[u8; 32]> = coeffs.iter().map(|a| a.to_bytes()).collect();
+
+    let c = if bases.len() < 4 {
+        1
+    } else if bases.len() < 32 {
+        3
+    } else {
+        (f64::from(bases.len() as u32)).ln().ceil() as usize
+    };
+
+    fn get_at(segment: usize, c: usize, bytes: &[u8; 32]) -> usize {
+        let skip_bits = segment * c;
+        let skip_bytes = skip_bits / 8;
+
+        if skip_bytes >= 32 {
+            return 0;
+        }
+
+        let mut v = [0; 8];
+        for (v, o) in v.iter_mut().zip(bytes[skip_bytes..].iter()) {
+            *v = *o;
+        }
+
+        let mut tmp = u64::from_le_bytes(v);
+        tmp >>= skip_bits - (skip_bytes * 8);
+        tmp %= 1 << c;
+
+        tmp as usize
+    }
+
+    #[derive(Clone, Copy)]
+    enum Bucket<C: CurveAffine> {
+        None,
+        Affine(C),
+        Projective(C::Projective),
+    }
+
+    impl<C: CurveAffine> Bucket<C> {
+        fn add_assign(&mut self, other: &C) {
+            *self = match *self {
+                Bucket::None => Bucket::Affine(*other),
+                Bucket::Affine(a) => Bucket::Projective(a + *other),
+                Bucket::Projective(mut a) => {
+                    a += *other;
+                    Bucket::Projective(a)
+                }
+            }
+        }
+
+        fn add(self, mut other: C::Projective) -> C::Projective {
+            match self {
+                Bucket::None => other,
+                Bucket::Affine(a) => {
+                    other += a;
+                    other
+                }
+                Bucket::Projective(a) => other + a,
+            }
+        }
+    }
+
+    let segments = (256 / c) + 1;
+
+    for current_segment in (0..segments).rev() {
+        for _ in 0..c {
+            *acc = acc.double();
+        }
+
+        let mut buckets: Vec<Bucket<C>> = vec![Bucket::None; (1 << c) - 1];
+
+        for (coeff, base) in coeffs.iter().zip(bases.iter()) {
+            let coeff = get_at(current_segment, c, coeff);
+            if coeff != 0 {
+                buckets[coeff - 1].add_assign(base);
+            }
+        }
+
+        // Summation by parts
+        // e.g. 3a + 2b + 1c = a +
+        //                    (a) + b +
+        //                    ((a) + b) + c
+        let mut running_sum = C::Projective::zero();
+        for exp in buckets.into_iter().rev() {
+            running_sum = exp.add(running_sum);
+            *acc += running_sum;
+        }
+    }
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    // Generating the parameters dominates the setup, so do it once per size.
+    let params: Vec<(u32, Params<EqAffine>)> = (10..=20).map(|k| (k, Params::new(k))).collect();
+
+    let mut group = c.benchmark_group("multiexp");
+    for (k, params) in params.iter() {
+        let bases = params.get_g();
+        let coeffs: Vec<_> = (0..bases.len()).map(|_| Fp::rand()).collect();
+        assert!(baseline_multiexp(&coeffs, &bases) == best_multiexp(&coeffs, &bases));
+
+        group.bench_with_input(BenchmarkId::new("baseline", k), k, |b, _| {
+            b.iter(|| baseline_multiexp(&coeffs, &bases))
+        });
+        group.bench_with_input(BenchmarkId::new("best_multiexp", k), k, |b, _| {
+            b.iter(|| best_multiexp(&coeffs, &bases))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("commit");
+    let mut compute_g = vec![];
+    for (k, params) in params.iter() {
+        let domain = EvaluationDomain::new(1, *k);
+        let mut px = domain.empty_coeff();
+        for a in px.iter_mut() {
+            *a = Fp::rand();
+        }
+        let blind = Blind(Fp::rand());
+
+        // The commitment as `Params::commit` computes it without precomputed
+        // tables, through the baseline multiexp.
+        let mut bases = params.get_g();
+        bases.push(params.get_h());
+        let mut scalars = px.to_vec();
+        scalars.push(blind.0);
+        assert!(baseline_multiexp(&scalars, &bases) == params.commit(&px, blind));
+
+        group.bench_with_input(BenchmarkId::new("baseline", k), k, |b, _| {
+            b.iter(|| baseline_multiexp(&scalars, &bases))
+        });
+        group.bench_with_input(BenchmarkId::new("best_multiexp", k), k, |b, _| {
+            b.iter(|| params.commit(&px, blind))
+        });
+
+        // Create an opening proof, so that there is a guard whose G can be
+        // computed.
+        let p = params.commit(&px, blind).to_affine();
+        let mut transcript = Transcript::<_, DummyHash<_>, DummyHash<_>>::new();
+        transcript.absorb_point(&p).unwrap();
+        let x = ChallengeScalar::<_, ()>::get(&mut transcript);
+        let v = eval_polynomial(&px, *x);
+        transcript.absorb_base(Fq::from_bytes(&v.to_bytes()).unwrap());
+
+        let opening_proof = loop {
+            let transcript_dup = transcript.clone();
+            match Proof::create(params, &mut transcript, &px, blind, *x, OsRng) {
+                Ok(proof) => {
+                    transcript = transcript_dup;
+                    break proof;
+                }
+                Err(_) => {
+                    transcript = transcript_dup;
+                    transcript.absorb_base(Fq::one());
+                }
+            }
+        };
+        compute_g.push((k, params, opening_proof, transcript, *x, p, v));
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("compute_g");
+    for (k, params, opening_proof, transcript, x, p, v) in compute_g.iter() {
+        let mut commitment_msm = params.empty_msm();
+        commitment_msm.append_term(Fp::one(), *p);
+        let guard = opening_proof
+            .verify(
+                params,
+                params.empty_msm(),
+                &mut transcript.clone(),
+                *x,
+                commitment_msm,
+                *v,
+            )
+            .unwrap();
+
+        // G as `Guard::compute_g` computes it, through the baseline multiexp.
+        let g = params.get_g();
+        let h = params.get_h();
+        let baseline_compute_g = || {
+            let mut tmp = baseline_multiexp(&guard.g_scalars(), &g);
+            tmp += h;
+            tmp.to_affine()
+        };
+        assert!(baseline_compute_g() == guard.compute_g());
+
+        group.bench_with_input(BenchmarkId::new("baseline", k), *k, |b, _| {
+            b.iter(baseline_compute_g)
+        });
+        group.bench_with_input(BenchmarkId::new("best_multiexp", k), *k, |b, _| {
+            b.iter(|| guard.compute_g())
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("commit_lagrange");
+    for k in 10..=16 {
+        let params: Params<EqAffine> = Params::new(k);
+        let mut precomputed: Params<EqAffine> = Params::new(k);
+        precomputed.precompute();
+        let domain = EvaluationDomain::new(1, k);
+        let mut px = domain.empty_lagrange();
+        for a in px.iter_mut() {
+            *a = Fp::rand();
+        }
+        let blind = Blind(Fp::rand());
+        assert!(params.commit_lagrange(&px, blind) == precomputed.commit_lagrange(&px, blind));
+
+        group.bench_with_input(BenchmarkId::new("best_multiexp", k), &k, |b, _| {
+            b.iter(|| params.commit_lagrange(&px, blind))
+        });
+        group.bench_with_input(BenchmarkId::new("precomputed", k), &k, |b, _| {
+            b.iter(|| precomputed.commit_lagrange(&px, blind))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -3,11 +3,13 @@ extern crate criterion;
 
 extern crate halo2;
 use halo2::arithmetic::FieldExt;
+use halo2::multicore::ThreadPool;
 use halo2::pasta::{EqAffine, Fp, Fq};
 use halo2::plonk::*;
 use halo2::poly::commitment::Params;
 use halo2::transcript::DummyHash;
 
+use rand::rngs::OsRng;
 use std::marker::PhantomData;
 
 use criterion::Criterion;
@@ -18,7 +20,7 @@ fn bench_with_k(name: &str, k: u32, c: &mut Criterion) {
     pub struct Variable(Column<Advice>, usize);
 
     // Initialize the polynomial commitment parameters
-    let params: Params<EqAffine> = Params::new::<DummyHash<Fq>>(k);
+    let params: Params<EqAffine> = Params::new(k);
 
     struct PLONKConfig {
         a: Column<Advice>,
@@ -239,8 +241,15 @@ fn bench_with_k(name: &str, k: u32, c: &mut Criterion) {
             };
 
             // Create a proof
-            Proof::create::<DummyHash<Fq>, DummyHash<Fp>, _>(&params, &pk, &circuit, &[])
-                .expect("proof generation should not fail")
+            Proof::create::<DummyHash<Fq>, DummyHash<Fp>, _, _, _>(
+                &params,
+                &pk,
+                &[circuit],
+                &[&[]],
+                &ThreadPool::global(),
+                OsRng,
+            )
+            .expect("proof generation should not fail")
         });
     });
 
@@ -250,14 +259,21 @@ fn bench_with_k(name: &str, k: u32, c: &mut Criterion) {
     };
 
     // Create a proof
-    let proof = Proof::create::<DummyHash<Fq>, DummyHash<Fp>, _>(&params, &pk, &circuit, &[])
-        .expect("proof generation should not fail");
+    let proof = Proof::create::<DummyHash<Fq>, DummyHash<Fp>, _, _, _>(
+        &params,
+        &pk,
+        &[circuit],
+        &[&[]],
+        &ThreadPool::global(),
+        OsRng,
+    )
+    .expect("proof generation should not fail");
 
     c.bench_function(&verifier_name, |b| {
         b.iter(|| {
             let msm = params.empty_msm();
             let guard = proof
-                .verify::<DummyHash<Fq>, DummyHash<Fp>>(&params, pk.get_vk(), msm, &[])
+                .verify::<DummyHash<Fq>, DummyHash<Fp>, _>(&params, pk.get_vk(), msm, &[&[]], &[])
                 .unwrap();
             let msm = guard.clone().use_challenges();
             assert!(msm.eval());
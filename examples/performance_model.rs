@@ -1,12 +1,14 @@
 use halo2::{
     arithmetic::{Curve, FieldExt},
-    model::ModelRecorder,
+    model::{CircuitCost, ModelRecorder},
+    multicore::ThreadPool,
     pasta::{EqAffine, Fp, Fq},
     plonk::*,
     poly::commitment::{Blind, Params},
     transcript::DummyHash,
 };
 
+use rand::rngs::OsRng;
 use std::marker::PhantomData;
 
 /// This represents an advice column at a certain row in the ConstraintSystem
@@ -249,7 +251,7 @@ fn main() {
     let k = 11;
 
     // Initialize the polynomial commitment parameters
-    let params: Params<EqAffine> = Params::new::<DummyHash<Fq>>(k);
+    let params: Params<EqAffine> = Params::new(k);
 
     let empty_circuit: MyCircuit<Fp> = MyCircuit { a: None, k };
 
@@ -273,20 +275,31 @@ fn main() {
     };
 
     // Create a proof
-    let proof =
-        Proof::create::<DummyHash<Fq>, DummyHash<Fp>, _>(&params, &pk, &circuit, &[pubinputs])
-            .expect("proof generation should not fail");
+    let proof = Proof::create::<DummyHash<Fq>, DummyHash<Fp>, _, _, _>(
+        &params,
+        &pk,
+        &[circuit],
+        &[&[pubinputs]],
+        &ThreadPool::global(),
+        OsRng,
+    )
+    .expect("proof generation should not fail");
 
     println!("[Prover] {}", recorder);
     recorder.clear();
 
-    let pubinput_slice = &[pubinput];
+    let pubinput_slice = &[&[pubinput][..]];
     let msm = params.empty_msm();
     let guard = proof
-        .verify::<DummyHash<Fq>, DummyHash<Fp>>(&params, pk.get_vk(), msm, pubinput_slice)
+        .verify::<DummyHash<Fq>, DummyHash<Fp>, _>(&params, pk.get_vk(), msm, pubinput_slice, &[])
         .unwrap();
     let msm = guard.clone().use_challenges();
     assert!(msm.eval());
 
     println!("[Verifier] {}", recorder);
+
+    let cost = CircuitCost::<EqAffine>::measure::<MyCircuit<Fp>>(k, 1);
+    println!("[Estimate] {:#?}", cost.proof_size());
+    println!("[Estimate] {:#?}", cost.verifier_cost());
+    println!("[Estimate] {:#?}", cost.prover_cost());
 }
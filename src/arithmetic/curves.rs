@@ -8,6 +8,10 @@ use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 
 use super::{FieldExt, Group};
 
+/// A function returned by [`Curve::hash_to_curve`], which hashes messages to
+/// points of the curve `C`.
+pub type HashToCurve<'a, C> = Box<dyn Fn(&[u8]) -> C + 'a>;
+
 /// This trait is a common interface for dealing with elements of an elliptic
 /// curve group in the "projective" form, where that arithmetic is usually more
 /// efficient.
@@ -55,6 +59,19 @@ pub trait Curve:
     /// The base field over which this elliptic curve is constructed.
     type Base: FieldExt;
 
+    /// A name for this curve, which separates hashes to it from hashes to
+    /// other curves.
+    const CURVE_ID: &'static str;
+
+    /// Returns a function that hashes messages to this curve, with domain
+    /// separation given by `domain_prefix`.
+    ///
+    /// The default implementation uses the Shallue-van de Woestijne map, and
+    /// assumes that the curve has prime order.
+    fn hash_to_curve<'a>(domain_prefix: &'a str) -> HashToCurve<'a, Self> {
+        Box::new(super::hash_to_curve_svdw(domain_prefix))
+    }
+
     /// Obtains the additive identity.
     fn zero() -> Self;
 
@@ -71,6 +88,12 @@ pub trait Curve:
     /// by an element of multiplicative order 3.
     fn endo(&self) -> Self;
 
+    /// Multiplies this element by `scalar` in variable time. This must only be
+    /// used when neither is secret, such as when verifying proofs.
+    fn mul_vartime(&self, scalar: &<Self as Curve>::Scalar) -> Self {
+        *self * *scalar
+    }
+
     /// Converts this element into its affine form.
     fn to_affine(&self) -> Self::Affine;
 
@@ -121,6 +144,10 @@ pub trait CurveAffine:
     type Scalar: FieldExt;
     /// The base field over which this elliptic curve is constructed.
     type Base: FieldExt;
+    /// The compressed representation of a point: the little endian encoding
+    /// of its $x$-coordinate followed by the sign of its $y$-coordinate, which
+    /// is 32 bytes long when the top bit of $x$ is always unset.
+    type Repr: Copy + Default + Send + Sync + 'static + AsRef<[u8]> + AsMut<[u8]>;
 
     /// Obtains the additive identity.
     fn zero() -> Self;
@@ -145,13 +172,12 @@ pub trait CurveAffine:
     /// always be true unless an "unchecked" API was used.
     fn is_on_curve(&self) -> Choice;
 
-    /// Attempts to obtain a group element from its compressed 32-byte little
-    /// endian representation.
-    fn from_bytes(bytes: &[u8; 32]) -> CtOption<Self>;
+    /// Attempts to obtain a group element from its compressed little endian
+    /// representation.
+    fn from_bytes(bytes: &Self::Repr) -> CtOption<Self>;
 
-    /// Obtains the compressed, 32-byte little endian representation of this
-    /// element.
-    fn to_bytes(&self) -> [u8; 32];
+    /// Obtains the compressed little endian representation of this element.
+    fn to_bytes(&self) -> Self::Repr;
 
     /// Attempts to obtain a group element from its uncompressed 64-byte little
     /// endian representation.
@@ -164,3 +190,77 @@ pub trait CurveAffine:
     /// Returns the curve constant $b$
     fn b() -> Self::Base;
 }
+
+/// Checks the group law, conversions, serialization and endomorphism of the
+/// curve `C` on random points. Every curve implementation should pass this.
+#[cfg(test)]
+pub(crate) fn test_curve<C: Curve>() {
+    use ff::Field;
+
+    let g = C::one();
+    let zero = C::zero();
+    assert!(bool::from(g.is_on_curve()));
+    assert!(bool::from(C::Affine::one().is_on_curve()));
+    assert!(bool::from(zero.is_on_curve()));
+    assert!(bool::from(zero.is_zero()));
+    assert!(!bool::from(g.is_zero()));
+    assert_eq!(g.to_affine(), C::Affine::one());
+    assert!(bool::from(zero.to_affine().is_zero()));
+
+    // Group law
+    let a = <C as Curve>::Scalar::rand();
+    let b = <C as Curve>::Scalar::rand();
+    let p = g * a;
+    let q = g * b;
+    assert!(bool::from(p.is_on_curve()));
+    assert_eq!(p + q, g * (a + b));
+    assert_eq!(q + p, p + q);
+    assert_eq!(p * b, g * (a * b));
+    assert_eq!(p + p, p.double());
+    assert_eq!(p - q + q, p);
+    assert_eq!(p + (-p), zero);
+    assert_eq!(p + zero, p);
+    let mut r = p;
+    r += q.to_affine();
+    assert_eq!(r, p + q);
+    assert_eq!(p.to_affine() + q.to_affine(), p + q);
+    assert_eq!(g * -<C as Curve>::Scalar::one(), -g);
+    assert_eq!(g * <C as Curve>::Scalar::zero(), zero);
+    assert_eq!(p.mul_vartime(&b), p * b);
+
+    // Conversions
+    assert_eq!(C::from(p.to_affine()), p);
+    let points = [p, zero, q];
+    let mut affine = [C::Affine::zero(); 3];
+    C::batch_to_affine(&points, &mut affine);
+    for (point, affine) in points.iter().zip(affine.iter()) {
+        assert_eq!(point.to_affine(), *affine);
+    }
+    let (x, y) = p.to_affine().get_xy().unwrap();
+    assert_eq!(C::Affine::from_xy(x, y).unwrap(), p.to_affine());
+    assert!(bool::from(
+        C::Affine::from_xy(x, y + C::Base::one()).is_none()
+    ));
+
+    // Serialization
+    for point in [p, -p, zero].iter() {
+        let point = point.to_affine();
+        assert_eq!(C::Affine::from_bytes(&point.to_bytes()).unwrap(), point);
+        assert_eq!(
+            C::Affine::from_bytes_wide(&point.to_bytes_wide()).unwrap(),
+            point
+        );
+    }
+
+    // Endomorphism
+    assert_eq!(p.endo(), p * <C as Curve>::Scalar::ZETA);
+
+    // Hashing
+    let hash = C::hash_to_curve("halo2:test");
+    let p = hash(b"hello");
+    assert!(bool::from(p.is_on_curve()));
+    assert!(!bool::from(p.is_zero()));
+    assert_eq!(p, hash(b"hello"));
+    assert!(p != hash(b"hello!"));
+    assert!(p != C::hash_to_curve("halo2:other")(b"hello"));
+}
@@ -0,0 +1,338 @@
+//! This module implements the Fast-Fourier Transformation (FFT) over the
+//! multiplicative subgroups of order $2^k$ of a scalar field, for vectors of
+//! any [`Group`] elements.
+//!
+//! The transformation is recursive: after a bit-reversal permutation, each
+//! half or quarter of the vector is transformed in place, and the results are
+//! combined with a single pass of butterflies. The deeper levels thus work on
+//! data that fits in cache. Pairs of radix-$2$ levels are fused into radix-$4$
+//! levels, which save a quarter of the scalings. The powers of the root of
+//! unity are read from a precomputed table, and the sub-transformations and
+//! butterflies of large levels are spread across threads.
+
+use super::{parallelize, FieldExt, Group};
+use crate::multicore;
+
+/// Below this many elements, a level of the transformation is not worth
+/// splitting across threads.
+const PARALLEL_THRESHOLD: usize = 1 << 10;
+
+/// The powers of a root of unity $\omega$ of order $2^k$, which are needed to
+/// transform vectors of size up to $2^k$.
+#[derive(Clone, Debug)]
+pub struct FftTwiddles<F> {
+    log_n: u32,
+    // omega^i for i < 2^(k - 1); the others are their negations.
+    powers: Vec<F>,
+}
+
+impl<F: FieldExt> FftTwiddles<F> {
+    /// Precomputes the powers of `omega`, which must be an element of
+    /// multiplicative order $2^{\mathsf{log\_n}}$.
+    pub fn new(omega: F, log_n: u32) -> Self {
+        let mut powers = vec![F::zero(); (1 << log_n) >> 1];
+        if !powers.is_empty() {
+            parallelize(&mut powers, |powers, start| {
+                let mut cur = omega.pow_vartime(&[start as u64, 0, 0, 0]);
+                for power in powers.iter_mut() {
+                    *power = cur;
+                    cur *= &omega;
+                }
+            });
+        }
+
+        FftTwiddles { log_n, powers }
+    }
+
+    /// Returns $k$, where $2^k$ is the order of $\omega$.
+    pub fn log_n(&self) -> u32 {
+        self.log_n
+    }
+
+    /// Returns $\omega^i$ for $i < 2^k$.
+    fn get(&self, i: usize) -> F {
+        let half = self.powers.len();
+        if i < half {
+            self.powers[i]
+        } else {
+            -self.powers[i - half]
+        }
+    }
+}
+
+/// Performs an FFT on a vector `a` of size $n = 2^j \leq 2^k$, given the
+/// `twiddles` of a root of unity $\omega$ of order $2^k$. The result is that
+/// the vector `a`, when interpreted as the coefficients of a polynomial of
+/// degree $n - 1$, is transformed into the evaluations of this polynomial at
+/// each of the $n$ distinct powers of $\omega^{2^{k - j}}$.
+///
+/// The inverse transformation is this one, followed by reversing all but the
+/// first element of the result and dividing each element by $n$.
+///
+/// This function will panic if the size of `a` is not a power of two, or is
+/// larger than $2^k$.
+///
+/// This will use multithreading if beneficial.
+pub fn fft<G: Group>(a: &mut [G], twiddles: &FftTwiddles<G::Scalar>) {
+    let n = a.len();
+    assert!(n.is_power_of_two());
+    let log_n = n.trailing_zeros();
+    assert!(log_n <= twiddles.log_n);
+
+    if n == 1 {
+        return;
+    }
+
+    fn bitreverse(mut n: usize, l: u32) -> usize {
+        let mut r = 0;
+        for _ in 0..l {
+            r = (r << 1) | (n & 1);
+            n >>= 1;
+        }
+        r
+    }
+
+    for k in 0..n {
+        let rk = bitreverse(k, log_n);
+        if k < rk {
+            a.swap(rk, k);
+        }
+    }
+
+    recursive_fft(
+        a,
+        twiddles,
+        1 << (twiddles.log_n - log_n),
+        multicore::current_num_threads(),
+    );
+}
+
+/// Performs an FFT on a vector `a` of size $n = 2^k$, when provided `log_n` =
+/// $k$ and an element of multiplicative order $n$ called `omega` ($\omega$).
+/// The result is that the vector `a`, when interpreted as the coefficients of
+/// a polynomial of degree $n - 1$, is transformed into the evaluations of this
+/// polynomial at each of the $n$ distinct powers of $\omega$. This
+/// transformation is invertible by providing $\omega^{-1}$ in place of
+/// $\omega$ and dividing each resulting field element by $n$.
+///
+/// This computes the powers of $\omega$ on every call; use [`fft`] to reuse
+/// them across transformations.
+///
+/// This will use multithreading if beneficial.
+pub fn best_fft<G: Group>(a: &mut [G], omega: G::Scalar, log_n: u32) {
+    assert_eq!(a.len(), 1 << log_n);
+
+    fft(a, &FftTwiddles::new(omega, log_n));
+}
+
+/// Transforms the bit-reversed vector `a` with the root of unity
+/// $\omega^{\mathsf{stride}}$, using up to `num_threads` threads.
+fn recursive_fft<G: Group>(
+    a: &mut [G],
+    twiddles: &FftTwiddles<G::Scalar>,
+    stride: usize,
+    num_threads: usize,
+) {
+    let n = a.len();
+    if n == 1 {
+        return;
+    }
+    let num_threads = if n < PARALLEL_THRESHOLD {
+        1
+    } else {
+        num_threads
+    };
+
+    if n.trailing_zeros() % 2 == 1 {
+        let (a0, a1) = a.split_at_mut(n / 2);
+        recurse(&mut [&mut *a0, &mut *a1], twiddles, stride * 2, num_threads);
+
+        let chunk = chunk_size(n / 2, num_threads);
+        if num_threads == 1 {
+            radix_2(a0, a1, 0, twiddles, stride);
+        } else {
+            multicore::scope(|scope| {
+                for (i, (a0, a1)) in a0.chunks_mut(chunk).zip(a1.chunks_mut(chunk)).enumerate() {
+                    scope.spawn(move || radix_2(a0, a1, i * chunk, twiddles, stride));
+                }
+            });
+        }
+    } else {
+        let (a01, a23) = a.split_at_mut(n / 2);
+        let (a0, a1) = a01.split_at_mut(n / 4);
+        let (a2, a3) = a23.split_at_mut(n / 4);
+        recurse(
+            &mut [&mut *a0, &mut *a1, &mut *a2, &mut *a3],
+            twiddles,
+            stride * 4,
+            num_threads,
+        );
+
+        let chunk = chunk_size(n / 4, num_threads);
+        if num_threads == 1 {
+            radix_4(a0, a1, a2, a3, 0, twiddles, stride);
+        } else {
+            multicore::scope(|scope| {
+                for (i, (((a0, a1), a2), a3)) in a0
+                    .chunks_mut(chunk)
+                    .zip(a1.chunks_mut(chunk))
+                    .zip(a2.chunks_mut(chunk))
+                    .zip(a3.chunks_mut(chunk))
+                    .enumerate()
+                {
+                    scope.spawn(move || radix_4(a0, a1, a2, a3, i * chunk, twiddles, stride));
+                }
+            });
+        }
+    }
+}
+
+/// Transforms each of the `parts` with the root of unity
+/// $\omega^{\mathsf{stride}}$, on separate threads if `num_threads` allows.
+fn recurse<G: Group>(
+    parts: &mut [&mut [G]],
+    twiddles: &FftTwiddles<G::Scalar>,
+    stride: usize,
+    num_threads: usize,
+) {
+    if num_threads == 1 {
+        for part in parts.iter_mut() {
+            recursive_fft(part, twiddles, stride, 1);
+        }
+    } else {
+        let num_threads = (num_threads / parts.len()).max(1);
+        multicore::scope(|scope| {
+            for part in parts.iter_mut() {
+                scope.spawn(move || recursive_fft(part, twiddles, stride, num_threads));
+            }
+        });
+    }
+}
+
+fn chunk_size(n: usize, num_threads: usize) -> usize {
+    ((n - 1) / num_threads + 1).max(1)
+}
+
+/// Combines the transformations `a0` and `a1` of the even and odd terms, from
+/// index `start` onwards, where the root of unity is
+/// $\omega^{\mathsf{stride}}$.
+fn radix_2<G: Group>(
+    a0: &mut [G],
+    a1: &mut [G],
+    start: usize,
+    twiddles: &FftTwiddles<G::Scalar>,
+    stride: usize,
+) {
+    for (j, (a0, a1)) in (start..).zip(a0.iter_mut().zip(a1.iter_mut())) {
+        let mut t = *a1;
+        if j != 0 {
+            t.group_scale(&twiddles.get(stride * j));
+        }
+        *a1 = *a0;
+        a1.group_sub(&t);
+        a0.group_add(&t);
+    }
+}
+
+/// Combines the transformations `a0`, `a1`, `a2` and `a3` of the terms with
+/// indices $0$, $2$, $1$ and $3$ modulo $4$, from index `start` onwards, where
+/// the root of unity is $\omega^{\mathsf{stride}}$.
+fn radix_4<G: Group>(
+    a0: &mut [G],
+    a1: &mut [G],
+    a2: &mut [G],
+    a3: &mut [G],
+    start: usize,
+    twiddles: &FftTwiddles<G::Scalar>,
+    stride: usize,
+) {
+    // A fourth root of unity.
+    let i4 = twiddles.get(1 << (twiddles.log_n - 2));
+
+    for (j, (((a0, a1), a2), a3)) in (start..).zip(
+        a0.iter_mut()
+            .zip(a1.iter_mut())
+            .zip(a2.iter_mut())
+            .zip(a3.iter_mut()),
+    ) {
+        let (mut t1, mut t2, mut t3) = (*a1, *a2, *a3);
+        if j != 0 {
+            t1.group_scale(&twiddles.get(2 * stride * j));
+            t2.group_scale(&twiddles.get(stride * j));
+            t3.group_scale(&twiddles.get(3 * stride * j));
+        }
+
+        let mut u0 = *a0;
+        u0.group_add(&t1);
+        let mut u1 = *a0;
+        u1.group_sub(&t1);
+        let mut u2 = t2;
+        u2.group_add(&t3);
+        let mut u3 = t2;
+        u3.group_sub(&t3);
+        u3.group_scale(&i4);
+
+        *a0 = u0;
+        a0.group_add(&u2);
+        *a2 = u0;
+        a2.group_sub(&u2);
+        *a1 = u1;
+        a1.group_add(&u3);
+        *a3 = u1;
+        a3.group_sub(&u3);
+    }
+}
+
+#[test]
+fn test_fft() {
+    use super::eval_polynomial;
+    use crate::pasta::Fp;
+    use ff::{Field, PrimeField};
+
+    const K: u32 = 7;
+    let mut omega = Fp::ROOT_OF_UNITY;
+    for _ in K..Fp::S {
+        omega = omega.square();
+    }
+    let twiddles = FftTwiddles::new(omega, K);
+
+    for log_n in 0..=K {
+        let n = 1 << log_n;
+        let coeffs: Vec<_> = (0..n).map(|_| Fp::rand()).collect();
+        let mut a = coeffs.clone();
+        fft(&mut a, &twiddles);
+
+        let omega_n = omega.pow_vartime(&[1 << (K - log_n), 0, 0, 0]);
+        for (i, a) in a.iter().enumerate() {
+            let x = omega_n.pow_vartime(&[i as u64, 0, 0, 0]);
+            assert_eq!(*a, eval_polynomial(&coeffs, x));
+        }
+    }
+}
+
+#[test]
+fn test_fft_inverse() {
+    use crate::pasta::Fp;
+    use ff::{Field, PrimeField};
+
+    // Large enough to be transformed with several threads.
+    const K: u32 = 13;
+    let mut omega = Fp::ROOT_OF_UNITY;
+    for _ in K..Fp::S {
+        omega = omega.square();
+    }
+    let twiddles = FftTwiddles::new(omega, K);
+
+    for log_n in [K - 1, K].iter() {
+        let coeffs: Vec<_> = (0..(1 << log_n)).map(|_| Fp::rand()).collect();
+        let mut a = coeffs.clone();
+        fft(&mut a, &twiddles);
+        fft(&mut a, &twiddles);
+        a[1..].reverse();
+
+        let n_inv = Fp::from_u64(1 << log_n).invert().unwrap();
+        for (a, coeff) in a.iter().zip(coeffs.iter()) {
+            assert_eq!(*a * n_inv, *coeff);
+        }
+    }
+}
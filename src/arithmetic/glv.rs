@@ -0,0 +1,438 @@
+//! This module implements scalar multiplication using the endomorphism of a
+//! curve, following Gallant, Lambert and Vanstone ([GLV]).
+//!
+//! The endomorphism $\phi$ acts on points as multiplication by the element
+//! $\lambda$ = `ZETA` of the scalar field, so a scalar $k$ may be split into
+//! two halves of about 128 bits with $k = k_1 + k_2 \lambda$, and $[k] P$
+//! computed as $[k_1] P + [k_2] \phi(P)$ with half as many doublings.
+//!
+//! [GLV]: https://www.iacr.org/archive/crypto2001/21390189.pdf
+
+use ff::Field;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+use super::{Curve, CurveAffine, FieldExt};
+
+/// The constants for decomposing scalars of a curve with an endomorphism.
+///
+/// They are derived from a short basis $(a_1, b_1), (a_2, b_2)$ of the lattice
+/// of $(a, b)$ with $a + b \lambda = 0$, with $b_1 < 0 < b_2$. The
+/// decomposition of $k$ is $(k, 0) - c_1 (a_1, b_1) - c_2 (a_2, b_2)$ for the
+/// integers $c_1, c_2$ closest to $b_2 k / r$ and $-b_1 k / r$.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Glv<F> {
+    /// $-b_1$.
+    pub(crate) neg_b1: F,
+    /// $b_2$.
+    pub(crate) b2: F,
+    /// $b_2 \cdot 2^{320} / r$, rounded.
+    pub(crate) g1: [u64; 4],
+    /// $-b_1 \cdot 2^{320} / r$, rounded.
+    pub(crate) g2: [u64; 4],
+}
+
+/// The magnitude of a decomposed scalar and whether it is negative. The
+/// magnitude is less than $2^{128}$ for the basis of any curve this is used
+/// with.
+#[derive(Clone, Copy, Debug)]
+struct SignedScalar {
+    neg: Choice,
+    limbs: [u64; 2],
+}
+
+impl SignedScalar {
+    /// Interprets `x` as the integer of smallest absolute value congruent to
+    /// it, which must be less than $2^{128}$.
+    fn new<F: FieldExt>(x: F) -> Self {
+        let to_limbs = |x: F| {
+            let bytes = x.to_bytes();
+            let mut limbs = [0u64; 4];
+            for (limb, bytes) in limbs.iter_mut().zip(bytes.chunks(8)) {
+                let mut buf = [0u8; 8];
+                buf.copy_from_slice(bytes);
+                *limb = u64::from_le_bytes(buf);
+            }
+            limbs
+        };
+
+        // The value is negative exactly when its representative is not
+        // small.
+        let pos = to_limbs(x);
+        let neg = to_limbs(-x);
+        let is_neg = !(pos[2].ct_eq(&0) & pos[3].ct_eq(&0));
+        debug_assert!(
+            pos[2] | pos[3] == 0 || neg[2] | neg[3] == 0,
+            "decomposed scalar does not fit in 128 bits"
+        );
+        SignedScalar {
+            neg: is_neg,
+            limbs: [
+                u64::conditional_select(&pos[0], &neg[0], is_neg),
+                u64::conditional_select(&pos[1], &neg[1], is_neg),
+            ],
+        }
+    }
+
+    fn bit(&self, i: usize) -> u64 {
+        (self.limbs[i / 64] >> (i % 64)) & 1
+    }
+
+    fn to_u128(self) -> u128 {
+        (self.limbs[0] as u128) | ((self.limbs[1] as u128) << 64)
+    }
+}
+
+/// Computes $\lfloor k g / 2^{320} \rceil$, which must be less than $2^{128}$.
+fn mul_shift(k: &[u64; 4], g: &[u64; 4]) -> u128 {
+    let mut res = [0u64; 8];
+    for (i, k) in k.iter().enumerate() {
+        let mut carry = 0;
+        for (j, g) in g.iter().enumerate() {
+            let t = (res[i + j] as u128) + (*k as u128) * (*g as u128) + (carry as u128);
+            res[i + j] = t as u64;
+            carry = (t >> 64) as u64;
+        }
+        res[i + 4] = carry;
+    }
+
+    // Round by adding 2^319 before discarding the low 320 bits.
+    let (_, carry) = res[4].overflowing_add(1 << 63);
+    debug_assert!(
+        res[7] == 0 && !(res[5] == u64::MAX && res[6] == u64::MAX && carry),
+        "rounded quotient does not fit in 128 bits"
+    );
+    (res[5] as u128 | ((res[6] as u128) << 64)) + carry as u128
+}
+
+impl<F: FieldExt> Glv<F> {
+    /// Returns $k_1, k_2$ with $k = k_1 + k_2 \lambda$.
+    fn decompose(&self, k: &F) -> (SignedScalar, SignedScalar) {
+        let bytes = k.to_bytes();
+        let mut limbs = [0u64; 4];
+        for (limb, bytes) in limbs.iter_mut().zip(bytes.chunks(8)) {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(bytes);
+            *limb = u64::from_le_bytes(buf);
+        }
+
+        let c1 = F::from_u128(mul_shift(&limbs, &self.g1));
+        let c2 = F::from_u128(mul_shift(&limbs, &self.g2));
+        let k2 = c1 * self.neg_b1 - c2 * self.b2;
+        let k1 = *k - k2 * F::ZETA;
+
+        (SignedScalar::new(k1), SignedScalar::new(k2))
+    }
+}
+
+/// A point in homogeneous projective coordinates $(X : Y : Z)$, with the
+/// identity at $(0 : 1 : 0)$.
+///
+/// The formulas of Renes, Costello and Batina ([RCB]) add and double these
+/// with a fixed sequence of field operations, whatever the points are. They
+/// are complete on curves $y^2 = x^3 + b$ of prime order.
+///
+/// [RCB]: https://eprint.iacr.org/2015/1060
+#[derive(Clone, Copy, Debug)]
+struct Complete<F> {
+    x: F,
+    y: F,
+    z: F,
+}
+
+impl<F: FieldExt> Complete<F> {
+    fn from_curve<C: Curve<Base = F>>(p: &C) -> Self {
+        let xy = p.to_affine().get_xy();
+        let is_zero = xy.is_none();
+        let (x, y) = Option::from(xy).unwrap_or((F::zero(), F::one()));
+        Complete {
+            x,
+            y,
+            z: F::conditional_select(&F::one(), &F::zero(), is_zero),
+        }
+    }
+
+    fn to_curve<C: Curve<Base = F>>(self) -> C {
+        let zinv = self.z.invert().unwrap_or(F::zero());
+        let p = C::Affine::from_xy(self.x * zinv, self.y * zinv);
+        p.unwrap_or(C::Affine::zero()).to_projective()
+    }
+
+    fn select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Complete {
+            x: F::conditional_select(&a.x, &b.x, choice),
+            y: F::conditional_select(&a.y, &b.y, choice),
+            z: F::conditional_select(&a.z, &b.z, choice),
+        }
+    }
+
+    /// Algorithm 7 of [RCB].
+    ///
+    /// [RCB]: https://eprint.iacr.org/2015/1060
+    fn add(&self, other: &Self, b3: F) -> Self {
+        let t0 = self.x * other.x;
+        let t1 = self.y * other.y;
+        let t2 = self.z * other.z;
+        let t3 = (self.x + self.y) * (other.x + other.y) - (t0 + t1);
+        let t4 = (self.y + self.z) * (other.y + other.z) - (t1 + t2);
+        let y3 = (self.x + self.z) * (other.x + other.z) - (t0 + t2);
+        let t0 = t0.double() + t0;
+        let t2 = b3 * t2;
+        let z3 = t1 + t2;
+        let t1 = t1 - t2;
+        let y3 = b3 * y3;
+
+        Complete {
+            x: t3 * t1 - t4 * y3,
+            y: y3 * t0 + t1 * z3,
+            z: z3 * t4 + t0 * t3,
+        }
+    }
+
+    /// Algorithm 9 of [RCB].
+    ///
+    /// [RCB]: https://eprint.iacr.org/2015/1060
+    fn double(&self, b3: F) -> Self {
+        let t0 = self.y.square();
+        let z3 = t0.double().double().double();
+        let t1 = self.y * self.z;
+        let t2 = b3 * self.z.square();
+        let x3 = t2 * z3;
+        let y3 = t0 + t2;
+        let z3 = t1 * z3;
+        let t0 = t0 - (t2.double() + t2);
+        let y3 = t0 * y3 + x3;
+        let x3 = (t0 * (self.x * self.y)).double();
+
+        Complete {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+}
+
+/// Computes $[k] P$ with a joint ladder over the two halves of $k$, which
+/// takes two bits of each half at a time. The curve must have prime order.
+///
+/// The ladder uses complete formulas, so the sequence of field operations does
+/// not depend on $k$, and neither do the table entries that are read.
+pub(crate) fn mul_glv<C: Curve>(
+    p: &C,
+    k: &<C as Curve>::Scalar,
+    glv: &Glv<<C as Curve>::Scalar>,
+) -> C {
+    let b3 = C::b().double() + C::b();
+
+    let (k1, k2) = glv.decompose(k);
+    let p1 = C::conditional_select(p, &-*p, k1.neg);
+    let p2 = p.endo();
+    let p2 = C::conditional_select(&p2, &-p2, k2.neg);
+    let p1 = Complete::from_curve(&p1);
+    let p2 = Complete::from_curve(&p2);
+
+    // table[i + 4 * j] = [i] P1 + [j] P2
+    let identity = Complete::from_curve(&C::zero());
+    let mut table = [identity; 16];
+    for i in 1..4 {
+        table[i] = table[i - 1].add(&p1, b3);
+    }
+    for j in 1..4 {
+        for i in 0..4 {
+            table[i + 4 * j] = table[i + 4 * (j - 1)].add(&p2, b3);
+        }
+    }
+
+    let mut acc = identity;
+    for w in (0..64).rev() {
+        acc = acc.double(b3).double(b3);
+
+        let index = (k1.bit(2 * w) | (k1.bit(2 * w + 1) << 1))
+            | ((k2.bit(2 * w) | (k2.bit(2 * w + 1) << 1)) << 2);
+        let mut t = identity;
+        for (i, entry) in table.iter().enumerate() {
+            t = Complete::select(&t, entry, index.ct_eq(&(i as u64)));
+        }
+        acc = acc.add(&t, b3);
+    }
+
+    acc.to_curve()
+}
+
+/// The width of the windows of the non-adjacent forms used by
+/// [`mul_glv_vartime`].
+const WNAF_WIDTH: usize = 5;
+
+/// Returns the width-$w$ non-adjacent form of `k`, with the least significant
+/// digit first: every digit is zero or odd and less than $2^{w - 1}$ in
+/// absolute value, and any $w$ consecutive digits have at most one nonzero.
+fn wnaf(k: u128, w: usize) -> Vec<i64> {
+    let width = 1u64 << w;
+    let window_mask = width - 1;
+
+    let mut naf = vec![0; 129];
+    let mut pos = 0;
+    let mut carry = 0;
+    while pos < naf.len() {
+        let bits = if pos < 128 { (k >> pos) as u64 } else { 0 };
+        let window = carry + (bits & window_mask);
+
+        if window & 1 == 0 {
+            // The digit here is zero, and any carry moves on to the next
+            // position unchanged.
+            pos += 1;
+            continue;
+        }
+
+        if window < width / 2 {
+            carry = 0;
+            naf[pos] = window as i64;
+        } else {
+            carry = 1;
+            naf[pos] = (window as i64) - (width as i64);
+        }
+        pos += w;
+    }
+
+    naf
+}
+
+/// Computes $[k] P$ in variable time, using the width-5 non-adjacent forms of
+/// the two halves of $k$. This must only be used when $k$ and $P$ are public,
+/// such as when verifying proofs.
+pub(crate) fn mul_glv_vartime<C: Curve>(
+    p: &C,
+    k: &<C as Curve>::Scalar,
+    glv: &Glv<<C as Curve>::Scalar>,
+) -> C {
+    let (k1, k2) = glv.decompose(k);
+    let p1 = if bool::from(k1.neg) { -*p } else { *p };
+    let p2 = if bool::from(k2.neg) {
+        -p.endo()
+    } else {
+        p.endo()
+    };
+
+    // The odd multiples [1] P, [3] P, ..., [2^(w - 1) - 1] P of each point.
+    let odd_multiples = |p: C| {
+        let p2 = p.double();
+        let mut table = vec![p; 1 << (WNAF_WIDTH - 2)];
+        for i in 1..table.len() {
+            table[i] = table[i - 1] + p2;
+        }
+        table
+    };
+    let table1 = odd_multiples(p1);
+    let table2 = odd_multiples(p2);
+    let naf1 = wnaf(k1.to_u128(), WNAF_WIDTH);
+    let naf2 = wnaf(k2.to_u128(), WNAF_WIDTH);
+
+    let mut acc = C::zero();
+    for i in (0..naf1.len()).rev() {
+        acc = acc.double();
+        for (naf, table) in [(&naf1, &table1), (&naf2, &table2)].iter() {
+            let digit = naf[i];
+            if digit > 0 {
+                acc = acc + table[(digit / 2) as usize];
+            } else if digit < 0 {
+                acc = acc - table[(-digit / 2) as usize];
+            }
+        }
+    }
+
+    acc
+}
+
+#[cfg(test)]
+fn mul_naive<C: Curve>(p: &C, k: &<C as Curve>::Scalar) -> C {
+    let mut acc = C::zero();
+    for byte in k.to_bytes().iter().rev() {
+        for i in (0..8).rev() {
+            acc = acc.double();
+            if (byte >> i) & 1 == 1 {
+                acc = acc + p;
+            }
+        }
+    }
+    acc
+}
+
+#[cfg(test)]
+fn test_scalars<F: FieldExt>() -> Vec<F> {
+    let mut scalars = vec![F::zero(), F::one(), -F::one(), F::ZETA, -F::ZETA];
+    scalars.extend((0..20).map(|_| F::rand()));
+    scalars
+}
+
+#[test]
+fn test_decompose() {
+    use crate::pasta::{Ep, Eq};
+
+    // Besides the usual test scalars, those at the ends of the range of the
+    // halves, next to multiples of lambda, and whose quotients round half
+    // way, which are where a wrong lattice constant first makes a half
+    // overflow 128 bits.
+    fn check<F: FieldExt>(glv: Glv<F>) {
+        let to_field = |x: SignedScalar| {
+            let x_abs = F::from_u128(x.to_u128());
+            F::conditional_select(&x_abs, &-x_abs, x.neg)
+        };
+        let half = F::from_u64(2).invert().unwrap();
+        let mut scalars = test_scalars::<F>();
+        scalars.extend_from_slice(&[
+            F::from_u128(u128::MAX),
+            F::ZETA + F::one(),
+            F::ZETA - F::one(),
+            half,
+            -half,
+        ]);
+        for k in scalars {
+            let (k1, k2) = glv.decompose(&k);
+            assert_eq!(to_field(k1) + to_field(k2) * F::ZETA, k);
+        }
+    }
+    check(Ep::GLV.unwrap());
+    check(Eq::GLV.unwrap());
+}
+
+#[test]
+fn test_wnaf() {
+    use crate::pasta::Fp;
+
+    for k in [0, 1, 15, 16, 31, u128::MAX, Fp::rand().get_lower_128()].iter() {
+        let naf = wnaf(*k, WNAF_WIDTH);
+        let mut acc = 0i128;
+        for (i, digit) in naf.iter().enumerate().rev() {
+            assert!(digit.abs() < 1 << (WNAF_WIDTH - 1));
+            assert!(*digit == 0 || digit % 2 != 0);
+            // Reconstruct the value modulo 2^128.
+            acc = acc.wrapping_mul(2).wrapping_add(*digit as i128);
+            if *digit != 0 {
+                assert!(naf[i + 1..].iter().take(WNAF_WIDTH - 1).all(|d| *d == 0));
+            }
+        }
+        assert_eq!(acc as u128, *k);
+    }
+}
+
+#[test]
+fn test_mul_glv() {
+    use crate::pasta::{Ep, Eq};
+
+    fn check<C: Curve>() {
+        let points = [
+            C::zero(),
+            C::one(),
+            mul_naive(&C::one(), &<C as Curve>::Scalar::rand()),
+        ];
+        for p in points.iter() {
+            for k in test_scalars::<<C as Curve>::Scalar>() {
+                let expected = mul_naive(p, &k);
+                assert!(*p * k == expected);
+                assert!(p.to_affine() * k == expected);
+                assert!(p.mul_vartime(&k) == expected);
+            }
+        }
+    }
+    check::<Ep>();
+    check::<Eq>();
+}
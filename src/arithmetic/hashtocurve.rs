@@ -0,0 +1,224 @@
+//! This module contains the parts of hashing to elliptic curves, as specified
+//! in [draft-irtf-cfrg-hash-to-curve], that are shared between curves, and the
+//! Shallue-van de Woestijne map that is used for curves without a more
+//! efficient one.
+//!
+//! [draft-irtf-cfrg-hash-to-curve]: https://datatracker.ietf.org/doc/draft-irtf-cfrg-hash-to-curve/
+
+use subtle::ConstantTimeEq;
+
+use super::{Curve, CurveAffine, FieldExt};
+
+/// Each field element is derived from 64 bytes, which is enough for a field of
+/// at most 256 bits to be uniformly distributed up to a negligible bias.
+const CHUNKLEN: usize = 64;
+/// The input block size of BLAKE2b.
+const R_IN_BYTES: usize = 128;
+
+fn blake2b() -> blake2b_simd::State {
+    blake2b_simd::Params::new()
+        .hash_length(CHUNKLEN)
+        .personal(&[0u8; 16])
+        .to_state()
+}
+
+/// Hashes `message` to two field elements using `expand_message_xmd` with
+/// BLAKE2b, under the domain separation tag `dst`.
+pub(crate) fn hash_to_field<F: FieldExt>(dst: &str, message: &[u8]) -> [F; 2] {
+    // A tag that does not fit in the single byte of its length is replaced by
+    // its hash, as the draft specifies.
+    if dst.len() > 255 {
+        let dst = blake2b()
+            .update(b"H2C-OVERSIZE-DST-")
+            .update(dst.as_bytes())
+            .finalize();
+        expand_message_xmd(dst.as_bytes(), message)
+    } else {
+        expand_message_xmd(dst.as_bytes(), message)
+    }
+}
+
+/// Hashes `message` to two field elements under a tag `dst` of at most 255
+/// bytes.
+fn expand_message_xmd<F: FieldExt>(dst: &[u8], message: &[u8]) -> [F; 2] {
+    let empty_hasher = blake2b();
+    let dst_prime = [dst, &[dst.len() as u8]];
+
+    let b_0 = {
+        let mut hasher = empty_hasher.clone();
+        hasher
+            .update(&[0; R_IN_BYTES])
+            .update(message)
+            .update(&[0, (CHUNKLEN * 2) as u8, 0]);
+        for part in dst_prime.iter() {
+            hasher.update(part);
+        }
+        hasher.finalize()
+    };
+
+    let b_1 = {
+        let mut hasher = empty_hasher.clone();
+        hasher.update(b_0.as_bytes()).update(&[1]);
+        for part in dst_prime.iter() {
+            hasher.update(part);
+        }
+        hasher.finalize()
+    };
+
+    let b_2 = {
+        let mut hasher = empty_hasher;
+        for (l, r) in b_0.as_bytes().iter().zip(b_1.as_bytes().iter()) {
+            hasher.update(&[*l ^ *r]);
+        }
+        hasher.update(&[2]);
+        for part in dst_prime.iter() {
+            hasher.update(part);
+        }
+        hasher.finalize()
+    };
+
+    let mut res = [F::zero(); 2];
+    for (b, res) in [b_1, b_2].iter().zip(res.iter_mut()) {
+        // The output of expand_message_xmd is interpreted as a big endian
+        // integer.
+        let mut bytes = [0u8; CHUNKLEN];
+        bytes.copy_from_slice(b.as_bytes());
+        bytes.reverse();
+        *res = F::from_bytes_wide(&bytes);
+    }
+    res
+}
+
+/// Returns whether `a` is "negative", that is, whether its canonical
+/// representative is odd.
+pub(crate) fn sgn0<F: FieldExt>(a: &F) -> subtle::Choice {
+    subtle::Choice::from(a.to_bytes()[0] & 1)
+}
+
+/// The constants of the Shallue-van de Woestijne map to a curve
+/// $y^2 = x^3 + b$, which depend only on the curve.
+#[derive(Clone, Copy, Debug)]
+struct SvdwConstants<F> {
+    z: F,
+    c1: F,
+    c2: F,
+    c3: F,
+    c4: F,
+}
+
+impl<F: FieldExt> SvdwConstants<F> {
+    fn new(b: F) -> Self {
+        let g = |x: F| x.square() * x + b;
+
+        // Find the Z of smallest absolute value, preferring positive values,
+        // that meets the criteria of the specification.
+        let z = (1..)
+            .map(F::from_u64)
+            .flat_map(|i| vec![i, -i])
+            .find(|&z| {
+                let gz = g(z);
+                let three_z2 = z.square() + z.square() + z.square();
+                let h = -three_z2 * (gz + gz + gz + gz).invert().unwrap_or(F::zero());
+                !bool::from(gz.ct_is_zero())
+                    && !bool::from(h.ct_is_zero())
+                    && bool::from(h.sqrt().is_some())
+                    && (bool::from(gz.sqrt().is_some())
+                        || bool::from(g(-z * F::TWO_INV).sqrt().is_some()))
+            })
+            .unwrap();
+
+        let gz = g(z);
+        let three_z2 = z.square() + z.square() + z.square();
+        let c3 = (-gz * three_z2).sqrt().unwrap();
+        SvdwConstants {
+            z,
+            c1: gz,
+            c2: -z * F::TWO_INV,
+            c3: F::conditional_select(&c3, &-c3, sgn0(&c3)),
+            c4: -(gz + gz + gz + gz) * three_z2.invert().unwrap(),
+        }
+    }
+
+    /// Maps `u` to a point $(x, y)$ of the curve $y^2 = x^3 + b$.
+    fn map_to_curve(&self, u: &F, b: F) -> (F, F) {
+        let g = |x: F| x.square() * x + b;
+
+        let tv1 = u.square() * self.c1;
+        let tv2 = F::one() + tv1;
+        let tv1 = F::one() - tv1;
+        let tv3 = (tv1 * tv2).invert().unwrap_or(F::zero());
+        let tv4 = *u * tv1 * tv3 * self.c3;
+
+        let x1 = self.c2 - tv4;
+        let y1 = g(x1).sqrt();
+        let x2 = self.c2 + tv4;
+        let y2 = g(x2).sqrt();
+        let x3 = (tv2.square() * tv3).square() * self.c4 + self.z;
+        // The choice of Z ensures that x3 is on the curve whenever x1 and x2
+        // are not.
+        let y3 = g(x3).sqrt().unwrap_or(F::zero());
+
+        let e1 = y1.is_some();
+        let e2 = y2.is_some() & !e1;
+        let x = F::conditional_select(&x3, &x1, e1);
+        let x = F::conditional_select(&x, &x2, e2);
+        let y = F::conditional_select(&y3, &y1.unwrap_or(F::zero()), e1);
+        let y = F::conditional_select(&y, &y2.unwrap_or(F::zero()), e2);
+
+        let y = F::conditional_select(&-y, &y, sgn0(u).ct_eq(&sgn0(&y)));
+        (x, y)
+    }
+}
+
+/// Returns a function hashing messages to the group `C`, which must be of
+/// prime order, using the Shallue-van de Woestijne map.
+pub(crate) fn hash_to_curve_svdw<'a, C: Curve>(domain_prefix: &'a str) -> impl Fn(&[u8]) -> C + 'a {
+    let b = C::b();
+    let constants = SvdwConstants::new(b);
+    let dst = format!("{}-{}_XMD:BLAKE2b_SVDW_RO_", domain_prefix, C::CURVE_ID);
+
+    move |message| {
+        let us = hash_to_field::<C::Base>(&dst, message);
+        let mut res = C::zero();
+        for u in us.iter() {
+            let (x, y) = constants.map_to_curve(u, b);
+            res += C::Affine::from_xy(x, y).unwrap();
+        }
+        res
+    }
+}
+
+#[test]
+fn test_map_to_curve_svdw() {
+    use crate::bn254::{Fq, G1};
+
+    let b = G1::b();
+    let constants = SvdwConstants::new(b);
+    for u in [Fq::zero(), Fq::one(), -Fq::one(), Fq::rand()].iter() {
+        let (x, y) = constants.map_to_curve(u, b);
+        assert_eq!(y.square(), x.square() * x + b);
+        assert!(bool::from(sgn0(&y).ct_eq(&sgn0(u))));
+    }
+}
+
+#[test]
+fn test_hash_to_field_oversize_dst() {
+    use crate::pasta::Fp;
+
+    let short = "a".repeat(255);
+    let long = "a".repeat(256);
+    let a: [Fp; 2] = hash_to_field(&short, b"message");
+    let b: [Fp; 2] = hash_to_field(&long, b"message");
+    assert!(a != b);
+
+    // The long tag is replaced by H("H2C-OVERSIZE-DST-" || tag).
+    let long_dst = blake2b_simd::Params::new()
+        .hash_length(64)
+        .personal(&[0u8; 16])
+        .to_state()
+        .update(b"H2C-OVERSIZE-DST-")
+        .update(long.as_bytes())
+        .finalize();
+    let c: [Fp; 2] = expand_message_xmd(long_dst.as_bytes(), b"message");
+    assert!(b == c);
+}
@@ -0,0 +1,635 @@
+//! This module implements multi-scalar multiplication using Pippenger's bucket
+//! method.
+//!
+//! Scalars are recoded into signed digits, so that a window of $c$ bits needs
+//! only $2^{c - 1}$ buckets. For large inputs, the points of each bucket are
+//! summed in affine coordinates, sharing a single inversion between all of the
+//! additions of a round. The windows are independent and are processed in
+//! parallel.
+
+use core::cmp;
+use core::ops::Range;
+
+use super::{parallelize, small_multiexp, Curve, CurveAffine, FieldExt};
+use crate::multicore;
+
+/// Below this many terms, the fixed cost of every window outweighs the
+/// savings of the bucket method.
+const SMALL_MULTIEXP_THRESHOLD: usize = 16;
+
+/// Below this many terms, the buckets of a window are too small to amortize
+/// the inversion of each round of affine additions.
+const BATCH_AFFINE_THRESHOLD: usize = 1 << 9;
+
+/// Returns the window size minimizing the approximate number of additions:
+/// each window costs one addition per term and two per bucket.
+fn window_size(num_terms: usize, num_bits: usize) -> usize {
+    (1..=16)
+        .min_by_key(|c| (num_bits / c + 1) * (num_terms + (1 << c)))
+        .unwrap()
+}
+
+/// The largest window of a [`FixedBaseTable`]. Its buckets are reduced only
+/// once rather than once per window, so it can afford larger windows than
+/// [`window_size`] chooses.
+const MAX_FIXED_WINDOW: usize = 20;
+
+/// Returns the window size of a [`FixedBaseTable`] minimizing the approximate
+/// number of additions: each term costs one addition per window, and the
+/// buckets, which all windows share, two each.
+fn fixed_window_size(num_terms: usize, num_bits: usize) -> usize {
+    (1..=MAX_FIXED_WINDOW)
+        .min_by_key(|c| (num_bits / c + 1) * num_terms + (1 << c))
+        .unwrap()
+}
+
+/// Returns `len` bits of the little endian `bytes`, starting from bit `start`.
+fn get_bits(bytes: &[u8; 32], start: usize, len: usize) -> u64 {
+    let skip_bytes = start / 8;
+    if skip_bytes >= 32 {
+        return 0;
+    }
+
+    let mut v = [0; 8];
+    for (v, o) in v.iter_mut().zip(bytes[skip_bytes..].iter()) {
+        *v = *o;
+    }
+
+    (u64::from_le_bytes(v) >> (start % 8)) % (1 << len)
+}
+
+/// Returns the digit of `bytes` at `window` in its signed base-$2^c$
+/// expansion, which lies in $[-2^{c - 1}, 2^{c - 1}]$.
+///
+/// A window borrows $2^c$ from the next one whenever its top bit is set, so
+/// every digit can be computed without looking at the lower windows.
+fn get_digit(bytes: &[u8; 32], window: usize, c: usize) -> i64 {
+    let start = window * c;
+    let carry_in = if start == 0 {
+        0
+    } else {
+        get_bits(bytes, start - 1, 1)
+    };
+    let carry_out = get_bits(bytes, start + c - 1, 1);
+
+    (get_bits(bytes, start, c) + carry_in) as i64 - ((carry_out as i64) << c)
+}
+
+/// A point in affine coordinates, or the point at infinity.
+#[derive(Clone, Copy, Debug)]
+struct Affine<F> {
+    x: F,
+    y: F,
+    infinity: bool,
+}
+
+impl<F: FieldExt> Affine<F> {
+    fn identity() -> Self {
+        Affine {
+            x: F::zero(),
+            y: F::zero(),
+            infinity: true,
+        }
+    }
+
+    /// Returns the value to invert for adding `self` and `other`, which is the
+    /// denominator of the slope of the line through them.
+    fn denominator(&self, other: &Self) -> F {
+        if self.infinity || other.infinity {
+            F::one()
+        } else if self.x != other.x {
+            other.x - self.x
+        } else if self.y == other.y {
+            self.y + self.y
+        } else {
+            F::one()
+        }
+    }
+
+    /// Adds `self` and `other` given the inverse of their `denominator`.
+    fn add(&self, other: &Self, inv: F) -> Self {
+        if self.infinity {
+            return *other;
+        }
+        if other.infinity {
+            return *self;
+        }
+
+        let lambda = if self.x != other.x {
+            (other.y - self.y) * inv
+        } else if self.y == other.y {
+            let x2 = self.x.square();
+            (x2 + x2 + x2) * inv
+        } else {
+            return Affine::identity();
+        };
+
+        let x = lambda.square() - self.x - other.x;
+        let y = lambda * (self.x - x) - self.y;
+        Affine {
+            x,
+            y,
+            infinity: false,
+        }
+    }
+}
+
+/// The bucket sums of one window.
+struct Buckets<F> {
+    sums: Vec<Affine<F>>,
+    // Scratch space, laid out by bucket.
+    offsets: Vec<usize>,
+    lens: Vec<usize>,
+    points: Vec<Affine<F>>,
+    denominators: Vec<F>,
+    products: Vec<F>,
+}
+
+impl<F: FieldExt> Buckets<F> {
+    fn new(num_buckets: usize) -> Self {
+        Buckets {
+            sums: vec![Affine::identity(); num_buckets],
+            offsets: vec![0; num_buckets],
+            lens: vec![0; num_buckets],
+            points: vec![],
+            denominators: vec![],
+            products: vec![],
+        }
+    }
+
+    /// Adds each point to the bucket of its digit, subtracting it instead when
+    /// the digit is negative.
+    fn add_batch(&mut self, digits: &[i64], points: &[Affine<F>]) {
+        // Sort the points by bucket, after the current sum of each bucket.
+        for len in self.lens.iter_mut() {
+            *len = 1;
+        }
+        for digit in digits.iter().filter(|digit| **digit != 0) {
+            self.lens[(digit.abs() - 1) as usize] += 1;
+        }
+        let mut offset = 0;
+        for (start, len) in self.offsets.iter_mut().zip(self.lens.iter()) {
+            *start = offset;
+            offset += len;
+        }
+        self.points.clear();
+        self.points.resize(offset, Affine::identity());
+        let mut next = self.offsets.clone();
+        for (sum, next) in self.sums.iter().zip(next.iter_mut()) {
+            self.points[*next] = *sum;
+            *next += 1;
+        }
+        for (digit, point) in digits.iter().zip(points.iter()) {
+            if *digit != 0 {
+                let bucket = (digit.abs() - 1) as usize;
+                let mut point = *point;
+                if *digit < 0 {
+                    point.y = -point.y;
+                }
+                self.points[next[bucket]] = point;
+                next[bucket] += 1;
+            }
+        }
+
+        // Halve the number of points of every bucket in each round, by adding
+        // them in pairs with one shared inversion.
+        loop {
+            self.denominators.clear();
+            for (start, len) in self.offsets.iter().zip(self.lens.iter()) {
+                let points = &self.points[*start..*start + *len];
+                for pair in points.chunks_exact(2) {
+                    self.denominators.push(pair[0].denominator(&pair[1]));
+                }
+            }
+            if self.denominators.is_empty() {
+                break;
+            }
+
+            // Invert the denominators in a batch, none of which are zero.
+            self.products.clear();
+            let mut acc = F::one();
+            for denominator in self.denominators.iter() {
+                self.products.push(acc);
+                acc *= denominator;
+            }
+            let mut acc = acc.invert().unwrap();
+            for (denominator, product) in
+                self.denominators.iter_mut().zip(self.products.iter()).rev()
+            {
+                let inv = acc * product;
+                acc *= *denominator;
+                *denominator = inv;
+            }
+
+            let mut inverses = self.denominators.iter();
+            for (start, len) in self.offsets.iter().zip(self.lens.iter_mut()) {
+                let points = &mut self.points[*start..*start + *len];
+                for i in 0..*len / 2 {
+                    let inv = *inverses.next().unwrap();
+                    points[i] = points[2 * i].add(&points[2 * i + 1], inv);
+                }
+                if *len % 2 == 1 {
+                    points[*len / 2] = points[*len - 1];
+                }
+                *len -= *len / 2;
+            }
+        }
+
+        for (sum, start) in self.sums.iter_mut().zip(self.offsets.iter()) {
+            *sum = self.points[*start];
+        }
+    }
+
+    /// Returns $\sum_i (\mathsf{offset} + i + 1) S_i$ for the sums $S_i$ of
+    /// the buckets.
+    fn finalize_offset<C: CurveAffine<Base = F>>(&self, offset: usize) -> C::Projective {
+        let (acc, sum) = self.reduce::<C>();
+        acc + &(sum * C::Scalar::from_u64(offset as u64))
+    }
+
+    /// Returns $\sum_i (i + 1) S_i$ for the sums $S_i$ of the buckets.
+    fn finalize<C: CurveAffine<Base = F>>(&self) -> C::Projective {
+        self.reduce::<C>().0
+    }
+
+    /// Returns $\sum_i (i + 1) S_i$ and $\sum_i S_i$.
+    fn reduce<C: CurveAffine<Base = F>>(&self) -> (C::Projective, C::Projective) {
+        // Summation by parts
+        // e.g. 3a + 2b + 1c = a +
+        //                    (a) + b +
+        //                    ((a) + b) + c
+        let mut running_sum = C::Projective::zero();
+        let mut acc = C::Projective::zero();
+        for sum in self.sums.iter().rev() {
+            if !sum.infinity {
+                running_sum += C::from_xy(sum.x, sum.y).unwrap();
+            }
+            acc = acc + &running_sum;
+        }
+        (acc, running_sum)
+    }
+}
+
+/// Returns the sum of the terms at `window` for windows of `c` bits, with
+/// the buckets in projective coordinates.
+fn window_sum_projective<C: CurveAffine>(
+    coeffs: &[[u8; 32]],
+    bases: &[C],
+    window: usize,
+    c: usize,
+) -> C::Projective {
+    let mut buckets = vec![C::Projective::zero(); 1 << (c - 1)];
+    for (coeff, base) in coeffs.iter().zip(bases.iter()) {
+        let digit = get_digit(coeff, window, c);
+        if digit > 0 {
+            buckets[(digit - 1) as usize] += *base;
+        } else if digit < 0 {
+            buckets[(-digit - 1) as usize] -= *base;
+        }
+    }
+
+    let mut running_sum = C::Projective::zero();
+    let mut acc = C::Projective::zero();
+    for bucket in buckets.iter().rev() {
+        running_sum = running_sum + bucket;
+        acc = acc + &running_sum;
+    }
+    acc
+}
+
+/// Returns the sum of the terms at `window` for windows of `c` bits, with
+/// the buckets in affine coordinates.
+fn window_sum<C: CurveAffine>(
+    coeffs: &[[u8; 32]],
+    points: &[Affine<C::Base>],
+    window: usize,
+    c: usize,
+) -> C::Projective {
+    let num_buckets = 1 << (c - 1);
+    // Bound the scratch space, while keeping the rounds large enough that the
+    // inversion is amortized over many additions.
+    let batch_size = cmp::max(1 << 10, 4 * num_buckets);
+
+    let mut buckets = Buckets::new(num_buckets);
+    let mut digits = Vec::with_capacity(batch_size);
+    for (coeffs, points) in coeffs.chunks(batch_size).zip(points.chunks(batch_size)) {
+        digits.clear();
+        digits.extend(coeffs.iter().map(|coeff| get_digit(coeff, window, c)));
+        buckets.add_batch(&digits, points);
+    }
+
+    buckets.finalize::<C>()
+}
+
+/// Performs a multi-exponentiation operation.
+///
+/// This function will panic if coeffs and bases have a different length.
+///
+/// This will use multithreading if beneficial.
+pub fn best_multiexp<C: CurveAffine>(coeffs: &[C::Scalar], bases: &[C]) -> C::Projective {
+    assert_eq!(coeffs.len(), bases.len());
+
+    if coeffs.len() < SMALL_MULTIEXP_THRESHOLD {
+        return small_multiexp(coeffs, bases);
+    }
+
+    let num_bits = <C::Scalar as ff::PrimeField>::NUM_BITS as usize;
+    let c = window_size(coeffs.len(), num_bits);
+    // The top window absorbs the borrow from the one below it.
+    let num_windows = num_bits / c + 1;
+
+    // Affine buckets cannot hold the identity, so those terms are dropped.
+    let batch_affine = coeffs.len() >= BATCH_AFFINE_THRESHOLD;
+    let mut scalars = Vec::with_capacity(coeffs.len());
+    let mut points = vec![];
+    for (coeff, base) in coeffs.iter().zip(bases.iter()) {
+        if !batch_affine {
+            scalars.push(coeff.to_bytes());
+        } else if let Some((x, y)) = Option::from(base.get_xy()) {
+            scalars.push(coeff.to_bytes());
+            points.push(Affine {
+                x,
+                y,
+                infinity: false,
+            });
+        }
+    }
+
+    let mut window_sums = vec![C::Projective::zero(); num_windows];
+    // Each window is a task of its own, which idle threads steal.
+    multicore::scope(|scope| {
+        for (window, window_sum_out) in window_sums.iter_mut().enumerate() {
+            let scalars = &scalars;
+            let points = &points;
+            scope.spawn(move || {
+                *window_sum_out = if batch_affine {
+                    window_sum::<C>(scalars, points, window, c)
+                } else {
+                    window_sum_projective(scalars, bases, window, c)
+                };
+            });
+        }
+    });
+
+    let mut acc = C::Projective::zero();
+    for window_sum in window_sums.iter().rev() {
+        for _ in 0..c {
+            acc = acc.double();
+        }
+        acc = acc + window_sum;
+    }
+    acc
+}
+
+/// Multiples of a list of fixed bases, for multi-exponentiations over those
+/// bases in which every window of the scalars is a separate term.
+///
+/// For windows of $c$ bits, each base $B_i$ is stored along with
+/// $[2^{c j}] B_i$ for every window $j$. A multi-exponentiation then sums all
+/// of the signed digits into a single set of $2^{c - 1}$ buckets, with no
+/// doublings between windows. The buckets are only reduced once, so the
+/// window can be larger than that of a variable-base multi-exponentiation over
+/// as many terms, which makes for fewer additions per term. The table holds
+/// about $(256 / c + 1) n$ points for $n$ bases.
+#[derive(Clone, Debug)]
+pub(crate) struct FixedBaseTable<C: CurveAffine> {
+    c: usize,
+    num_windows: usize,
+    // The multiple of base i for window j is at i * num_windows + j.
+    points: Vec<Affine<C::Base>>,
+}
+
+impl<C: CurveAffine> FixedBaseTable<C> {
+    /// Precomputes the multiples of `bases` for the window that makes a
+    /// multi-exponentiation over all of them cheapest.
+    pub(crate) fn for_bases(bases: &[C]) -> Self {
+        let num_bits = <C::Scalar as ff::PrimeField>::NUM_BITS as usize;
+        let c = fixed_window_size(bases.len(), num_bits);
+        Self::new(bases, c)
+    }
+
+    /// Precomputes the multiples of `bases` for windows of `c` bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `c` is not between 1 and 20.
+    pub(crate) fn new(bases: &[C], c: usize) -> Self {
+        assert!((1..=MAX_FIXED_WINDOW).contains(&c));
+
+        let num_bits = <C::Scalar as ff::PrimeField>::NUM_BITS as usize;
+        // The top window absorbs the borrow from the one below it.
+        let num_windows = num_bits / c + 1;
+
+        let mut multiples = vec![C::Projective::zero(); bases.len() * num_windows];
+        parallelize(&mut multiples, |multiples, start| {
+            let mut acc = C::Projective::zero();
+            for (i, multiple) in (start..).zip(multiples.iter_mut()) {
+                let (base, window) = (i / num_windows, i % num_windows);
+                let doublings = if window == 0 || i == start {
+                    acc = bases[base].to_projective();
+                    c * window
+                } else {
+                    c
+                };
+                for _ in 0..doublings {
+                    acc = acc.double();
+                }
+                *multiple = acc;
+            }
+        });
+
+        let mut affine = vec![C::zero(); multiples.len()];
+        parallelize(&mut affine, |affine, start| {
+            C::Projective::batch_to_affine(&multiples[start..(start + affine.len())], affine);
+        });
+
+        let points = affine
+            .iter()
+            .map(|point| match Option::from(point.get_xy()) {
+                Some((x, y)) => Affine {
+                    x,
+                    y,
+                    infinity: false,
+                },
+                None => Affine::identity(),
+            })
+            .collect();
+
+        FixedBaseTable {
+            c,
+            num_windows,
+            points,
+        }
+    }
+
+    /// Returns the number of bases in the table.
+    pub(crate) fn len(&self) -> usize {
+        self.points.len() / self.num_windows
+    }
+
+    /// Computes $\sum_i a_i B_i$ for the scalars `coeffs` $a_i$, using the
+    /// first `coeffs.len()` bases $B_i$ of the table.
+    ///
+    /// This function will panic if there are more scalars than bases.
+    ///
+    /// This will use multithreading if beneficial. Each thread sums the
+    /// digits of its own range of buckets, so that the buckets are only
+    /// reduced once.
+    pub(crate) fn multiexp(&self, coeffs: &[C::Scalar]) -> C::Projective {
+        assert!(coeffs.len() <= self.len());
+
+        if coeffs.is_empty() {
+            return C::Projective::zero();
+        }
+
+        let mut digits = vec![0; coeffs.len() * self.num_windows];
+        parallelize(&mut digits, |digits, start| {
+            for (i, digit) in (start..).zip(digits.iter_mut()) {
+                let (coeff, window) = (i / self.num_windows, i % self.num_windows);
+                *digit = get_digit(&coeffs[coeff].to_bytes(), window, self.c);
+            }
+        });
+
+        let num_buckets = 1 << (self.c - 1);
+        let num_ranges = cmp::min(num_buckets, multicore::current_num_threads());
+        let range = (num_buckets - 1) / num_ranges + 1;
+
+        let mut results = vec![C::Projective::zero(); (num_buckets - 1) / range + 1];
+        multicore::scope(|scope| {
+            for (i, acc) in results.iter_mut().enumerate() {
+                let digits = &digits;
+                let buckets = (i * range)..cmp::min(num_buckets, (i + 1) * range);
+                scope.spawn(move || {
+                    *acc = self.bucket_range_sum(digits, buckets);
+                });
+            }
+        });
+
+        results.iter().fold(C::Projective::zero(), |a, b| a + b)
+    }
+
+    /// Returns the sum of the terms whose digits fall in the buckets of
+    /// `range`, which hold the digits of absolute value `range.start + 1` to
+    /// `range.end`.
+    fn bucket_range_sum(&self, digits: &[i64], range: Range<usize>) -> C::Projective {
+        let offset = range.start as i64;
+        let num_buckets = range.len();
+        // As in `window_sum`, keep the rounds large enough to amortize the
+        // inversion, with about four digits per bucket.
+        let batch_size = cmp::max(1 << 10, 4 * (1 << (self.c - 1)));
+
+        let mut buckets = Buckets::new(num_buckets);
+        let mut range_digits = Vec::with_capacity(batch_size);
+        for (digits, points) in digits
+            .chunks(batch_size)
+            .zip(self.points.chunks(batch_size))
+        {
+            range_digits.clear();
+            range_digits.extend(digits.iter().map(|digit| {
+                let bucket = digit.abs() - 1 - offset;
+                if bucket >= 0 && bucket < num_buckets as i64 {
+                    digit.signum() * (bucket + 1)
+                } else {
+                    0
+                }
+            }));
+            buckets.add_batch(&range_digits, points);
+        }
+
+        buckets.finalize_offset::<C>(range.start)
+    }
+}
+
+#[test]
+fn test_get_digit() {
+    use crate::pasta::Fp;
+
+    for c in [1, 2, 5, 13, 16, 20].iter() {
+        let k = Fp::rand();
+        let bytes = k.to_bytes();
+        let base = Fp::from_u64(1 << c);
+        let mut acc = Fp::zero();
+        for window in (0..(255 / c + 1)).rev() {
+            let digit = get_digit(&bytes, window, *c);
+            assert!(digit.abs() <= 1 << (c - 1));
+            let digit_field = Fp::from_u64(digit.unsigned_abs());
+            acc = acc * base + if digit < 0 { -digit_field } else { digit_field };
+        }
+        assert_eq!(acc, k);
+    }
+}
+
+#[test]
+fn test_fixed_window_size() {
+    // With the window chosen from the number of terms, a fixed-base table
+    // must need fewer additions than the variable-base multiexp it replaces.
+    let num_bits = 255;
+    for k in 10..=20 {
+        let n = 1 << k;
+        let c = window_size(n, num_bits);
+        let variable = (num_bits / c + 1) * (n + (1 << c));
+        let c = fixed_window_size(n, num_bits);
+        let fixed = (num_bits / c + 1) * n + (1 << c);
+        assert!(fixed * 10 < variable * 9, "k = {}", k);
+    }
+}
+
+#[test]
+fn test_best_multiexp() {
+    use crate::pasta::{Ep, EpAffine, Fq};
+
+    for n in [0, 1, 15, 16, 100, 1000].iter() {
+        let mut bases = vec![EpAffine::zero(); *n];
+        let points: Vec<_> = (0..*n).map(|_| Ep::one() * Fq::rand()).collect();
+        Ep::batch_to_affine(&points, &mut bases);
+        let mut coeffs: Vec<_> = (0..*n).map(|_| Fq::rand()).collect();
+
+        // Exercise the exceptional cases of affine addition.
+        if *n >= 100 {
+            bases[1] = bases[0];
+            bases[2] = -bases[0];
+            bases[3] = EpAffine::zero();
+            coeffs[1] = coeffs[0];
+            coeffs[2] = coeffs[0];
+            coeffs[4] = Fq::zero();
+            coeffs[5] = -Fq::one();
+        }
+
+        let expected = coeffs
+            .iter()
+            .zip(bases.iter())
+            .fold(Ep::zero(), |acc, (coeff, base)| acc + *base * *coeff);
+        assert_eq!(best_multiexp(&coeffs, &bases), expected);
+    }
+}
+
+#[test]
+fn test_fixed_base_table() {
+    use crate::pasta::{Ep, EpAffine, Fq};
+
+    let n = 100;
+    let mut bases = vec![EpAffine::zero(); n];
+    let points: Vec<_> = (0..n).map(|_| Ep::one() * Fq::rand()).collect();
+    Ep::batch_to_affine(&points, &mut bases);
+    bases[1] = -bases[0];
+    bases[2] = EpAffine::zero();
+
+    for c in [1, 4, 9, 17].iter() {
+        let table = FixedBaseTable::new(&bases, *c);
+        assert_eq!(table.len(), n);
+
+        for len in [0, 1, 50, n].iter() {
+            let mut coeffs: Vec<_> = (0..*len).map(|_| Fq::rand()).collect();
+            if *len == n {
+                coeffs[1] = coeffs[0];
+                coeffs[3] = Fq::zero();
+                coeffs[4] = -Fq::one();
+            }
+
+            assert_eq!(
+                table.multiexp(&coeffs),
+                best_multiexp(&coeffs, &bases[..*len])
+            );
+        }
+    }
+}
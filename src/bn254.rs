@@ -0,0 +1,126 @@
+//! This module contains implementations for the BN254 curve (also known as
+//! `alt_bn128`), its pairing-friendly extension fields and its optimal ate
+//! pairing, as used by Ethereum's precompiled contracts.
+//!
+//! BN254 is the curve $y^2 = x^3 + 3$ over the field $\mathbb{F}_q$, with a
+//! subgroup $\mathbb{G}_1$ of prime order $r$ equal to the whole group. Its
+//! scalar field $\mathbb{F}_r$ has a $2^{28}$-th root of unity, so circuits
+//! over it can be proven with [`G1Affine`] as the commitment curve, using
+//! either the inner product argument or the [KZG](kzg) commitment scheme.
+
+mod curves;
+mod fields;
+pub mod kzg;
+mod pairing;
+
+pub use curves::*;
+pub use fields::*;
+pub use pairing::*;
+
+#[test]
+fn test_endo_consistency() {
+    use crate::arithmetic::{Curve, FieldExt};
+
+    let a = G1::one();
+    assert_eq!(a * Fr::ZETA, a.endo());
+}
+
+#[test]
+fn test_curve() {
+    crate::arithmetic::test_curve::<G1>();
+}
+
+#[test]
+fn test_proving() {
+    use crate::arithmetic::{Curve, CurveAffine, FieldExt};
+    use crate::multicore::ThreadPool;
+    use crate::plonk::{
+        keygen, Advice, Assignment, Aux, Circuit, Column, ConstraintSystem, Error, Fixed, Proof,
+    };
+    use crate::poly::commitment::{self, Blind};
+    use crate::poly::scheme::{CommitmentSchemeVerifier, SchemeMSM};
+    use crate::transcript::DummyHash;
+    use rand::rngs::OsRng;
+
+    const K: u32 = 4;
+
+    // Constrains the advice column to square to the aux column.
+    struct MyCircuit {
+        value: Option<Fr>,
+    }
+
+    impl Circuit<Fr> for MyCircuit {
+        type Config = (Column<Advice>, Column<Aux>, Column<Fixed>);
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let a = meta.advice_column();
+            let p = meta.aux_column();
+            let f = meta.fixed_column();
+
+            meta.create_gate(|meta| {
+                let a = meta.query_advice(a, 0);
+                let p = meta.query_aux(p, 0);
+                let f = meta.query_fixed(f, 0);
+
+                f * (a.clone() * a - p)
+            });
+
+            (a, p, f)
+        }
+
+        fn synthesize(
+            &self,
+            cs: &mut impl Assignment<Fr>,
+            (a, _, f): Self::Config,
+        ) -> Result<(), Error> {
+            for row in 0..(1 << K) {
+                cs.assign_advice(a, row, || self.value.ok_or(Error::SynthesisError))?;
+                cs.assign_fixed(f, row, || Ok(Fr::one()))?;
+            }
+            Ok(())
+        }
+    }
+
+    // Proves and verifies the circuit with the commitment scheme `params`.
+    fn prove_and_verify<P>(params: &P)
+    where
+        P: for<'a> CommitmentSchemeVerifier<'a, G1Affine>,
+    {
+        let pk = keygen(params, &MyCircuit { value: None }).expect("keygen should not fail");
+
+        let value = Fr::rand();
+        let mut aux = pk.get_vk().get_domain().empty_lagrange();
+        for cell in aux.iter_mut() {
+            *cell = value.square();
+        }
+        let proof = Proof::create::<DummyHash<Fq>, DummyHash<Fr>, _, _, _>(
+            params,
+            &pk,
+            &[MyCircuit { value: Some(value) }],
+            &[&[aux.clone()]],
+            &ThreadPool::global(),
+            OsRng,
+        )
+        .expect("proof generation should not fail");
+
+        let verify = |aux_commitment: G1Affine| {
+            let aux_commitments = &[&[aux_commitment][..]];
+            proof
+                .verify::<DummyHash<Fq>, DummyHash<Fr>, _>(
+                    params,
+                    pk.get_vk(),
+                    params.empty_msm(),
+                    aux_commitments,
+                    &[],
+                )
+                .map(|guard| P::finalize(guard).eval())
+                .unwrap_or(false)
+        };
+        let aux_commitment = params.commit_lagrange(&aux, Blind::default()).to_affine();
+        assert!(verify(aux_commitment));
+        assert!(!verify((aux_commitment + G1Affine::one()).to_affine()));
+    }
+
+    prove_and_verify(&commitment::Params::<G1Affine>::new(K));
+    prove_and_verify(&kzg::Params::setup(K, OsRng));
+}
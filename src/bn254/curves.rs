@@ -0,0 +1,603 @@
+//! This module contains implementations for the groups $\mathbb{G}_1$ and
+//! $\mathbb{G}_2$ of the BN254 curve.
+
+use core::cmp;
+use core::ops::{Add, Mul, Neg, Sub};
+use ff::Field;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use super::{Fq, Fq2, Fr};
+use crate::arithmetic::{Curve, CurveAffine, FieldExt, Group};
+
+new_curve_impl!(
+    G1,
+    G1Affine,
+    Fq,
+    Fr,
+    [u8; 32],
+    "bn254",
+    // b = 3
+    Fq::from_raw([3, 0, 0, 0]),
+    // (1, 2) is a point of the curve.
+    (Fq::one(), Fq::from_raw([2, 0, 0, 0]))
+);
+
+/// The constant $b' = 3 / \xi$ of the sextic twist $y^2 = x^3 + b'$ over
+/// $\mathbb{F}_{q^2}$, on which $\mathbb{G}_2$ is defined.
+const G2_B: Fq2 = Fq2 {
+    c0: Fq::from_raw([
+        0x3267e6dc24a138e5,
+        0xb5b4c5e559dbefa3,
+        0x81be18991be06ac3,
+        0x2b149d40ceb8aaae,
+    ]),
+    c1: Fq::from_raw([
+        0xe4a2bd0685c315d2,
+        0xa74fa084e52d1852,
+        0xcd2cafadeed8fdf4,
+        0x009713b03af0fed4,
+    ]),
+};
+
+/// The generator of $\mathbb{G}_2$ used by Ethereum's `alt_bn128` precompiles.
+const G2_GENERATOR_X: Fq2 = Fq2 {
+    c0: Fq::from_raw([
+        0x46debd5cd992f6ed,
+        0x674322d4f75edadd,
+        0x426a00665e5c4479,
+        0x1800deef121f1e76,
+    ]),
+    c1: Fq::from_raw([
+        0x97e485b7aef312c2,
+        0xf1aa493335a9e712,
+        0x7260bfb731fb5d25,
+        0x198e9393920d483a,
+    ]),
+};
+const G2_GENERATOR_Y: Fq2 = Fq2 {
+    c0: Fq::from_raw([
+        0x4ce6cc0166fa7daa,
+        0xe3d1e7690c43d37b,
+        0x4aab71808dcb408f,
+        0x12c85ea5db8c6deb,
+    ]),
+    c1: Fq::from_raw([
+        0x55acdadcd122975b,
+        0xbc4b313370b38ef3,
+        0xec9e99ad690c3395,
+        0x090689d0585ff075,
+    ]),
+};
+
+/// A point of $\mathbb{G}_2$ in the affine coordinate space (or the point at
+/// infinity), on the sextic twist $y^2 = x^3 + 3 / \xi$ of BN254 over
+/// $\mathbb{F}_{q^2}$.
+#[derive(Copy, Clone, Debug)]
+pub struct G2Affine {
+    pub(crate) x: Fq2,
+    pub(crate) y: Fq2,
+    pub(crate) infinity: Choice,
+}
+
+/// A point of $\mathbb{G}_2$ in the Jacobian projective coordinate space.
+#[derive(Copy, Clone, Debug)]
+pub struct G2 {
+    x: Fq2,
+    y: Fq2,
+    z: Fq2,
+}
+
+impl G2Affine {
+    /// Returns the point at infinity.
+    pub fn zero() -> Self {
+        G2Affine {
+            x: Fq2::zero(),
+            y: Fq2::zero(),
+            infinity: Choice::from(1u8),
+        }
+    }
+
+    /// Returns the fixed generator of $\mathbb{G}_2$.
+    pub fn one() -> Self {
+        G2Affine {
+            x: G2_GENERATOR_X,
+            y: G2_GENERATOR_Y,
+            infinity: Choice::from(0u8),
+        }
+    }
+
+    /// Returns true if this point is the point at infinity.
+    pub fn is_zero(&self) -> Choice {
+        self.infinity
+    }
+
+    /// Returns true if this point is on the twist. This does not check that
+    /// the point is in $\mathbb{G}_2$; see [`G2Affine::is_torsion_free`].
+    pub fn is_on_curve(&self) -> Choice {
+        // y^2 - x^3 ?= b'
+        (self.y.square() - (self.x.square() * self.x)).ct_eq(&G2_B) | self.infinity
+    }
+
+    /// Returns true if this point has order dividing $r$, i.e. it is in
+    /// $\mathbb{G}_2$ if it is on the twist.
+    pub fn is_torsion_free(&self) -> Choice {
+        self.to_projective().is_torsion_free()
+    }
+
+    /// Obtains a point given its $(x, y)$ coordinates, failing if it is not
+    /// in $\mathbb{G}_2$.
+    pub fn from_xy(x: Fq2, y: Fq2) -> CtOption<Self> {
+        let p = G2Affine {
+            x,
+            y,
+            infinity: Choice::from(0u8),
+        };
+        CtOption::new(p, p.is_on_curve() & p.is_torsion_free())
+    }
+
+    /// Gets the $(x, y)$ coordinates of this point, or `None` if it is the
+    /// point at infinity.
+    pub fn get_xy(&self) -> CtOption<(Fq2, Fq2)> {
+        CtOption::new((self.x, self.y), !self.is_zero())
+    }
+
+    /// Converts this point to the projective coordinate space.
+    pub fn to_projective(&self) -> G2 {
+        G2 {
+            x: self.x,
+            y: self.y,
+            z: Fq2::conditional_select(&Fq2::one(), &Fq2::zero(), self.infinity),
+        }
+    }
+
+    /// Attempts to obtain a point from its uncompressed encoding, the
+    /// coefficients of $x$ followed by those of $y$, failing if it is not in
+    /// $\mathbb{G}_2$. The point at infinity is encoded as zeroes.
+    pub fn from_bytes_wide(bytes: &[u8; 128]) -> CtOption<Self> {
+        let mut coeffs = [Fq::zero(); 4];
+        let mut canonical = Choice::from(1u8);
+        for (coeff, bytes) in coeffs.iter_mut().zip(bytes.chunks(32)) {
+            let mut tmp = [0u8; 32];
+            tmp.copy_from_slice(bytes);
+            let c = Fq::from_bytes(&tmp);
+            canonical &= c.is_some();
+            *coeff = c.unwrap_or(Fq::zero());
+        }
+        let x = Fq2 {
+            c0: coeffs[0],
+            c1: coeffs[1],
+        };
+        let y = Fq2 {
+            c0: coeffs[2],
+            c1: coeffs[3],
+        };
+
+        // (0, 0) is not on the twist, so it is free to encode infinity.
+        let is_zero = x.ct_eq(&Fq2::zero()) & y.ct_eq(&Fq2::zero());
+        CtOption::new(Self::zero(), is_zero)
+            .or_else(|| Self::from_xy(x, y))
+            .and_then(|p| CtOption::new(p, canonical))
+    }
+
+    /// Returns the uncompressed encoding of this point, as read by
+    /// [`G2Affine::from_bytes_wide`].
+    pub fn to_bytes_wide(&self) -> [u8; 128] {
+        let mut res = [0u8; 128];
+        if !bool::from(self.is_zero()) {
+            for (bytes, coeff) in res
+                .chunks_mut(32)
+                .zip([self.x.c0, self.x.c1, self.y.c0, self.y.c1].iter())
+            {
+                bytes.copy_from_slice(&coeff.to_bytes());
+            }
+        }
+        res
+    }
+}
+
+impl G2 {
+    /// Returns the point at infinity.
+    pub fn zero() -> Self {
+        G2 {
+            x: Fq2::zero(),
+            y: Fq2::zero(),
+            z: Fq2::zero(),
+        }
+    }
+
+    /// Returns the fixed generator of $\mathbb{G}_2$.
+    pub fn one() -> Self {
+        G2Affine::one().to_projective()
+    }
+
+    /// Returns true if this point is the point at infinity.
+    pub fn is_zero(&self) -> Choice {
+        self.z.is_zero()
+    }
+
+    /// Returns true if this point is on the twist.
+    pub fn is_on_curve(&self) -> Choice {
+        // Y^2 - X^3 = b'(Z^6)
+        (self.y.square() - (self.x.square() * self.x))
+            .ct_eq(&((self.z.square() * self.z).square() * G2_B))
+            | self.z.is_zero()
+    }
+
+    /// Returns true if this point has order dividing $r$.
+    pub fn is_torsion_free(&self) -> Choice {
+        // [r - 1] P + P is the identity iff [r] P is.
+        (self * -Fr::one() + self).is_zero()
+    }
+
+    /// Doubles this point.
+    pub fn double(&self) -> Self {
+        // http://www.hyperelliptic.org/EFD/g1p/auto-shortw-jacobian-0.html#doubling-dbl-2009-l
+        //
+        // There are no points of order 2.
+
+        let a = self.x.square();
+        let b = self.y.square();
+        let c = b.square();
+        let d = self.x + b;
+        let d = d.square();
+        let d = d - a - c;
+        let d = d + d;
+        let e = a + a + a;
+        let f = e.square();
+        let z3 = self.z * self.y;
+        let z3 = z3 + z3;
+        let x3 = f - (d + d);
+        let c = c + c;
+        let c = c + c;
+        let c = c + c;
+        let y3 = e * (d - x3) - c;
+
+        let tmp = G2 {
+            x: x3,
+            y: y3,
+            z: z3,
+        };
+
+        G2::conditional_select(&tmp, &G2::zero(), self.is_zero())
+    }
+
+    /// Converts this point to the affine coordinate space.
+    pub fn to_affine(&self) -> G2Affine {
+        let zinv = self.z.invert().unwrap_or(Fq2::zero());
+        let zinv2 = zinv.square();
+        let x = self.x * zinv2;
+        let zinv3 = zinv2 * zinv;
+        let y = self.y * zinv3;
+
+        let tmp = G2Affine {
+            x,
+            y,
+            infinity: Choice::from(0u8),
+        };
+
+        G2Affine::conditional_select(&tmp, &G2Affine::zero(), zinv.is_zero())
+    }
+}
+
+impl<'a> From<&'a G2Affine> for G2 {
+    fn from(p: &'a G2Affine) -> G2 {
+        p.to_projective()
+    }
+}
+
+impl From<G2Affine> for G2 {
+    fn from(p: G2Affine) -> G2 {
+        p.to_projective()
+    }
+}
+
+impl<'a> From<&'a G2> for G2Affine {
+    fn from(p: &'a G2) -> G2Affine {
+        p.to_affine()
+    }
+}
+
+impl From<G2> for G2Affine {
+    fn from(p: G2) -> G2Affine {
+        p.to_affine()
+    }
+}
+
+impl Default for G2 {
+    fn default() -> G2 {
+        G2::zero()
+    }
+}
+
+impl Default for G2Affine {
+    fn default() -> G2Affine {
+        G2Affine::zero()
+    }
+}
+
+impl ConstantTimeEq for G2 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        // Is (xz^2, yz^3, z) equal to (x'z'^2, yz'^3, z') when converted to affine?
+
+        let z = other.z.square();
+        let x1 = self.x * z;
+        let z = z * other.z;
+        let y1 = self.y * z;
+        let z = self.z.square();
+        let x2 = other.x * z;
+        let z = z * self.z;
+        let y2 = other.y * z;
+
+        let self_is_zero = self.is_zero();
+        let other_is_zero = other.is_zero();
+
+        (self_is_zero & other_is_zero) // Both point at infinity
+            | ((!self_is_zero) & (!other_is_zero) & x1.ct_eq(&x2) & y1.ct_eq(&y2))
+        // Neither point at infinity, coordinates are the same
+    }
+}
+
+impl PartialEq for G2 {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl cmp::Eq for G2 {}
+
+impl ConstantTimeEq for G2Affine {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let z1 = self.infinity;
+        let z2 = other.infinity;
+
+        (z1 & z2) | ((!z1) & (!z2) & (self.x.ct_eq(&other.x)) & (self.y.ct_eq(&other.y)))
+    }
+}
+
+impl PartialEq for G2Affine {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl cmp::Eq for G2Affine {}
+
+impl ConditionallySelectable for G2 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        G2 {
+            x: Fq2::conditional_select(&a.x, &b.x, choice),
+            y: Fq2::conditional_select(&a.y, &b.y, choice),
+            z: Fq2::conditional_select(&a.z, &b.z, choice),
+        }
+    }
+}
+
+impl ConditionallySelectable for G2Affine {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        G2Affine {
+            x: Fq2::conditional_select(&a.x, &b.x, choice),
+            y: Fq2::conditional_select(&a.y, &b.y, choice),
+            infinity: Choice::conditional_select(&a.infinity, &b.infinity, choice),
+        }
+    }
+}
+
+impl<'a> Neg for &'a G2 {
+    type Output = G2;
+
+    fn neg(self) -> G2 {
+        G2 {
+            x: self.x,
+            y: -self.y,
+            z: self.z,
+        }
+    }
+}
+
+impl Neg for G2 {
+    type Output = G2;
+
+    fn neg(self) -> G2 {
+        -&self
+    }
+}
+
+impl<'a> Neg for &'a G2Affine {
+    type Output = G2Affine;
+
+    fn neg(self) -> G2Affine {
+        G2Affine {
+            x: self.x,
+            y: -self.y,
+            infinity: self.infinity,
+        }
+    }
+}
+
+impl Neg for G2Affine {
+    type Output = G2Affine;
+
+    fn neg(self) -> G2Affine {
+        -&self
+    }
+}
+
+impl<'a, 'b> Add<&'a G2> for &'b G2 {
+    type Output = G2;
+
+    fn add(self, rhs: &'a G2) -> G2 {
+        if bool::from(self.is_zero()) {
+            *rhs
+        } else if bool::from(rhs.is_zero()) {
+            *self
+        } else {
+            let z1z1 = self.z.square();
+            let z2z2 = rhs.z.square();
+            let u1 = self.x * z2z2;
+            let u2 = rhs.x * z1z1;
+            let s1 = self.y * z2z2 * rhs.z;
+            let s2 = rhs.y * z1z1 * self.z;
+
+            if u1 == u2 {
+                if s1 == s2 {
+                    self.double()
+                } else {
+                    G2::zero()
+                }
+            } else {
+                let h = u2 - u1;
+                let i = (h + h).square();
+                let j = h * i;
+                let r = s2 - s1;
+                let r = r + r;
+                let v = u1 * i;
+                let x3 = r.square() - j - v - v;
+                let s1 = s1 * j;
+                let s1 = s1 + s1;
+                let y3 = r * (v - x3) - s1;
+                let z3 = (self.z + rhs.z).square() - z1z1 - z2z2;
+                let z3 = z3 * h;
+
+                G2 {
+                    x: x3,
+                    y: y3,
+                    z: z3,
+                }
+            }
+        }
+    }
+}
+
+impl<'a, 'b> Add<&'a G2Affine> for &'b G2 {
+    type Output = G2;
+
+    fn add(self, rhs: &'a G2Affine) -> G2 {
+        self + rhs.to_projective()
+    }
+}
+
+impl<'a, 'b> Sub<&'a G2> for &'b G2 {
+    type Output = G2;
+
+    fn sub(self, other: &'a G2) -> G2 {
+        self + (-other)
+    }
+}
+
+impl<'a, 'b> Sub<&'a G2Affine> for &'b G2 {
+    type Output = G2;
+
+    fn sub(self, other: &'a G2Affine) -> G2 {
+        self + (-other)
+    }
+}
+
+impl<'a, 'b> Mul<&'b Fr> for &'a G2 {
+    type Output = G2;
+
+    fn mul(self, other: &'b Fr) -> Self::Output {
+        let mut acc = G2::zero();
+
+        // This is a simple double-and-add implementation of point
+        // multiplication, moving from most significant to least
+        // significant bit of the scalar.
+        //
+        // NOTE: We skip the leading bit because it's always unset.
+        for bit in other
+            .to_bytes()
+            .iter()
+            .rev()
+            .flat_map(|byte| (0..8).rev().map(move |i| Choice::from((byte >> i) & 1u8)))
+            .skip(1)
+        {
+            acc = acc.double();
+            acc = G2::conditional_select(&acc, &(acc + self), bit);
+        }
+
+        acc
+    }
+}
+
+impl<'a, 'b> Mul<&'b Fr> for &'a G2Affine {
+    type Output = G2;
+
+    fn mul(self, other: &'b Fr) -> Self::Output {
+        self.to_projective() * other
+    }
+}
+
+impl_binops_additive!(G2, G2);
+impl_binops_additive!(G2, G2Affine);
+impl_binops_multiplicative!(G2, Fr);
+impl_binops_multiplicative_mixed!(G2Affine, Fr, G2);
+
+impl Group for G2 {
+    type Scalar = Fr;
+
+    fn group_zero() -> Self {
+        Self::zero()
+    }
+    fn group_add(&mut self, rhs: &Self) {
+        *self = *self + *rhs;
+    }
+    fn group_sub(&mut self, rhs: &Self) {
+        *self = *self - *rhs;
+    }
+    fn group_scale(&mut self, by: &Self::Scalar) {
+        *self = *self * (*by);
+    }
+}
+
+#[test]
+fn test_g2() {
+    let g = G2::one();
+    assert!(bool::from(G2Affine::one().is_on_curve()));
+    assert!(bool::from(g.is_on_curve()));
+    assert!(bool::from(g.is_torsion_free()));
+    assert!(!bool::from(g.is_zero()));
+
+    let a = Fr::rand();
+    let b = Fr::rand();
+    assert_eq!(g * a + g * b, g * (a + b));
+    assert_eq!((g * a) * b, g * (a * b));
+    assert_eq!(g + g, g.double());
+    assert_eq!(g - g, G2::zero());
+    assert!(bool::from((g * a).is_on_curve()));
+    assert_eq!((g * a).to_affine().to_projective(), g * a);
+    assert_eq!(G2::zero().to_affine(), G2Affine::zero());
+
+    // The twist has a large cofactor, so points on it need not be in G_2.
+    let x = Fq2::one();
+    let y = Fq2 {
+        c0: Fq::from_raw([
+            0xab4b871c0531f1bb,
+            0xaadd70e52c9830e9,
+            0xf8e2728fdb825a51,
+            0x2869111d5381f072,
+        ]),
+        c1: Fq::from_raw([
+            0x1b7f8da82de048a4,
+            0x998c7f790cb4d751,
+            0x36846e70a1934187,
+            0x0d1271953ed9ea08,
+        ]),
+    };
+    let p = G2Affine {
+        x,
+        y,
+        infinity: Choice::from(0u8),
+    };
+    assert!(bool::from(p.is_on_curve()));
+    assert!(!bool::from(p.is_torsion_free()));
+    assert!(bool::from(G2Affine::from_xy(x, y).is_none()));
+    assert!(bool::from(
+        G2Affine::from_bytes_wide(&p.to_bytes_wide()).is_none()
+    ));
+
+    for p in [G2Affine::zero(), (g * a).to_affine()].iter() {
+        assert_eq!(G2Affine::from_bytes_wide(&p.to_bytes_wide()).unwrap(), *p);
+    }
+    let mut bytes = (g * a).to_affine().to_bytes_wide();
+    bytes[31] = 0xff;
+    assert!(bool::from(G2Affine::from_bytes_wide(&bytes).is_none()));
+}
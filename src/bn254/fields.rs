@@ -0,0 +1,34 @@
+//! This module contains implementations for the two prime fields of the BN254
+//! curve, and the tower of extension fields of its base field used by the
+//! pairing.
+
+mod fq;
+mod fq12;
+mod fq2;
+mod fq6;
+mod fr;
+
+pub use fq::*;
+pub use fq12::*;
+pub use fq2::*;
+pub use fq6::Fq6;
+pub(crate) use fq6::FROBENIUS_COEFFS;
+pub use fr::*;
+
+#[cfg(test)]
+use ff::{Field, PrimeField};
+
+#[cfg(test)]
+use crate::arithmetic::FieldExt;
+
+#[test]
+fn test_extract() {
+    let a = Fr::rand();
+    let a = a.square();
+    let (t, s) = a.extract_radix2_vartime().unwrap();
+    assert_eq!(
+        t.pow_vartime(&[1 << Fr::S, 0, 0, 0]) * Fr::ROOT_OF_UNITY.pow_vartime(&[s, 0, 0, 0]),
+        a
+    );
+    assert_eq!(a.deterministic_sqrt().unwrap().square(), a);
+}
@@ -0,0 +1,280 @@
+use core::ops::{Add, Mul, Neg, Sub};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use super::fq6::{Fq6, FROBENIUS_COEFFS};
+
+/// An element of $\mathbb{F}_{q^{12}} = \mathbb{F}_{q^6}\[w\] / (w^2 - v)$,
+/// represented as `c0 + c1 * w`. The pairing takes its values in the subgroup
+/// of order $r$ of the multiplicative group of this field.
+#[derive(Copy, Clone, Debug, Eq)]
+pub struct Fq12 {
+    /// The constant coefficient.
+    pub c0: Fq6,
+    /// The coefficient of `w`.
+    pub c1: Fq6,
+}
+
+impl ConstantTimeEq for Fq12 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.c0.ct_eq(&other.c0) & self.c1.ct_eq(&other.c1)
+    }
+}
+
+impl PartialEq for Fq12 {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl ConditionallySelectable for Fq12 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Fq12 {
+            c0: Fq6::conditional_select(&a.c0, &b.c0, choice),
+            c1: Fq6::conditional_select(&a.c1, &b.c1, choice),
+        }
+    }
+}
+
+impl Default for Fq12 {
+    fn default() -> Self {
+        Fq12::zero()
+    }
+}
+
+impl<'a> Neg for &'a Fq12 {
+    type Output = Fq12;
+
+    #[inline]
+    fn neg(self) -> Fq12 {
+        self.neg()
+    }
+}
+
+impl Neg for Fq12 {
+    type Output = Fq12;
+
+    #[inline]
+    fn neg(self) -> Fq12 {
+        -&self
+    }
+}
+
+impl<'a, 'b> Sub<&'b Fq12> for &'a Fq12 {
+    type Output = Fq12;
+
+    #[inline]
+    fn sub(self, rhs: &'b Fq12) -> Fq12 {
+        self.sub(rhs)
+    }
+}
+
+impl<'a, 'b> Add<&'b Fq12> for &'a Fq12 {
+    type Output = Fq12;
+
+    #[inline]
+    fn add(self, rhs: &'b Fq12) -> Fq12 {
+        self.add(rhs)
+    }
+}
+
+impl<'a, 'b> Mul<&'b Fq12> for &'a Fq12 {
+    type Output = Fq12;
+
+    #[inline]
+    fn mul(self, rhs: &'b Fq12) -> Fq12 {
+        self.mul(rhs)
+    }
+}
+
+impl_binops_additive!(Fq12, Fq12);
+impl_binops_multiplicative!(Fq12, Fq12);
+
+impl Fq12 {
+    /// Returns zero, the additive identity.
+    pub const fn zero() -> Fq12 {
+        Fq12 {
+            c0: Fq6::zero(),
+            c1: Fq6::zero(),
+        }
+    }
+
+    /// Returns one, the multiplicative identity.
+    pub const fn one() -> Fq12 {
+        Fq12 {
+            c0: Fq6::one(),
+            c1: Fq6::zero(),
+        }
+    }
+
+    /// Returns true iff this element is zero.
+    pub fn is_zero(&self) -> Choice {
+        self.c0.is_zero() & self.c1.is_zero()
+    }
+
+    /// Adds `rhs` to `self`, returning the result.
+    pub fn add(&self, rhs: &Self) -> Self {
+        Fq12 {
+            c0: self.c0.add(&rhs.c0),
+            c1: self.c1.add(&rhs.c1),
+        }
+    }
+
+    /// Subtracts `rhs` from `self`, returning the result.
+    pub fn sub(&self, rhs: &Self) -> Self {
+        Fq12 {
+            c0: self.c0.sub(&rhs.c0),
+            c1: self.c1.sub(&rhs.c1),
+        }
+    }
+
+    /// Negates `self`.
+    pub fn neg(&self) -> Self {
+        Fq12 {
+            c0: self.c0.neg(),
+            c1: self.c1.neg(),
+        }
+    }
+
+    /// Multiplies `rhs` by `self`, returning the result.
+    pub fn mul(&self, rhs: &Self) -> Self {
+        // Karatsuba multiplication, using w^2 = v.
+        let v0 = self.c0.mul(&rhs.c0);
+        let v1 = self.c1.mul(&rhs.c1);
+        let c1 = self
+            .c0
+            .add(&self.c1)
+            .mul(&rhs.c0.add(&rhs.c1))
+            .sub(&v0)
+            .sub(&v1);
+
+        Fq12 {
+            c0: v0.add(&v1.mul_by_nonresidue()),
+            c1,
+        }
+    }
+
+    /// Squares this element.
+    pub fn square(&self) -> Self {
+        // (a0 + a1 w)^2 = (a0 + a1)(a0 + v a1) - (1 + v) a0 a1 + 2 a0 a1 w
+        let ab = self.c0.mul(&self.c1);
+        let c0 = self
+            .c0
+            .add(&self.c1)
+            .mul(&self.c0.add(&self.c1.mul_by_nonresidue()))
+            .sub(&ab)
+            .sub(&ab.mul_by_nonresidue());
+
+        Fq12 {
+            c0,
+            c1: ab.add(&ab),
+        }
+    }
+
+    /// Returns the conjugate `c0 - c1 * w` of this element, which is its image
+    /// under $x \mapsto x^{q^6}$. On elements of order dividing $q^6 + 1$, which
+    /// include the values of the pairing, this is the inverse.
+    pub fn conjugate(&self) -> Self {
+        Fq12 {
+            c0: self.c0,
+            c1: self.c1.neg(),
+        }
+    }
+
+    /// Applies the Frobenius map $x \mapsto x^q$.
+    pub fn frobenius_map(&self) -> Self {
+        // The coefficients of c1 are those of w^(2i + 1), which pick up one
+        // more factor of xi^((q - 1) / 6) than those of c0.
+        Fq12 {
+            c0: self.c0.frobenius_map(),
+            c1: self.c1.frobenius_map().mul_by_fq2(&FROBENIUS_COEFFS[1]),
+        }
+    }
+
+    /// Computes the multiplicative inverse of this element, failing if it is
+    /// zero.
+    pub fn invert(&self) -> CtOption<Self> {
+        // 1 / (a0 + a1 w) = (a0 - a1 w) / (a0^2 - v a1^2)
+        (self.c0.square() - self.c1.square().mul_by_nonresidue())
+            .invert()
+            .map(|t| Fq12 {
+                c0: self.c0 * t,
+                c1: -(self.c1 * t),
+            })
+    }
+
+    /// Exponentiates `self` by `exp`, where `exp` is a little-endian order
+    /// integer exponent.
+    ///
+    /// **This operation is variable time with respect to the exponent.** If
+    /// the exponent is fixed, this operation is effectively constant time.
+    pub fn pow_vartime<S: AsRef<[u64]>>(&self, exp: S) -> Self {
+        let mut res = Self::one();
+        for e in exp.as_ref().iter().rev() {
+            for i in (0..64).rev() {
+                res = res.square();
+
+                if ((*e >> i) & 1) == 1 {
+                    res *= self;
+                }
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+fn random_fq12() -> Fq12 {
+    use super::{Fq, Fq2};
+    use crate::arithmetic::FieldExt;
+
+    let random_fq2 = || Fq2 {
+        c0: Fq::rand(),
+        c1: Fq::rand(),
+    };
+    let random_fq6 = || Fq6 {
+        c0: random_fq2(),
+        c1: random_fq2(),
+        c2: random_fq2(),
+    };
+    Fq12 {
+        c0: random_fq6(),
+        c1: random_fq6(),
+    }
+}
+
+#[test]
+fn test_fq12_arithmetic() {
+    let a = random_fq12();
+    let b = random_fq12();
+    let c = random_fq12();
+
+    assert_eq!(a * (b + c), a * b + a * c);
+    assert_eq!((a * b) * c, a * (b * c));
+    assert_eq!(a.square(), a * a);
+    assert_eq!(a * a.invert().unwrap(), Fq12::one());
+    assert!(bool::from(Fq12::zero().invert().is_none()));
+}
+
+#[test]
+fn test_fq12_frobenius() {
+    let a = random_fq12();
+    let b = random_fq12();
+
+    assert_eq!(
+        (a * b).frobenius_map(),
+        a.frobenius_map() * b.frobenius_map()
+    );
+
+    // Applying the Frobenius map 6 times is conjugation, and 12 times is
+    // the identity.
+    let mut c = a;
+    for _ in 0..6 {
+        c = c.frobenius_map();
+    }
+    assert_eq!(c, a.conjugate());
+    for _ in 0..6 {
+        c = c.frobenius_map();
+    }
+    assert_eq!(c, a);
+}
@@ -0,0 +1,274 @@
+use core::ops::{Add, Mul, Neg, Sub};
+use ff::Field;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use super::fq::Fq;
+use crate::arithmetic::FieldExt;
+
+/// An element of $\mathbb{F}_{q^2} = \mathbb{F}_q\[u\] / (u^2 + 1)$, represented
+/// as `c0 + c1 * u`.
+#[derive(Copy, Clone, Debug, Eq)]
+pub struct Fq2 {
+    /// The constant coefficient.
+    pub c0: Fq,
+    /// The coefficient of `u`.
+    pub c1: Fq,
+}
+
+impl ConstantTimeEq for Fq2 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.c0.ct_eq(&other.c0) & self.c1.ct_eq(&other.c1)
+    }
+}
+
+impl PartialEq for Fq2 {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl ConditionallySelectable for Fq2 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Fq2 {
+            c0: Fq::conditional_select(&a.c0, &b.c0, choice),
+            c1: Fq::conditional_select(&a.c1, &b.c1, choice),
+        }
+    }
+}
+
+impl Default for Fq2 {
+    fn default() -> Self {
+        Fq2::zero()
+    }
+}
+
+impl<'a> Neg for &'a Fq2 {
+    type Output = Fq2;
+
+    #[inline]
+    fn neg(self) -> Fq2 {
+        self.neg()
+    }
+}
+
+impl Neg for Fq2 {
+    type Output = Fq2;
+
+    #[inline]
+    fn neg(self) -> Fq2 {
+        -&self
+    }
+}
+
+impl<'a, 'b> Sub<&'b Fq2> for &'a Fq2 {
+    type Output = Fq2;
+
+    #[inline]
+    fn sub(self, rhs: &'b Fq2) -> Fq2 {
+        self.sub(rhs)
+    }
+}
+
+impl<'a, 'b> Add<&'b Fq2> for &'a Fq2 {
+    type Output = Fq2;
+
+    #[inline]
+    fn add(self, rhs: &'b Fq2) -> Fq2 {
+        self.add(rhs)
+    }
+}
+
+impl<'a, 'b> Mul<&'b Fq2> for &'a Fq2 {
+    type Output = Fq2;
+
+    #[inline]
+    fn mul(self, rhs: &'b Fq2) -> Fq2 {
+        self.mul(rhs)
+    }
+}
+
+impl_binops_additive!(Fq2, Fq2);
+impl_binops_multiplicative!(Fq2, Fq2);
+
+impl Fq2 {
+    /// Returns zero, the additive identity.
+    pub const fn zero() -> Fq2 {
+        Fq2 {
+            c0: Fq::zero(),
+            c1: Fq::zero(),
+        }
+    }
+
+    /// Returns one, the multiplicative identity.
+    pub const fn one() -> Fq2 {
+        Fq2 {
+            c0: Fq::one(),
+            c1: Fq::zero(),
+        }
+    }
+
+    /// Returns true iff this element is zero.
+    pub fn is_zero(&self) -> Choice {
+        self.c0.ct_is_zero() & self.c1.ct_is_zero()
+    }
+
+    /// Adds `rhs` to `self`, returning the result.
+    pub fn add(&self, rhs: &Self) -> Self {
+        Fq2 {
+            c0: self.c0.add(&rhs.c0),
+            c1: self.c1.add(&rhs.c1),
+        }
+    }
+
+    /// Subtracts `rhs` from `self`, returning the result.
+    pub fn sub(&self, rhs: &Self) -> Self {
+        Fq2 {
+            c0: self.c0.sub(&rhs.c0),
+            c1: self.c1.sub(&rhs.c1),
+        }
+    }
+
+    /// Negates `self`.
+    pub fn neg(&self) -> Self {
+        Fq2 {
+            c0: self.c0.neg(),
+            c1: self.c1.neg(),
+        }
+    }
+
+    /// Doubles this element.
+    pub fn double(&self) -> Self {
+        Fq2 {
+            c0: self.c0.double(),
+            c1: self.c1.double(),
+        }
+    }
+
+    /// Multiplies `rhs` by `self`, returning the result.
+    pub fn mul(&self, rhs: &Self) -> Self {
+        // Karatsuba multiplication, using u^2 = -1:
+        // (a0 + a1 u)(b0 + b1 u) = a0 b0 - a1 b1 + ((a0 + a1)(b0 + b1) - a0 b0 - a1 b1) u
+        let v0 = self.c0.mul(&rhs.c0);
+        let v1 = self.c1.mul(&rhs.c1);
+        let c1 = self.c0.add(&self.c1).mul(&rhs.c0.add(&rhs.c1));
+
+        Fq2 {
+            c0: v0.sub(&v1),
+            c1: c1.sub(&v0).sub(&v1),
+        }
+    }
+
+    /// Squares this element.
+    pub fn square(&self) -> Self {
+        // (a0 + a1 u)^2 = (a0 + a1)(a0 - a1) + 2 a0 a1 u
+        let a = self.c0.add(&self.c1);
+        let b = self.c0.sub(&self.c1);
+        let c = self.c0.mul(&self.c1);
+
+        Fq2 {
+            c0: a.mul(&b),
+            c1: c.double(),
+        }
+    }
+
+    /// Multiplies this element by an element of the base field.
+    pub fn mul_by_base(&self, rhs: &Fq) -> Self {
+        Fq2 {
+            c0: self.c0.mul(rhs),
+            c1: self.c1.mul(rhs),
+        }
+    }
+
+    /// Multiplies this element by the cubic and quadratic nonresidue
+    /// $\xi = 9 + u$ used to build $\mathbb{F}_{q^6}$ and $\mathbb{F}_{q^{12}}$.
+    pub fn mul_by_nonresidue(&self) -> Self {
+        // (a0 + a1 u)(9 + u) = 9 a0 - a1 + (a0 + 9 a1) u
+        let t0 = self.c0.double().double().double();
+        let t1 = self.c1.double().double().double();
+
+        Fq2 {
+            c0: t0.add(&self.c0).sub(&self.c1),
+            c1: t1.add(&self.c1).add(&self.c0),
+        }
+    }
+
+    /// Returns the conjugate `c0 - c1 * u` of this element, which is its image
+    /// under the Frobenius map $x \mapsto x^q$.
+    pub fn conjugate(&self) -> Self {
+        Fq2 {
+            c0: self.c0,
+            c1: self.c1.neg(),
+        }
+    }
+
+    /// Computes the multiplicative inverse of this element, failing if it is
+    /// zero.
+    pub fn invert(&self) -> CtOption<Self> {
+        // 1 / (a0 + a1 u) = (a0 - a1 u) / (a0^2 + a1^2)
+        (self.c0.square() + self.c1.square()).invert().map(|t| Fq2 {
+            c0: self.c0 * t,
+            c1: -(self.c1 * t),
+        })
+    }
+
+    /// Exponentiates `self` by `exp`, where `exp` is a little-endian order
+    /// integer exponent.
+    ///
+    /// **This operation is variable time with respect to the exponent.** If
+    /// the exponent is fixed, this operation is effectively constant time.
+    pub fn pow_vartime<S: AsRef<[u64]>>(&self, exp: S) -> Self {
+        let mut res = Self::one();
+        for e in exp.as_ref().iter().rev() {
+            for i in (0..64).rev() {
+                res = res.square();
+
+                if ((*e >> i) & 1) == 1 {
+                    res *= self;
+                }
+            }
+        }
+        res
+    }
+}
+
+#[test]
+fn test_fq2_inverse() {
+    let a = Fq2 {
+        c0: Fq::rand(),
+        c1: Fq::rand(),
+    };
+    assert_eq!(a * a.invert().unwrap(), Fq2::one());
+    assert!(bool::from(Fq2::zero().invert().is_none()));
+}
+
+#[test]
+fn test_fq2_square_and_conjugate() {
+    let a = Fq2 {
+        c0: Fq::rand(),
+        c1: Fq::rand(),
+    };
+    let b = Fq2 {
+        c0: Fq::rand(),
+        c1: Fq::rand(),
+    };
+    assert_eq!(a.square(), a * a);
+    assert_eq!((a * b).conjugate(), a.conjugate() * b.conjugate());
+    // The Frobenius map is exponentiation by q.
+    assert_eq!(
+        a.conjugate(),
+        a.pow_vartime(&[
+            0x3c208c16d87cfd47,
+            0x97816a916871ca8d,
+            0xb85045b68181585d,
+            0x30644e72e131a029,
+        ])
+    );
+    assert_eq!(
+        a.mul_by_nonresidue(),
+        a * Fq2 {
+            c0: Fq::from_u64(9),
+            c1: Fq::one()
+        }
+    );
+}
@@ -0,0 +1,350 @@
+use core::ops::{Add, Mul, Neg, Sub};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use super::fq::Fq;
+use super::fq2::Fq2;
+
+/// An element of $\mathbb{F}_{q^6} = \mathbb{F}_{q^2}\[v\] / (v^3 - \xi)$ with
+/// $\xi = 9 + u$, represented as `c0 + c1 * v + c2 * v^2`.
+#[derive(Copy, Clone, Debug, Eq)]
+pub struct Fq6 {
+    /// The constant coefficient.
+    pub c0: Fq2,
+    /// The coefficient of `v`.
+    pub c1: Fq2,
+    /// The coefficient of `v^2`.
+    pub c2: Fq2,
+}
+
+/// `FROBENIUS_COEFFS[i]` is $\xi^{i (q - 1) / 6}$. As $w^6 = \xi$, the
+/// Frobenius map sends $c \cdot w^i$ to $\bar{c} \cdot \xi^{i (q - 1) / 6} \cdot w^i$
+/// for $c \in \mathbb{F}_{q^2}$.
+pub(crate) const FROBENIUS_COEFFS: [Fq2; 6] = [
+    Fq2::one(),
+    Fq2 {
+        c0: Fq::from_raw([
+            0xd60b35dadcc9e470,
+            0x5c521e08292f2176,
+            0xe8b99fdd76e68b60,
+            0x1284b71c2865a7df,
+        ]),
+        c1: Fq::from_raw([
+            0xca5cf05f80f362ac,
+            0x747992778eeec7e5,
+            0xa6327cfe12150b8e,
+            0x246996f3b4fae7e6,
+        ]),
+    },
+    Fq2 {
+        c0: Fq::from_raw([
+            0x99e39557176f553d,
+            0xb78cc310c2c3330c,
+            0x4c0bec3cf559b143,
+            0x2fb347984f7911f7,
+        ]),
+        c1: Fq::from_raw([
+            0x1665d51c640fcba2,
+            0x32ae2a1d0b7c9dce,
+            0x4ba4cc8bd75a0794,
+            0x16c9e55061ebae20,
+        ]),
+    },
+    Fq2 {
+        c0: Fq::from_raw([
+            0xdc54014671a0135a,
+            0xdbaae0eda9c95998,
+            0xdc5ec698b6e2f9b9,
+            0x063cf305489af5dc,
+        ]),
+        c1: Fq::from_raw([
+            0x82d37f632623b0e3,
+            0x21807dc98fa25bd2,
+            0x0704b5a7ec796f2b,
+            0x07c03cbcac41049a,
+        ]),
+    },
+    Fq2 {
+        c0: Fq::from_raw([
+            0x848a1f55921ea762,
+            0xd33365f7be94ec72,
+            0x80f3c0b75a181e84,
+            0x05b54f5e64eea801,
+        ]),
+        c1: Fq::from_raw([
+            0xc13b4711cd2b8126,
+            0x3685d2ea1bdec763,
+            0x9f3a80b03b0b1c92,
+            0x2c145edbe7fd8aee,
+        ]),
+    },
+    Fq2 {
+        c0: Fq::from_raw([
+            0x2ea2c810eab7692f,
+            0x425c459b55aa1bd3,
+            0xe93a3661a4353ff4,
+            0x0183c1e74f798649,
+        ]),
+        c1: Fq::from_raw([
+            0x24c6b8ee6e0c2c4b,
+            0xb080cb99678e2ac0,
+            0xa27fb246c7729f7d,
+            0x12acf2ca76fd0675,
+        ]),
+    },
+];
+
+impl ConstantTimeEq for Fq6 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.c0.ct_eq(&other.c0) & self.c1.ct_eq(&other.c1) & self.c2.ct_eq(&other.c2)
+    }
+}
+
+impl PartialEq for Fq6 {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl ConditionallySelectable for Fq6 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Fq6 {
+            c0: Fq2::conditional_select(&a.c0, &b.c0, choice),
+            c1: Fq2::conditional_select(&a.c1, &b.c1, choice),
+            c2: Fq2::conditional_select(&a.c2, &b.c2, choice),
+        }
+    }
+}
+
+impl Default for Fq6 {
+    fn default() -> Self {
+        Fq6::zero()
+    }
+}
+
+impl<'a> Neg for &'a Fq6 {
+    type Output = Fq6;
+
+    #[inline]
+    fn neg(self) -> Fq6 {
+        self.neg()
+    }
+}
+
+impl Neg for Fq6 {
+    type Output = Fq6;
+
+    #[inline]
+    fn neg(self) -> Fq6 {
+        -&self
+    }
+}
+
+impl<'a, 'b> Sub<&'b Fq6> for &'a Fq6 {
+    type Output = Fq6;
+
+    #[inline]
+    fn sub(self, rhs: &'b Fq6) -> Fq6 {
+        self.sub(rhs)
+    }
+}
+
+impl<'a, 'b> Add<&'b Fq6> for &'a Fq6 {
+    type Output = Fq6;
+
+    #[inline]
+    fn add(self, rhs: &'b Fq6) -> Fq6 {
+        self.add(rhs)
+    }
+}
+
+impl<'a, 'b> Mul<&'b Fq6> for &'a Fq6 {
+    type Output = Fq6;
+
+    #[inline]
+    fn mul(self, rhs: &'b Fq6) -> Fq6 {
+        self.mul(rhs)
+    }
+}
+
+impl_binops_additive!(Fq6, Fq6);
+impl_binops_multiplicative!(Fq6, Fq6);
+
+impl Fq6 {
+    /// Returns zero, the additive identity.
+    pub const fn zero() -> Fq6 {
+        Fq6 {
+            c0: Fq2::zero(),
+            c1: Fq2::zero(),
+            c2: Fq2::zero(),
+        }
+    }
+
+    /// Returns one, the multiplicative identity.
+    pub const fn one() -> Fq6 {
+        Fq6 {
+            c0: Fq2::one(),
+            c1: Fq2::zero(),
+            c2: Fq2::zero(),
+        }
+    }
+
+    /// Returns true iff this element is zero.
+    pub fn is_zero(&self) -> Choice {
+        self.c0.is_zero() & self.c1.is_zero() & self.c2.is_zero()
+    }
+
+    /// Adds `rhs` to `self`, returning the result.
+    pub fn add(&self, rhs: &Self) -> Self {
+        Fq6 {
+            c0: self.c0.add(&rhs.c0),
+            c1: self.c1.add(&rhs.c1),
+            c2: self.c2.add(&rhs.c2),
+        }
+    }
+
+    /// Subtracts `rhs` from `self`, returning the result.
+    pub fn sub(&self, rhs: &Self) -> Self {
+        Fq6 {
+            c0: self.c0.sub(&rhs.c0),
+            c1: self.c1.sub(&rhs.c1),
+            c2: self.c2.sub(&rhs.c2),
+        }
+    }
+
+    /// Negates `self`.
+    pub fn neg(&self) -> Self {
+        Fq6 {
+            c0: self.c0.neg(),
+            c1: self.c1.neg(),
+            c2: self.c2.neg(),
+        }
+    }
+
+    /// Multiplies `rhs` by `self`, returning the result.
+    pub fn mul(&self, rhs: &Self) -> Self {
+        // Karatsuba multiplication, using v^3 = xi.
+        let v0 = self.c0.mul(&rhs.c0);
+        let v1 = self.c1.mul(&rhs.c1);
+        let v2 = self.c2.mul(&rhs.c2);
+
+        // c0 = v0 + xi ((a1 + a2)(b1 + b2) - v1 - v2)
+        let c0 = self
+            .c1
+            .add(&self.c2)
+            .mul(&rhs.c1.add(&rhs.c2))
+            .sub(&v1)
+            .sub(&v2)
+            .mul_by_nonresidue()
+            .add(&v0);
+        // c1 = (a0 + a1)(b0 + b1) - v0 - v1 + xi v2
+        let c1 = self
+            .c0
+            .add(&self.c1)
+            .mul(&rhs.c0.add(&rhs.c1))
+            .sub(&v0)
+            .sub(&v1)
+            .add(&v2.mul_by_nonresidue());
+        // c2 = (a0 + a2)(b0 + b2) - v0 - v2 + v1
+        let c2 = self
+            .c0
+            .add(&self.c2)
+            .mul(&rhs.c0.add(&rhs.c2))
+            .sub(&v0)
+            .sub(&v2)
+            .add(&v1);
+
+        Fq6 { c0, c1, c2 }
+    }
+
+    /// Squares this element.
+    pub fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    /// Multiplies this element by an element of $\mathbb{F}_{q^2}$.
+    pub fn mul_by_fq2(&self, rhs: &Fq2) -> Self {
+        Fq6 {
+            c0: self.c0.mul(rhs),
+            c1: self.c1.mul(rhs),
+            c2: self.c2.mul(rhs),
+        }
+    }
+
+    /// Multiplies this element by `v`, the quadratic nonresidue used to build
+    /// $\mathbb{F}_{q^{12}}$.
+    pub fn mul_by_nonresidue(&self) -> Self {
+        // (a0 + a1 v + a2 v^2) v = xi a2 + a0 v + a1 v^2
+        Fq6 {
+            c0: self.c2.mul_by_nonresidue(),
+            c1: self.c0,
+            c2: self.c1,
+        }
+    }
+
+    /// Applies the Frobenius map $x \mapsto x^q$.
+    pub fn frobenius_map(&self) -> Self {
+        // v = w^2, so the coefficient of v^i is scaled by xi^(2i (q - 1) / 6).
+        Fq6 {
+            c0: self.c0.conjugate(),
+            c1: self.c1.conjugate().mul(&FROBENIUS_COEFFS[2]),
+            c2: self.c2.conjugate().mul(&FROBENIUS_COEFFS[4]),
+        }
+    }
+
+    /// Computes the multiplicative inverse of this element, failing if it is
+    /// zero.
+    pub fn invert(&self) -> CtOption<Self> {
+        let t0 = self.c0.square() - (self.c1 * self.c2).mul_by_nonresidue();
+        let t1 = self.c2.square().mul_by_nonresidue() - self.c0 * self.c1;
+        let t2 = self.c1.square() - self.c0 * self.c2;
+
+        // The norm of this element down to Fq2, divided by the other two
+        // conjugates (t0 + t1 v + t2 v^2).
+        let norm = self.c0 * t0 + (self.c2 * t1 + self.c1 * t2).mul_by_nonresidue();
+        norm.invert().map(|t| Fq6 {
+            c0: t0 * t,
+            c1: t1 * t,
+            c2: t2 * t,
+        })
+    }
+}
+
+#[cfg(test)]
+use crate::arithmetic::FieldExt;
+
+#[cfg(test)]
+fn random_fq6() -> Fq6 {
+    let random_fq2 = || Fq2 {
+        c0: Fq::rand(),
+        c1: Fq::rand(),
+    };
+    Fq6 {
+        c0: random_fq2(),
+        c1: random_fq2(),
+        c2: random_fq2(),
+    }
+}
+
+#[test]
+fn test_fq6_arithmetic() {
+    let a = random_fq6();
+    let b = random_fq6();
+    let c = random_fq6();
+
+    assert_eq!(a * (b + c), a * b + a * c);
+    assert_eq!((a * b) * c, a * (b * c));
+    assert_eq!(a * a.invert().unwrap(), Fq6::one());
+    assert!(bool::from(Fq6::zero().invert().is_none()));
+
+    let v = Fq6 {
+        c0: Fq2::zero(),
+        c1: Fq2::one(),
+        c2: Fq2::zero(),
+    };
+    assert_eq!(a.mul_by_nonresidue(), a * v);
+    assert_eq!(
+        (a * b).frobenius_map(),
+        a.frobenius_map() * b.frobenius_map()
+    );
+}
@@ -0,0 +1,551 @@
+//! This module contains an implementation of the polynomial commitment scheme
+//! of [Kate, Zaverucha and Goldberg][kzg] over BN254, with commitments in
+//! $\mathbb{G}_1$. Commitments are blinded in the same way as those of the
+//! [inner product argument](crate::poly::commitment), so that (Turbo)PLONK
+//! proofs can be created with either scheme.
+//!
+//! A commitment to $p(X)$ with blinding factor $r$ is
+//! $C = [p(\tau)] G + [r] H$. To open it to $v$ at $x$, the prover sends
+//! $W = [q(\tau)] G + [s] H$ for the quotient $q(X) = (p(X) - v) / (X - x)$
+//! and a random $s$, along with $R = [r + s x] G_2 - [s] [\tau] G_2$. The
+//! verifier checks that
+//! $e(C - [v] G + [x] W, G_2) = e(W, [\tau] G_2) \cdot e(H, R)$.
+//!
+//! [kzg]: https://www.iacr.org/archive/asiacrypt2010/6477178/6477178.pdf
+
+use ff::{Field, PrimeField};
+use rand::{CryptoRng, RngCore};
+use std::io;
+
+use super::{final_exponentiation, multi_miller_loop, Fq12, Fr, G1Affine, G2Affine, G1, G2};
+use crate::arithmetic::{best_multiexp, kate_division, parallelize, Curve, CurveAffine};
+use crate::poly::{
+    commitment::{g_to_lagrange, Blind},
+    scheme::{CommitmentScheme, CommitmentSchemeVerifier, SchemeMSM},
+    Coeff, Error, LagrangeCoeff, Polynomial,
+};
+use crate::transcript::{Hasher, Transcript};
+
+/// These are the public parameters for the polynomial commitment scheme.
+#[derive(Debug)]
+pub struct Params {
+    k: u32,
+    n: u64,
+    g: Vec<G1Affine>,
+    g_lagrange: Vec<G1Affine>,
+    h: G1Affine,
+    g2: G2Affine,
+    s_g2: G2Affine,
+}
+
+/// This is a proof object for the polynomial commitment scheme opening.
+#[derive(Debug, Clone)]
+pub struct Proof {
+    w: G1Affine,
+    r: G2Affine,
+}
+
+/// A multiscalar multiplication in the polynomial commitment scheme, which
+/// holds the deferred pairing checks of opening proofs.
+#[derive(Debug, Clone)]
+pub struct MSM<'a> {
+    params: &'a Params,
+    // The terms paired with G_2.
+    scalars: Vec<Fr>,
+    bases: Vec<G1Affine>,
+    // The terms paired with [tau] G_2.
+    s_scalars: Vec<Fr>,
+    s_bases: Vec<G1Affine>,
+    // The point paired with H.
+    h_g2: G2,
+}
+
+impl Params {
+    /// Generates parameters for polynomials of degree less than $2^k$ from a
+    /// secret $\tau$ sampled from `rng`.
+    ///
+    /// Anyone who knows $\tau$ can open commitments to any value, so these
+    /// parameters are only suitable for testing. Real parameters must come
+    /// from a setup ceremony that nobody learns $\tau$ from, and be loaded
+    /// with [`Params::from_powers`] or [`Params::read`].
+    pub fn setup<R: RngCore>(k: u32, mut rng: R) -> Self {
+        assert!(k <= Fr::S);
+
+        let n: u64 = 1 << k;
+        let tau = Fr::random(&mut rng);
+
+        // The generators g_i are [tau^i] G.
+        let g = {
+            let mut g = vec![G1::zero(); n as usize];
+            parallelize(&mut g, move |g, start| {
+                let mut power = tau.pow_vartime([start as u64, 0, 0, 0]);
+                for g in g.iter_mut() {
+                    *g = G1::one() * power;
+                    power *= tau;
+                }
+            });
+
+            let mut g_affine = vec![G1Affine::zero(); n as usize];
+            G1::batch_to_affine(&g, &mut g_affine);
+            g_affine
+        };
+
+        Self::from_checked_powers(g, (G2::one() * tau).to_affine())
+    }
+
+    /// Returns the parameters for polynomials of degree less than $2^k$
+    /// from the output of a setup ceremony: the powers $[\tau^i] G$ for
+    /// $i < 2^k$, and $[\tau] G_2$.
+    ///
+    /// Returns `None` if there are fewer than two powers or their number is
+    /// not a power of two, if the first power is not $G$, if `s_g2` is not in
+    /// $\mathbb{G}_2$, or if the powers are not those of the $\tau$ of
+    /// `s_g2`. A single power would not constrain $\tau$ at all. The last
+    /// check is probabilistic, with randomness from `rng`, and costs two
+    /// multi-exponentiations over the powers and two pairings.
+    pub fn from_powers(g: Vec<G1Affine>, s_g2: G2Affine, mut rng: impl RngCore) -> Option<Self> {
+        if g.len() < 2
+            || !g.len().is_power_of_two()
+            || g.len().trailing_zeros() > Fr::S
+            || g[0] != G1Affine::one()
+            || !bool::from(s_g2.is_torsion_free())
+        {
+            return None;
+        }
+
+        // g_{i + 1} = [tau] g_i for all i if and only if, for a random rho,
+        // e(sum_i [rho^i] g_{i + 1}, G_2) = e(sum_i [rho^i] g_i, [tau] G_2)
+        // except with negligible probability.
+        let rho = Fr::random(&mut rng);
+        let mut powers = Vec::with_capacity(g.len() - 1);
+        let mut power = Fr::one();
+        for _ in 1..g.len() {
+            powers.push(power);
+            power *= rho;
+        }
+        let a = best_multiexp(&powers, &g[..g.len() - 1]);
+        let b = best_multiexp(&powers, &g[1..]);
+        let valid = final_exponentiation(&multi_miller_loop(&[
+            (&b.to_affine(), &G2Affine::one()),
+            (&(-a).to_affine(), &s_g2),
+        ])) == Fq12::one();
+
+        if valid {
+            Some(Self::from_checked_powers(g, s_g2))
+        } else {
+            None
+        }
+    }
+
+    fn from_checked_powers(g: Vec<G1Affine>, s_g2: G2Affine) -> Self {
+        let k = g.len().trailing_zeros();
+        let n = g.len() as u64;
+
+        let g_lagrange = g_to_lagrange(&g, k);
+
+        // h is a hash to the curve, so that nobody knows its discrete
+        // logarithm.
+        let h = {
+            let hasher = G1::hash_to_curve("Halo2-KZG-Parameters");
+            hasher(&[1]).to_affine()
+        };
+
+        Params {
+            k,
+            n,
+            g,
+            g_lagrange,
+            h,
+            g2: G2Affine::one(),
+            s_g2,
+        }
+    }
+
+    /// Writes the parameters to `writer`: $k$, then the powers $[\tau^i] G$
+    /// and $[\tau] G_2$, from which [`Params::read`] recomputes the rest.
+    pub fn write<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(&self.k.to_le_bytes())?;
+        for g in self.g.iter() {
+            writer.write_all(&g.to_bytes())?;
+        }
+        writer.write_all(&self.s_g2.to_bytes_wide())
+    }
+
+    /// Reads parameters written by [`Params::write`], checking them as
+    /// [`Params::from_powers`] does with randomness from `rng`.
+    pub fn read<R: io::Read>(mut reader: R, rng: impl RngCore) -> io::Result<Self> {
+        let mut k = [0u8; 4];
+        reader.read_exact(&mut k)?;
+        let k = u32::from_le_bytes(k);
+        if k > Fr::S {
+            return Err(invalid_data("k is too large"));
+        }
+
+        let g = (0..1u64 << k)
+            .map(|_| read_g1(&mut reader))
+            .collect::<io::Result<_>>()?;
+        let s_g2 = read_g2(&mut reader)?;
+
+        Self::from_powers(g, s_g2, rng).ok_or_else(|| invalid_data("inconsistent powers of tau"))
+    }
+
+    /// This computes a commitment to a polynomial described by the provided
+    /// slice of coefficients. The commitment will be blinded by the blinding
+    /// factor `r`.
+    pub fn commit(&self, poly: &Polynomial<Fr, Coeff>, r: Blind<Fr>) -> G1 {
+        commit_with(&self.g, self.h, poly, r)
+    }
+
+    /// This commits to a polynomial using its evaluations over the $2^k$ size
+    /// evaluation domain. The commitment will be blinded by the blinding factor
+    /// `r`.
+    pub fn commit_lagrange(&self, poly: &Polynomial<Fr, LagrangeCoeff>, r: Blind<Fr>) -> G1 {
+        commit_with(&self.g_lagrange, self.h, poly, r)
+    }
+
+    /// Generates an empty multiscalar multiplication struct using the
+    /// appropriate params.
+    pub fn empty_msm(&self) -> MSM<'_> {
+        MSM {
+            params: self,
+            scalars: vec![],
+            bases: vec![],
+            s_scalars: vec![],
+            s_bases: vec![],
+            h_g2: G2::zero(),
+        }
+    }
+
+    /// Returns the parameters for polynomials of degree less than $2^k$,
+    /// which share their generators with these parameters. A commitment made
+    /// with the returned parameters is also a commitment to the same
+    /// polynomial with these parameters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is larger than the `k` of these parameters.
+    pub fn downsize(&self, k: u32) -> Self {
+        assert!(k <= self.k);
+
+        let n = 1 << k;
+        let g = self.g[..n as usize].to_vec();
+        let g_lagrange = g_to_lagrange(&g, k);
+
+        Params {
+            k,
+            n,
+            g,
+            g_lagrange,
+            h: self.h,
+            g2: self.g2,
+            s_g2: self.s_g2,
+        }
+    }
+}
+
+impl Proof {
+    /// Writes the proof to `writer`.
+    pub fn write<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(&self.w.to_bytes())?;
+        writer.write_all(&self.r.to_bytes_wide())
+    }
+
+    /// Reads a proof written by [`Proof::write`].
+    pub fn read<R: io::Read>(mut reader: R) -> io::Result<Self> {
+        let w = read_g1(&mut reader)?;
+        let r = read_g2(&mut reader)?;
+        Ok(Proof { w, r })
+    }
+}
+
+fn read_g1<R: io::Read>(reader: &mut R) -> io::Result<G1Affine> {
+    let mut bytes = [0u8; 32];
+    reader.read_exact(&mut bytes)?;
+    Option::from(G1Affine::from_bytes(&bytes)).ok_or_else(|| invalid_data("invalid point of G_1"))
+}
+
+fn read_g2<R: io::Read>(reader: &mut R) -> io::Result<G2Affine> {
+    let mut bytes = [0u8; 128];
+    reader.read_exact(&mut bytes)?;
+    Option::from(G2Affine::from_bytes_wide(&bytes))
+        .ok_or_else(|| invalid_data("invalid point of G_2"))
+}
+
+fn invalid_data(error: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// Computes $\sum_i [a_i] g_i + [r] h$ for the coefficients $a_i$ of `poly`.
+fn commit_with(g: &[G1Affine], h: G1Affine, poly: &[Fr], r: Blind<Fr>) -> G1 {
+    let mut tmp_scalars = Vec::with_capacity(poly.len() + 1);
+    let mut tmp_bases = Vec::with_capacity(poly.len() + 1);
+
+    tmp_scalars.extend(poly.iter());
+    tmp_scalars.push(r.0);
+
+    tmp_bases.extend(g[..poly.len()].iter());
+    tmp_bases.push(h);
+
+    best_multiexp::<G1Affine>(&tmp_scalars, &tmp_bases)
+}
+
+impl CommitmentScheme<G1Affine> for Params {
+    type Proof = Proof;
+
+    fn n(&self) -> u64 {
+        self.n
+    }
+
+    fn k(&self) -> u32 {
+        self.k
+    }
+
+    fn is_downsized_from(&self, params: &Self) -> bool {
+        self.k <= params.k
+            && self.h == params.h
+            && self.s_g2 == params.s_g2
+            && self.g[..] == params.g[..self.g.len()]
+    }
+
+    fn commit(&self, poly: &Polynomial<Fr, Coeff>, r: Blind<Fr>) -> G1 {
+        Params::commit(self, poly, r)
+    }
+
+    fn commit_lagrange(&self, poly: &Polynomial<Fr, LagrangeCoeff>, r: Blind<Fr>) -> G1 {
+        Params::commit_lagrange(self, poly, r)
+    }
+
+    fn open<HBase, HScalar, R>(
+        &self,
+        transcript: &mut Transcript<G1Affine, HBase, HScalar>,
+        px: &Polynomial<Fr, Coeff>,
+        blind: Blind<Fr>,
+        x: Fr,
+        mut rng: R,
+    ) -> Result<Proof, Error>
+    where
+        HBase: Hasher<<G1Affine as CurveAffine>::Base>,
+        HScalar: Hasher<Fr>,
+        R: RngCore + CryptoRng,
+    {
+        // The quotient (p(X) - p(x)) / (X - x), committed to with a fresh
+        // blinding factor s.
+        let q = kate_division(&px[..], x);
+        let s = Fr::random(&mut rng);
+        let w = commit_with(&self.g, self.h, &q, Blind(s)).to_affine();
+        transcript
+            .absorb_point(&w)
+            .map_err(|_| Error::SamplingError)?;
+
+        // R accounts for the blinding factors r of p(X) and s of q(X).
+        let r = (G2::one() * (blind.0 + s * x) - self.s_g2 * s).to_affine();
+
+        Ok(Proof { w, r })
+    }
+}
+
+impl<'a> CommitmentSchemeVerifier<'a, G1Affine> for Params {
+    type MSM = MSM<'a>;
+    type Guard = MSM<'a>;
+
+    fn empty_msm(&'a self) -> MSM<'a> {
+        Params::empty_msm(self)
+    }
+
+    fn finalize(guard: MSM<'a>) -> MSM<'a> {
+        guard
+    }
+
+    fn verify_opening<HBase, HScalar>(
+        &'a self,
+        proof: &Proof,
+        mut msm: MSM<'a>,
+        transcript: &mut Transcript<G1Affine, HBase, HScalar>,
+        x: Fr,
+        commitment: MSM<'a>,
+        v: Fr,
+    ) -> Result<MSM<'a>, Error>
+    where
+        HBase: Hasher<<G1Affine as CurveAffine>::Base>,
+        HScalar: Hasher<Fr>,
+    {
+        transcript
+            .absorb_point(&proof.w)
+            .map_err(|_| Error::InvalidPoint)?;
+
+        // e(C - [v] G + [x] W, G_2) e(-W, [tau] G_2) e(H, -R) = 1
+        msm.add_msm(&commitment);
+        msm.append_term(-v, self.g[0]);
+        msm.append_term(x, proof.w);
+        msm.s_scalars.push(-Fr::one());
+        msm.s_bases.push(proof.w);
+        msm.h_g2 = msm.h_g2 - proof.r;
+
+        Ok(msm)
+    }
+}
+
+impl<'a> SchemeMSM<G1Affine> for MSM<'a> {
+    fn append_term(&mut self, scalar: Fr, point: G1Affine) {
+        self.scalars.push(scalar);
+        self.bases.push(point);
+    }
+
+    fn add_msm(&mut self, other: &Self) {
+        self.scalars.extend(other.scalars.iter());
+        self.bases.extend(other.bases.iter());
+        self.s_scalars.extend(other.s_scalars.iter());
+        self.s_bases.extend(other.s_bases.iter());
+        self.h_g2 = self.h_g2 + other.h_g2;
+    }
+
+    fn scale(&mut self, factor: Fr) {
+        for scalar in self.scalars.iter_mut().chain(self.s_scalars.iter_mut()) {
+            *scalar *= factor;
+        }
+        self.h_g2 = self.h_g2 * factor;
+    }
+
+    fn eval(self) -> bool {
+        let a = best_multiexp(&self.scalars, &self.bases).to_affine();
+        let b = best_multiexp(&self.s_scalars, &self.s_bases).to_affine();
+        let h_g2 = self.h_g2.to_affine();
+
+        final_exponentiation(&multi_miller_loop(&[
+            (&a, &self.params.g2),
+            (&b, &self.params.s_g2),
+            (&self.params.h, &h_g2),
+        ])) == Fq12::one()
+    }
+}
+
+#[test]
+fn test_opening_proof() {
+    use crate::arithmetic::{eval_polynomial, FieldExt};
+    use crate::poly::EvaluationDomain;
+    use crate::transcript::DummyHash;
+    use rand::rngs::OsRng;
+
+    use super::Fq;
+
+    const K: u32 = 4;
+
+    let params = Params::setup(K, OsRng);
+    let domain = EvaluationDomain::new(1, K);
+
+    let mut px = domain.empty_coeff();
+    for (i, a) in px.iter_mut().enumerate() {
+        *a = Fr::from_u64(i as u64);
+    }
+    let blind = Blind(Fr::rand());
+    let p = params.commit(&px, blind).to_affine();
+    let x = Fr::rand();
+    let v = eval_polynomial(&px, x);
+
+    let mut transcript = Transcript::<_, DummyHash<Fq>, DummyHash<Fr>>::new();
+    let proof = params
+        .open(&mut transcript, &px, blind, x, OsRng)
+        .expect("opening should not fail");
+
+    let verify = |commitment: G1Affine, v: Fr| {
+        let mut transcript = Transcript::<_, DummyHash<Fq>, DummyHash<Fr>>::new();
+        let mut commitment_msm = params.empty_msm();
+        commitment_msm.append_term(Fr::one(), commitment);
+        params
+            .verify_opening(
+                &proof,
+                params.empty_msm(),
+                &mut transcript,
+                x,
+                commitment_msm,
+                v,
+            )
+            .map(|msm| msm.eval())
+    };
+    assert_eq!(verify(p, v), Ok(true));
+    assert_eq!(verify(p, v + Fr::one()), Ok(false));
+    assert_eq!(verify((p + params.h).to_affine(), v), Ok(false));
+
+    // The downsized parameters commit to polynomials of their size in the
+    // same way.
+    let small = params.downsize(K - 1);
+    let domain = EvaluationDomain::new(1, K - 1);
+    let mut a = domain.empty_coeff();
+    a[1] = Fr::one();
+    assert!(small.is_downsized_from(&params));
+    assert!(!params.is_downsized_from(&small));
+    assert_eq!(small.commit(&a, blind), params.commit(&a, blind));
+}
+
+#[test]
+fn test_serialization() {
+    use crate::arithmetic::FieldExt;
+    use crate::poly::EvaluationDomain;
+    use crate::transcript::DummyHash;
+    use rand::rngs::OsRng;
+    use subtle::Choice;
+
+    use super::{Fq, Fq2};
+
+    const K: u32 = 3;
+
+    let params = Params::setup(K, OsRng);
+    let mut bytes = vec![];
+    params.write(&mut bytes).unwrap();
+    let read = Params::read(&bytes[..], OsRng).unwrap();
+    assert!(read.is_downsized_from(&params) && params.is_downsized_from(&read));
+    assert_eq!(read.g_lagrange, params.g_lagrange);
+    assert!(Params::read(&bytes[..bytes.len() - 1], OsRng).is_err());
+
+    // The powers must be consistent with [tau] G_2.
+    assert!(Params::from_powers(params.g.clone(), params.s_g2, OsRng).is_some());
+    assert!(Params::from_powers(params.g.clone(), G2Affine::one(), OsRng).is_none());
+    let mut g = params.g.clone();
+    g.swap(2, 3);
+    assert!(Params::from_powers(g, params.s_g2, OsRng).is_none());
+    assert!(Params::from_powers(params.g[..3].to_vec(), params.s_g2, OsRng).is_none());
+    assert!(Params::from_powers(params.g[1..5].to_vec(), params.s_g2, OsRng).is_none());
+    assert!(Params::from_powers(params.g[..2].to_vec(), params.s_g2, OsRng).is_some());
+
+    // A single power is consistent with any tau, so it is rejected.
+    assert!(Params::from_powers(params.g[..1].to_vec(), params.s_g2, OsRng).is_none());
+
+    // [tau] G_2 must be in G_2, not just on the twist.
+    let s_g2 = G2Affine {
+        x: Fq2::one(),
+        y: Fq2 {
+            c0: Fq::from_raw([
+                0xab4b871c0531f1bb,
+                0xaadd70e52c9830e9,
+                0xf8e2728fdb825a51,
+                0x2869111d5381f072,
+            ]),
+            c1: Fq::from_raw([
+                0x1b7f8da82de048a4,
+                0x998c7f790cb4d751,
+                0x36846e70a1934187,
+                0x0d1271953ed9ea08,
+            ]),
+        },
+        infinity: Choice::from(0u8),
+    };
+    assert!(bool::from(s_g2.is_on_curve()));
+    assert!(Params::from_powers(params.g.clone(), s_g2, OsRng).is_none());
+
+    let domain = EvaluationDomain::new(1, K);
+    let mut px = domain.empty_coeff();
+    for a in px.iter_mut() {
+        *a = Fr::rand();
+    }
+    let blind = Blind(Fr::rand());
+    let x = Fr::rand();
+
+    let mut transcript = Transcript::<_, DummyHash<Fq>, DummyHash<Fr>>::new();
+    let proof = params
+        .open(&mut transcript, &px, blind, x, OsRng)
+        .expect("opening should not fail");
+    let mut bytes = vec![];
+    proof.write(&mut bytes).unwrap();
+    let read = Proof::read(&bytes[..]).unwrap();
+    assert_eq!((read.w, read.r), (proof.w, proof.r));
+}
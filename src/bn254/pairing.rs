@@ -0,0 +1,208 @@
+//! This module contains the optimal ate pairing
+//! $e: \mathbb{G}_1 \times \mathbb{G}_2 \rightarrow \mathbb{F}_{q^{12}}$ of
+//! the BN254 curve.
+
+use super::{Fq, Fq12, Fq2, Fq6, G1Affine, G2Affine, FROBENIUS_COEFFS};
+use crate::arithmetic::CurveAffine;
+
+/// The length $6x + 2$ of the Miller loop, where $x = 4965661367192848881$ is
+/// the parameter of the BN254 curve.
+const ATE_LOOP_COUNT: u128 = 0x19d797039be763ba8;
+
+/// The exponent $(q^4 - q^2 + 1) / r$ of the hard part of the final
+/// exponentiation.
+const HARD_EXPONENT: [u64; 12] = [
+    0xe81bb482ccdf42b1,
+    0x5abf5cc4f49c36d4,
+    0xf1154e7e1da014fd,
+    0xdcc7b44c87cdbacf,
+    0xaaa441e3954bcf8a,
+    0x6b887d56d5095f23,
+    0x79581e16f3fd90c6,
+    0x3b1b1355d189227d,
+    0x4e529a5861876f6b,
+    0x6c0eb522d5b12278,
+    0x331ec15183177faf,
+    0x01baaa710b0759ad,
+];
+
+/// Computes the pairing $e(p, q)$.
+pub fn pairing(p: &G1Affine, q: &G2Affine) -> Fq12 {
+    final_exponentiation(&multi_miller_loop(&[(p, q)]))
+}
+
+/// Computes the product of the Miller loops of all of the given pairs, so
+/// that a product of pairings $\prod_i e(p_i, q_i)$ can be computed with a
+/// single [`final_exponentiation`].
+pub fn multi_miller_loop(terms: &[(&G1Affine, &G2Affine)]) -> Fq12 {
+    // Pairs with a point at infinity contribute 1 to the product.
+    let terms: Vec<_> = terms
+        .iter()
+        .filter_map(|(p, q)| {
+            let p = Option::from(p.get_xy())?;
+            let q = Option::from(q.get_xy())?;
+            Some((p, q))
+        })
+        .collect();
+    let mut ts: Vec<(Fq2, Fq2)> = terms.iter().map(|(_, q)| *q).collect();
+
+    // The points Q of G_2 live on the sextic twist, in affine coordinates.
+    // Each step multiplies f by the line through T (and Q) evaluated at P.
+    let mut f = Fq12::one();
+    let bits = 128 - ATE_LOOP_COUNT.leading_zeros() - 1;
+    for i in (0..bits).rev() {
+        f = f.square();
+        for ((p, _), t) in terms.iter().zip(ts.iter_mut()) {
+            f *= double_step(t, p);
+        }
+
+        if (ATE_LOOP_COUNT >> i) & 1 == 1 {
+            for ((p, q), t) in terms.iter().zip(ts.iter_mut()) {
+                f *= add_step(t, q, p);
+            }
+        }
+    }
+
+    // Finally, add the images of Q under the Frobenius map of the twist,
+    // pi(Q) and -pi^2(Q).
+    for ((p, q), t) in terms.iter().zip(ts.iter_mut()) {
+        let q1 = frobenius(q);
+        let q2 = frobenius(&q1);
+        f *= add_step(t, &q1, p);
+        f *= add_step(t, &(q2.0, -q2.1), p);
+    }
+
+    f
+}
+
+/// Raises the output of a Miller loop to the power $(q^{12} - 1) / r$, giving
+/// an element of the subgroup of order $r$. Returns zero if `f` is zero.
+pub fn final_exponentiation(f: &Fq12) -> Fq12 {
+    // The easy part f^((q^6 - 1)(q^2 + 1)), using that f^(q^6) is the
+    // conjugate of f.
+    let f = f.conjugate() * f.invert().unwrap_or(Fq12::zero());
+    let f = f.frobenius_map().frobenius_map() * f;
+
+    // The hard part f^((q^4 - q^2 + 1) / r).
+    f.pow_vartime(&HARD_EXPONENT)
+}
+
+/// Evaluates at `p` the line through `t` with slope `lambda` on the twist,
+/// after mapping it to the curve over $\mathbb{F}_{q^{12}}$ with
+/// $(x, y) \mapsto (x w^2, y w^3)$.
+fn line(lambda: Fq2, t: &(Fq2, Fq2), p: &(Fq, Fq)) -> Fq12 {
+    // y_P - lambda x_P w + (lambda x_T - y_T) w^3, where w^3 = v w.
+    Fq12 {
+        c0: Fq6 {
+            c0: Fq2 {
+                c0: p.1,
+                c1: Fq::zero(),
+            },
+            c1: Fq2::zero(),
+            c2: Fq2::zero(),
+        },
+        c1: Fq6 {
+            c0: lambda.mul_by_base(&-p.0),
+            c1: lambda * t.0 - t.1,
+            c2: Fq2::zero(),
+        },
+    }
+}
+
+/// Doubles `t`, returning the tangent line at `t` evaluated at `p`.
+fn double_step(t: &mut (Fq2, Fq2), p: &(Fq, Fq)) -> Fq12 {
+    // As t has order r, it is never a point of order 2.
+    let x2 = t.0.square();
+    let lambda = (x2 + x2 + x2) * (t.1 + t.1).invert().unwrap();
+    let l = line(lambda, t, p);
+
+    let x = lambda.square() - t.0 - t.0;
+    t.1 = lambda * (t.0 - x) - t.1;
+    t.0 = x;
+    l
+}
+
+/// Adds `q` to `t`, returning the line through them evaluated at `p`.
+fn add_step(t: &mut (Fq2, Fq2), q: &(Fq2, Fq2), p: &(Fq, Fq)) -> Fq12 {
+    // Within the Miller loop t is a multiple of q smaller than its order, so
+    // the points are distinct and are not each other's negation.
+    let lambda = (q.1 - t.1) * (q.0 - t.0).invert().unwrap();
+    let l = line(lambda, t, p);
+
+    let x = lambda.square() - t.0 - q.0;
+    t.1 = lambda * (t.0 - x) - t.1;
+    t.0 = x;
+    l
+}
+
+/// The Frobenius endomorphism of the twist, which is the map
+/// $(x, y) \mapsto (x^q, y^q)$ on the curve carried over by the twist.
+fn frobenius(q: &(Fq2, Fq2)) -> (Fq2, Fq2) {
+    (
+        q.0.conjugate() * FROBENIUS_COEFFS[2],
+        q.1.conjugate() * FROBENIUS_COEFFS[3],
+    )
+}
+
+#[cfg(test)]
+use super::{Fr, G1, G2};
+
+#[cfg(test)]
+use crate::arithmetic::{Curve, FieldExt};
+
+#[test]
+fn test_bilinearity() {
+    let a = Fr::rand();
+    let b = Fr::rand();
+    let p = G1Affine::one();
+    let q = G2Affine::one();
+
+    let expected = pairing(&(p * (a * b)).to_affine(), &q);
+    assert_eq!(
+        pairing(&(p * a).to_affine(), &(q * b).to_affine()),
+        expected
+    );
+    assert_eq!(pairing(&p, &(q * (a * b)).to_affine()), expected);
+    assert_eq!(
+        pairing(&(p * a).to_affine(), &q) * pairing(&(p * b).to_affine(), &q),
+        pairing(&(p * (a + b)).to_affine(), &q)
+    );
+}
+
+#[test]
+fn test_non_degeneracy_and_order() {
+    let e = pairing(&G1Affine::one(), &G2Affine::one());
+    assert!(e != Fq12::one());
+
+    // e has order r.
+    assert_eq!(
+        e.pow_vartime(&[
+            0x43e1f593f0000001,
+            0x2833e84879b97091,
+            0xb85045b68181585d,
+            0x30644e72e131a029,
+        ]),
+        Fq12::one()
+    );
+
+    assert_eq!(pairing(&G1Affine::zero(), &G2Affine::one()), Fq12::one());
+    assert_eq!(pairing(&G1Affine::one(), &G2Affine::zero()), Fq12::one());
+}
+
+#[test]
+fn test_multi_miller_loop() {
+    let a = Fr::rand();
+    let p = (G1::one() * a).to_affine();
+    let q = G2::one().to_affine();
+    let neg_p = -p;
+
+    // e(P, Q) e(-P, Q) = 1
+    assert_eq!(
+        final_exponentiation(&multi_miller_loop(&[(&p, &q), (&neg_p, &q)])),
+        Fq12::one()
+    );
+    assert_eq!(
+        final_exponentiation(&multi_miller_loop(&[(&p, &q), (&G1Affine::one(), &q)])),
+        pairing(&p, &q) * pairing(&G1Affine::one(), &q)
+    );
+}
@@ -0,0 +1,8 @@
+//! Tools for developing circuits.
+
+mod export;
+mod layout;
+mod mock;
+pub use export::{export_dot, export_json};
+pub use layout::CircuitLayout;
+pub use mock::{MockProver, VerifyFailure};
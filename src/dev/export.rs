@@ -0,0 +1,302 @@
+//! Exports of a [`ConstraintSystem`] for inspection with other tools: as JSON,
+//! with the expression tree of every gate, or as a Graphviz DOT graph of which
+//! gates and arguments use each column.
+
+use ff::Field;
+use std::collections::BTreeSet;
+
+use crate::plonk::{Any, Column, ConstraintSystem, Expression};
+use crate::poly::Rotation;
+
+fn column_type_name(column_type: &Any) -> &'static str {
+    match column_type {
+        Any::Advice => "advice",
+        Any::Fixed => "fixed",
+        Any::Aux => "aux",
+    }
+}
+
+fn query_json<C: Copy + Into<Column<Any>>>(
+    kind: &str,
+    queries: &[(C, Rotation)],
+    index: usize,
+) -> String {
+    let (column, at): (Column<Any>, _) = (queries[index].0.into(), queries[index].1);
+    format!(
+        r#"{{"type":"{}","query":{},"column":{},"rotation":{}}}"#,
+        kind,
+        index,
+        column.index(),
+        at.0
+    )
+}
+
+fn expression_json<F: Field>(cs: &ConstraintSystem<F>, expression: &Expression<F>) -> String {
+    expression.evaluate(
+        &|index| query_json("fixed", &cs.fixed_queries, index),
+        &|index| query_json("advice", &cs.advice_queries, index),
+        &|index| query_json("aux", &cs.aux_queries, index),
+        &|a, b| format!(r#"{{"type":"sum","left":{},"right":{}}}"#, a, b),
+        &|a, b| format!(r#"{{"type":"product","left":{},"right":{}}}"#, a, b),
+        &|a, f| {
+            format!(
+                r#"{{"type":"scaled","expression":{},"scalar":"{:?}"}}"#,
+                a, f
+            )
+        },
+    )
+}
+
+fn columns_json(columns: &[Column<Any>]) -> String {
+    let columns: Vec<_> = columns
+        .iter()
+        .map(|column| {
+            format!(
+                r#"{{"type":"{}","index":{}}}"#,
+                column_type_name(column.column_type()),
+                column.index()
+            )
+        })
+        .collect();
+    format!("[{}]", columns.join(","))
+}
+
+fn queries_json<C: Copy + Into<Column<Any>>>(queries: &[(C, Rotation)]) -> String {
+    let queries: Vec<_> = queries
+        .iter()
+        .map(|(column, at)| {
+            let column: Column<Any> = (*column).into();
+            format!(r#"{{"column":{},"rotation":{}}}"#, column.index(), at.0)
+        })
+        .collect();
+    format!("[{}]", queries.join(","))
+}
+
+/// Exports a [`ConstraintSystem`] as JSON.
+///
+/// The output is a single object with the following keys:
+///
+/// - `columns`: the number of `advice`, `aux` and `fixed` columns.
+/// - `queries`: for each column type, the list of `{column, rotation}` queries.
+///   Expressions refer to queries by their index in these lists.
+/// - `gates`: the expression tree of each gate. Every node has a `type` of
+///   `advice`, `aux`, `fixed`, `sum`, `product` or `scaled`.
+/// - `permutations`: the advice columns of each permutation argument.
+/// - `lookups`: the `input_columns` and `table_columns` of each lookup
+///   argument.
+pub fn export_json<F: Field>(cs: &ConstraintSystem<F>) -> String {
+    let gates: Vec<_> = cs
+        .gates
+        .iter()
+        .map(|gate| expression_json(cs, gate))
+        .collect();
+
+    let permutations: Vec<_> = cs
+        .permutations
+        .iter()
+        .map(|argument| {
+            let columns: Vec<_> = argument
+                .get_columns()
+                .iter()
+                .map(|column| column.index().to_string())
+                .collect();
+            format!(r#"{{"columns":[{}]}}"#, columns.join(","))
+        })
+        .collect();
+
+    let lookups: Vec<_> = cs
+        .lookups
+        .iter()
+        .map(|argument| {
+            format!(
+                r#"{{"input_columns":{},"table_columns":{}}}"#,
+                columns_json(&argument.input_columns),
+                columns_json(&argument.table_columns)
+            )
+        })
+        .collect();
+
+    format!(
+        concat!(
+            r#"{{"columns":{{"advice":{},"aux":{},"fixed":{}}},"#,
+            r#""queries":{{"advice":{},"aux":{},"fixed":{}}},"#,
+            r#""gates":[{}],"permutations":[{}],"lookups":[{}]}}"#
+        ),
+        cs.num_advice_columns,
+        cs.num_aux_columns,
+        cs.num_fixed_columns,
+        queries_json(&cs.advice_queries),
+        queries_json(&cs.aux_queries),
+        queries_json(&cs.fixed_queries),
+        gates.join(","),
+        permutations.join(","),
+        lookups.join(","),
+    )
+}
+
+/// Exports a [`ConstraintSystem`] as a Graphviz DOT digraph.
+///
+/// Each column, gate, permutation argument and lookup argument is a node. Edges
+/// run from a column to every gate and argument that uses it; edges into gates
+/// are labelled with the rotations the column is queried at.
+pub fn export_dot<F: Field>(cs: &ConstraintSystem<F>) -> String {
+    let mut dot = String::from("digraph constraint_system {\n");
+
+    let column_node = |column: &Column<Any>| {
+        format!(
+            "{}_{}",
+            column_type_name(column.column_type()),
+            column.index()
+        )
+    };
+
+    for (name, count) in [
+        ("advice", cs.num_advice_columns),
+        ("aux", cs.num_aux_columns),
+        ("fixed", cs.num_fixed_columns),
+    ]
+    .iter()
+    {
+        for index in 0..*count {
+            dot.push_str(&format!(
+                "  {}_{} [shape=box, label=\"{} {}\"];\n",
+                name, index, name, index
+            ));
+        }
+    }
+
+    for (i, gate) in cs.gates.iter().enumerate() {
+        dot.push_str(&format!(
+            "  gate_{} [shape=ellipse, label=\"gate {} (degree {})\"];\n",
+            i,
+            i,
+            gate.degree()
+        ));
+
+        // Collect the distinct (column, rotation) pairs the gate queries.
+        let queries: BTreeSet<(String, i32)> = gate.evaluate(
+            &|index| {
+                let (column, at) = cs.fixed_queries[index];
+                Some((column_node(&column.into()), at.0))
+                    .into_iter()
+                    .collect()
+            },
+            &|index| {
+                let (column, at) = cs.advice_queries[index];
+                Some((column_node(&column.into()), at.0))
+                    .into_iter()
+                    .collect()
+            },
+            &|index| {
+                let (column, at) = cs.aux_queries[index];
+                Some((column_node(&column.into()), at.0))
+                    .into_iter()
+                    .collect()
+            },
+            &|a: BTreeSet<_>, b| a.union(&b).cloned().collect(),
+            &|a: BTreeSet<_>, b| a.union(&b).cloned().collect(),
+            &|a, _| a,
+        );
+
+        let mut columns: Vec<(String, Vec<i32>)> = vec![];
+        for (column, at) in queries {
+            match columns.last_mut() {
+                Some((last, rotations)) if *last == column => rotations.push(at),
+                _ => columns.push((column, vec![at])),
+            }
+        }
+        for (column, rotations) in columns {
+            let rotations: Vec<_> = rotations.iter().map(|at| at.to_string()).collect();
+            dot.push_str(&format!(
+                "  {} -> gate_{} [label=\"{}\"];\n",
+                column,
+                i,
+                rotations.join(", ")
+            ));
+        }
+    }
+
+    for (i, argument) in cs.permutations.iter().enumerate() {
+        dot.push_str(&format!(
+            "  permutation_{} [shape=diamond, label=\"permutation {}\"];\n",
+            i, i
+        ));
+        for column in argument.get_columns() {
+            dot.push_str(&format!(
+                "  {} -> permutation_{};\n",
+                column_node(&(*column).into()),
+                i
+            ));
+        }
+    }
+
+    for (i, argument) in cs.lookups.iter().enumerate() {
+        dot.push_str(&format!(
+            "  lookup_{} [shape=hexagon, label=\"lookup {}\"];\n",
+            i, i
+        ));
+        for column in argument.input_columns.iter() {
+            dot.push_str(&format!(
+                "  {} -> lookup_{} [label=\"input\"];\n",
+                column_node(column),
+                i
+            ));
+        }
+        for column in argument.table_columns.iter() {
+            dot.push_str(&format!(
+                "  {} -> lookup_{} [label=\"table\"];\n",
+                column_node(column),
+                i
+            ));
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+#[test]
+fn test_export() {
+    use crate::arithmetic::FieldExt;
+    use crate::pasta::Fp;
+
+    let mut meta = ConstraintSystem::<Fp>::default();
+    let a = meta.advice_column();
+    let b = meta.advice_column();
+    let s = meta.fixed_column();
+
+    meta.permutation(&[a, b]);
+    meta.lookup(&[a.into()], &[s.into()]);
+    meta.create_gate(|meta| {
+        let a_next = meta.query_advice(a, 1);
+        let a = meta.query_advice(a, 0);
+        let s = meta.query_fixed(s, 0);
+
+        s * (a + a_next * Fp::from_u64(2))
+    });
+
+    assert_eq!(
+        export_json(&meta),
+        concat!(
+            r#"{"columns":{"advice":2,"aux":0,"fixed":1},"#,
+            r#""queries":{"advice":[{"column":0,"rotation":0},{"column":1,"rotation":0},"#,
+            r#"{"column":0,"rotation":1}],"#,
+            r#""aux":[],"fixed":[{"column":0,"rotation":0}]},"#,
+            r#""gates":[{"type":"product","left":{"type":"fixed","query":0,"column":0,"rotation":0},"#,
+            r#""right":{"type":"sum","left":{"type":"advice","query":0,"column":0,"rotation":0},"#,
+            r#""right":{"type":"scaled","expression":{"type":"advice","query":2,"column":0,"rotation":1},"#,
+            r#""scalar":"0x0000000000000000000000000000000000000000000000000000000000000002"}}}],"#,
+            r#""permutations":[{"columns":[0,1]}],"#,
+            r#""lookups":[{"input_columns":[{"type":"advice","index":0}],"#,
+            r#""table_columns":[{"type":"fixed","index":0}]}]}"#,
+        )
+    );
+
+    let dot = export_dot(&meta);
+    assert!(dot.starts_with("digraph constraint_system {\n"));
+    assert!(dot.contains("  advice_0 -> gate_0 [label=\"0, 1\"];\n"));
+    assert!(dot.contains("  fixed_0 -> gate_0 [label=\"0\"];\n"));
+    assert!(!dot.contains("advice_1 -> gate_0"));
+    assert!(dot.contains("  advice_1 -> permutation_0;\n"));
+    assert!(dot.contains("  fixed_0 -> lookup_0 [label=\"table\"];\n"));
+}
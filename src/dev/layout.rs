@@ -0,0 +1,399 @@
+//! Rendering of the floor plan of a circuit, showing which cells it assigns
+//! and the copy constraints between them.
+
+use ff::Field;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use crate::plonk::{Advice, Any, Assignment, Circuit, Column, ConstraintSystem, Error, Fixed};
+
+/// Width of a column in the rendered layout.
+const COLUMN_WIDTH: usize = 24;
+
+/// Height of a row in the rendered layout.
+const ROW_HEIGHT: usize = 6;
+
+/// Height of the column labels above the layout.
+const HEADER_HEIGHT: usize = 24;
+
+/// Renders the floor plan of a circuit as an SVG image.
+///
+/// The circuit is synthesized without a witness. Columns are drawn left to
+/// right in the order advice, aux, fixed, with row 0 at the top:
+///
+/// - Assigned advice cells are drawn in green.
+/// - Assigned fixed cells are drawn in light yellow, or in orange when their
+///   value is non-zero (for example, an active selector).
+/// - Copy constraints are drawn as red lines between the two cells.
+/// - Columns that hold a lookup table are outlined in blue.
+///
+/// # Examples
+///
+/// ```no_run
+/// use halo2::dev::CircuitLayout;
+/// # use halo2::arithmetic::FieldExt;
+/// # use halo2::pasta::Fp;
+/// # use halo2::plonk::{Advice, Assignment, Circuit, Column, ConstraintSystem, Error};
+/// #
+/// # #[derive(Default)]
+/// # struct MyCircuit;
+/// #
+/// # impl<F: FieldExt> Circuit<F> for MyCircuit {
+/// #     type Config = Column<Advice>;
+/// #
+/// #     fn configure(meta: &mut ConstraintSystem<F>) -> Column<Advice> {
+/// #         let a = meta.advice_column();
+/// #         meta.create_gate(|meta| {
+/// #             let a = meta.query_advice(a, 0);
+/// #             a.clone() * a.clone() - a
+/// #         });
+/// #         a
+/// #     }
+/// #
+/// #     fn synthesize(&self, cs: &mut impl Assignment<F>, a: Column<Advice>) -> Result<(), Error> {
+/// #         cs.assign_advice(a, 0, || Ok(F::one()))
+/// #     }
+/// # }
+/// #
+///
+/// let circuit = MyCircuit::default();
+/// let svg = CircuitLayout::default()
+///     .render::<Fp, _>(5, &circuit)
+///     .unwrap();
+/// std::fs::write("layout.svg", svg).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct CircuitLayout {
+    labels: bool,
+}
+
+impl Default for CircuitLayout {
+    fn default() -> Self {
+        CircuitLayout { labels: true }
+    }
+}
+
+impl CircuitLayout {
+    /// Sets whether column labels are drawn above the layout.
+    pub fn show_labels(mut self, labels: bool) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Synthesizes the given circuit over $2^k$ rows and renders its layout.
+    pub fn render<F: Field, ConcreteCircuit: Circuit<F>>(
+        &self,
+        k: u32,
+        circuit: &ConcreteCircuit,
+    ) -> Result<String, Error> {
+        let n = 1usize << k;
+
+        let mut cs = ConstraintSystem::default();
+        let config = ConcreteCircuit::configure(&mut cs);
+
+        let mut layout = Layout {
+            n,
+            advice: vec![vec![false; n]; cs.num_advice_columns],
+            fixed: vec![vec![None; n]; cs.num_fixed_columns],
+            permutations: cs
+                .permutations
+                .iter()
+                .map(|p| p.get_columns().len())
+                .collect(),
+            copies: vec![],
+        };
+        circuit.synthesize(&mut layout, config)?;
+
+        let tables: BTreeSet<(bool, usize)> = cs
+            .lookups
+            .iter()
+            .flat_map(|lookup| lookup.table_columns.iter())
+            .map(|column| column_position(&cs, *column))
+            .collect();
+
+        let num_columns = cs.num_advice_columns + cs.num_aux_columns + cs.num_fixed_columns;
+        let header = if self.labels { HEADER_HEIGHT } else { 0 };
+        let width = num_columns * COLUMN_WIDTH;
+        let height = header + n * ROW_HEIGHT;
+
+        let mut svg = String::new();
+        self.write_svg(&mut svg, &cs, &layout, &tables, header, width, height)
+            .expect("writing to a String cannot fail");
+        Ok(svg)
+    }
+
+    fn write_svg<F: Field>(
+        &self,
+        svg: &mut String,
+        cs: &ConstraintSystem<F>,
+        layout: &Layout,
+        tables: &BTreeSet<(bool, usize)>,
+        header: usize,
+        width: usize,
+        height: usize,
+    ) -> std::fmt::Result {
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = width,
+            h = height,
+        )?;
+        writeln!(
+            svg,
+            r#"<rect x="0" y="0" width="{}" height="{}" fill="white"/>"#,
+            width, height
+        )?;
+
+        let columns = (0..cs.num_advice_columns)
+            .map(|i| ("A", i, "#ffffff"))
+            .chain((0..cs.num_aux_columns).map(|i| ("X", i, "#e8e8ff")))
+            .chain((0..cs.num_fixed_columns).map(|i| ("F", i, "#f0f0f0")));
+        for (x, (label, index, fill)) in columns.enumerate() {
+            writeln!(
+                svg,
+                r##"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" stroke="#c0c0c0"/>"##,
+                x * COLUMN_WIDTH,
+                header,
+                COLUMN_WIDTH,
+                layout.n * ROW_HEIGHT,
+                fill,
+            )?;
+            if self.labels {
+                writeln!(
+                    svg,
+                    r#"<text x="{}" y="{}" font-size="10" text-anchor="middle">{}{}</text>"#,
+                    x * COLUMN_WIDTH + COLUMN_WIDTH / 2,
+                    HEADER_HEIGHT * 2 / 3,
+                    label,
+                    index,
+                )?;
+            }
+        }
+
+        let advice_x = |column: usize| column * COLUMN_WIDTH;
+        let fixed_x =
+            |column: usize| (cs.num_advice_columns + cs.num_aux_columns + column) * COLUMN_WIDTH;
+        let row_y = |row: usize| header + row * ROW_HEIGHT;
+
+        for (column, rows) in layout.advice.iter().enumerate() {
+            for (row, _) in rows.iter().enumerate().filter(|(_, assigned)| **assigned) {
+                writeln!(
+                    svg,
+                    r##"<rect x="{}" y="{}" width="{}" height="{}" fill="#98c379"/>"##,
+                    advice_x(column),
+                    row_y(row),
+                    COLUMN_WIDTH,
+                    ROW_HEIGHT,
+                )?;
+            }
+        }
+
+        for (column, rows) in layout.fixed.iter().enumerate() {
+            for (row, non_zero) in rows
+                .iter()
+                .enumerate()
+                .filter_map(|(row, cell)| cell.map(|non_zero| (row, non_zero)))
+            {
+                writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    fixed_x(column),
+                    row_y(row),
+                    COLUMN_WIDTH,
+                    ROW_HEIGHT,
+                    if non_zero { "#d19a66" } else { "#f5e6b8" },
+                )?;
+            }
+        }
+
+        for (is_advice, column) in tables.iter() {
+            let x = if *is_advice {
+                advice_x(*column)
+            } else {
+                fixed_x(*column)
+            };
+            writeln!(
+                svg,
+                r##"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="#4a90d9" stroke-width="2"/>"##,
+                x + 1,
+                header + 1,
+                COLUMN_WIDTH - 2,
+                layout.n * ROW_HEIGHT - 2,
+            )?;
+        }
+
+        for &(permutation, left_column, left_row, right_column, right_row) in layout.copies.iter() {
+            let columns = cs.permutations[permutation].get_columns();
+            writeln!(
+                svg,
+                r##"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="#e06c75" stroke-width="1"/>"##,
+                advice_x(columns[left_column].index()) + COLUMN_WIDTH / 2,
+                row_y(left_row) + ROW_HEIGHT / 2,
+                advice_x(columns[right_column].index()) + COLUMN_WIDTH / 2,
+                row_y(right_row) + ROW_HEIGHT / 2,
+            )?;
+        }
+
+        writeln!(svg, "</svg>")
+    }
+}
+
+/// Returns whether the column is an advice column, and its index. Aux columns
+/// are drawn between the advice and fixed columns, so are returned as advice
+/// columns offset past the real ones.
+fn column_position<F: Field>(cs: &ConstraintSystem<F>, column: Column<Any>) -> (bool, usize) {
+    match column.column_type() {
+        Any::Advice => (true, column.index()),
+        Any::Aux => (true, cs.num_advice_columns + column.index()),
+        Any::Fixed => (false, column.index()),
+    }
+}
+
+/// Records which cells a circuit assigns during synthesis.
+struct Layout {
+    n: usize,
+    advice: Vec<Vec<bool>>,
+    /// `Some(true)` if the cell is assigned a non-zero value.
+    fixed: Vec<Vec<Option<bool>>>,
+    /// The number of columns in each permutation.
+    permutations: Vec<usize>,
+    copies: Vec<(usize, usize, usize, usize, usize)>,
+}
+
+impl<F: Field> Assignment<F> for Layout {
+    fn assign_advice(
+        &mut self,
+        column: Column<Advice>,
+        row: usize,
+        _: impl FnOnce() -> Result<F, Error>,
+    ) -> Result<(), Error> {
+        // We are only interested in where cells are, not their values
+        *self
+            .advice
+            .get_mut(column.index())
+            .and_then(|v| v.get_mut(row))
+            .ok_or(Error::BoundsFailure)? = true;
+
+        Ok(())
+    }
+
+    fn assign_fixed(
+        &mut self,
+        column: Column<Fixed>,
+        row: usize,
+        to: impl FnOnce() -> Result<F, Error>,
+    ) -> Result<(), Error> {
+        *self
+            .fixed
+            .get_mut(column.index())
+            .and_then(|v| v.get_mut(row))
+            .ok_or(Error::BoundsFailure)? = Some(to()? != F::zero());
+
+        Ok(())
+    }
+
+    fn copy(
+        &mut self,
+        permutation: usize,
+        left_column: usize,
+        left_row: usize,
+        right_column: usize,
+        right_row: usize,
+    ) -> Result<(), Error> {
+        let columns = *self
+            .permutations
+            .get(permutation)
+            .ok_or(Error::BoundsFailure)?;
+        if left_column >= columns || right_column >= columns {
+            return Err(Error::BoundsFailure);
+        }
+        if left_row >= self.n || right_row >= self.n {
+            return Err(Error::BoundsFailure);
+        }
+
+        self.copies
+            .push((permutation, left_column, left_row, right_column, right_row));
+        Ok(())
+    }
+}
+
+#[test]
+fn test_circuit_layout() {
+    use crate::arithmetic::FieldExt;
+    use crate::pasta::Fp;
+
+    #[derive(Clone, Copy)]
+    struct TestConfig {
+        a: Column<Advice>,
+        b: Column<Advice>,
+        s: Column<Fixed>,
+        t: Column<Fixed>,
+    }
+
+    // The permutation and right column of the copy constraint.
+    struct TestCircuit(usize, usize);
+
+    impl Circuit<Fp> for TestCircuit {
+        type Config = TestConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TestConfig {
+            let a = meta.advice_column();
+            let b = meta.advice_column();
+            let s = meta.fixed_column();
+            let t = meta.fixed_column();
+
+            meta.permutation(&[a, b]);
+            meta.lookup(&[b.into()], &[t.into()]);
+
+            meta.create_gate(|meta| {
+                let a = meta.query_advice(a, 0);
+                let b = meta.query_advice(b, 0);
+                let s = meta.query_fixed(s, 0);
+
+                s * (a + b * -Fp::one())
+            });
+
+            TestConfig { a, b, s, t }
+        }
+
+        fn synthesize(
+            &self,
+            cs: &mut impl Assignment<Fp>,
+            config: TestConfig,
+        ) -> Result<(), Error> {
+            for row in 0..4 {
+                cs.assign_advice(config.a, row, || Err(Error::SynthesisError))?;
+                cs.assign_advice(config.b, row, || Err(Error::SynthesisError))?;
+                cs.assign_fixed(config.s, row, || Ok(Fp::one()))?;
+                cs.assign_fixed(config.t, row, || Ok(Fp::from_u64(row as u64)))?;
+            }
+            cs.copy(self.0, 0, 0, self.1, 3)
+        }
+    }
+
+    let svg = CircuitLayout::default()
+        .render::<Fp, _>(3, &TestCircuit(0, 1))
+        .unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    // Eight advice cells
+    assert_eq!(svg.matches("#98c379").count(), 8);
+    // The selector is active on four rows, and the table is non-zero on three
+    assert_eq!(svg.matches("#d19a66").count(), 7);
+    assert_eq!(svg.matches("#f5e6b8").count(), 1);
+    assert_eq!(svg.matches("#4a90d9").count(), 1);
+    assert_eq!(svg.matches("<line").count(), 1);
+
+    for (k, circuit) in [
+        (1, TestCircuit(0, 1)),
+        (3, TestCircuit(1, 1)),
+        (3, TestCircuit(0, 2)),
+    ]
+    .iter()
+    {
+        assert!(matches!(
+            CircuitLayout::default().render::<Fp, _>(*k, circuit),
+            Err(Error::BoundsFailure)
+        ));
+    }
+}
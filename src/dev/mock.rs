@@ -0,0 +1,275 @@
+//! A prover that checks a circuit's constraints on its witness directly,
+//! without creating a proof.
+
+use ff::Field;
+
+use crate::plonk::{
+    Advice, Any, Assignment, Circuit, Column, ConstraintSystem, Error, Expression, Fixed,
+};
+use crate::poly::Rotation;
+
+/// A constraint of a circuit that its witness does not satisfy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifyFailure {
+    /// The gate with the given index is not zero at the given row.
+    Gate {
+        /// The index of the gate, in the order the circuit created it.
+        gate_index: usize,
+        /// The row at which the gate is not zero.
+        row: usize,
+    },
+    /// The inputs of the lookup with the given index at the given row are
+    /// not a row of its table.
+    Lookup {
+        /// The index of the lookup, in the order the circuit created it.
+        lookup_index: usize,
+        /// The row whose inputs are not in the table.
+        row: usize,
+    },
+    /// A copy constraint of the permutation with the given index relates two
+    /// cells with different values.
+    Permutation {
+        /// The index of the permutation, in the order the circuit created it.
+        perm_index: usize,
+        /// The index within the permutation of the column of the first cell.
+        column: usize,
+        /// The row of the first cell.
+        row: usize,
+    },
+}
+
+/// A cell in a permutation, as the index of its column within the permutation
+/// and its row.
+type PermutationCell = (usize, usize);
+
+/// A prover that synthesizes a circuit over $2^k$ rows and checks its gates,
+/// lookups and copy constraints on the assigned values, reporting the first
+/// one that fails.
+///
+/// Cells that the circuit does not assign are zero, as they are in the
+/// [`Proof`](crate::plonk::Proof)s created for it.
+///
+/// # Examples
+///
+/// ```
+/// use halo2::dev::{MockProver, VerifyFailure};
+/// # use halo2::arithmetic::FieldExt;
+/// # use halo2::pasta::Fp;
+/// # use halo2::plonk::{Advice, Assignment, Circuit, Column, ConstraintSystem, Error};
+/// #
+/// # struct MyCircuit(Fp);
+/// #
+/// # impl Circuit<Fp> for MyCircuit {
+/// #     type Config = Column<Advice>;
+/// #
+/// #     fn configure(meta: &mut ConstraintSystem<Fp>) -> Column<Advice> {
+/// #         let a = meta.advice_column();
+/// #         meta.create_gate(|meta| {
+/// #             let a = meta.query_advice(a, 0);
+/// #             a.clone() * a.clone() - a
+/// #         });
+/// #         a
+/// #     }
+/// #
+/// #     fn synthesize(&self, cs: &mut impl Assignment<Fp>, a: Column<Advice>) -> Result<(), Error> {
+/// #         cs.assign_advice(a, 0, || Ok(self.0))
+/// #     }
+/// # }
+///
+/// // The gate requires a to be a bit.
+/// let prover = MockProver::run(3, &MyCircuit(Fp::one()), vec![]).unwrap();
+/// assert_eq!(prover.verify(), Ok(()));
+///
+/// let prover = MockProver::run(3, &MyCircuit(Fp::from_u64(2)), vec![]).unwrap();
+/// assert_eq!(
+///     prover.verify(),
+///     Err(VerifyFailure::Gate {
+///         gate_index: 0,
+///         row: 0
+///     })
+/// );
+/// ```
+#[derive(Debug)]
+pub struct MockProver<F: Field> {
+    n: usize,
+    cs: ConstraintSystem<F>,
+    fixed: Vec<Vec<F>>,
+    advice: Vec<Vec<F>>,
+    aux: Vec<Vec<F>>,
+    copies: Vec<Vec<(PermutationCell, PermutationCell)>>,
+}
+
+impl<F: Field> Assignment<F> for MockProver<F> {
+    fn assign_advice(
+        &mut self,
+        column: Column<Advice>,
+        row: usize,
+        to: impl FnOnce() -> Result<F, Error>,
+    ) -> Result<(), Error> {
+        *self
+            .advice
+            .get_mut(column.index())
+            .and_then(|column| column.get_mut(row))
+            .ok_or(Error::BoundsFailure)? = to()?;
+
+        Ok(())
+    }
+
+    fn assign_fixed(
+        &mut self,
+        column: Column<Fixed>,
+        row: usize,
+        to: impl FnOnce() -> Result<F, Error>,
+    ) -> Result<(), Error> {
+        *self
+            .fixed
+            .get_mut(column.index())
+            .and_then(|column| column.get_mut(row))
+            .ok_or(Error::BoundsFailure)? = to()?;
+
+        Ok(())
+    }
+
+    fn copy(
+        &mut self,
+        permutation: usize,
+        left_column: usize,
+        left_row: usize,
+        right_column: usize,
+        right_row: usize,
+    ) -> Result<(), Error> {
+        let num_columns = self
+            .cs
+            .permutations
+            .get(permutation)
+            .ok_or(Error::BoundsFailure)?
+            .get_columns()
+            .len();
+        if left_column >= num_columns
+            || left_row >= self.n
+            || right_column >= num_columns
+            || right_row >= self.n
+        {
+            return Err(Error::BoundsFailure);
+        }
+
+        self.copies[permutation].push(((left_column, left_row), (right_column, right_row)));
+
+        Ok(())
+    }
+}
+
+impl<F: Field> MockProver<F> {
+    /// Synthesizes `circuit` over $2^k$ rows, with the given values of its aux
+    /// columns.
+    ///
+    /// Returns [`Error::IncompatibleParams`] if `aux` does not have one column
+    /// of $2^k$ values for each aux column of the circuit.
+    pub fn run<ConcreteCircuit: Circuit<F>>(
+        k: u32,
+        circuit: &ConcreteCircuit,
+        aux: Vec<Vec<F>>,
+    ) -> Result<Self, Error> {
+        let n = 1usize << k;
+
+        let mut cs = ConstraintSystem::default();
+        let config = ConcreteCircuit::configure(&mut cs);
+
+        if aux.len() != cs.num_aux_columns || aux.iter().any(|column| column.len() != n) {
+            return Err(Error::IncompatibleParams);
+        }
+
+        let mut prover = MockProver {
+            n,
+            fixed: vec![vec![F::zero(); n]; cs.num_fixed_columns],
+            advice: vec![vec![F::zero(); n]; cs.num_advice_columns],
+            aux,
+            copies: vec![vec![]; cs.permutations.len()],
+            cs,
+        };
+        circuit.synthesize(&mut prover, config)?;
+
+        Ok(prover)
+    }
+
+    /// Checks the gates, then the lookups, then the copy constraints of the
+    /// circuit, and returns the first that the assigned values do not
+    /// satisfy.
+    pub fn verify(&self) -> Result<(), VerifyFailure> {
+        let value = |column: &Column<Any>, row: usize| match column.column_type() {
+            Any::Advice => self.advice[column.index()][row],
+            Any::Fixed => self.fixed[column.index()][row],
+            Any::Aux => self.aux[column.index()][row],
+        };
+
+        for (gate_index, gate) in self.cs.gates.iter().enumerate() {
+            for row in 0..self.n {
+                if !self.evaluate(gate, row).is_zero() {
+                    return Err(VerifyFailure::Gate { gate_index, row });
+                }
+            }
+        }
+
+        for (lookup_index, lookup) in self.cs.lookups.iter().enumerate() {
+            let table: Vec<Vec<F>> = (0..self.n)
+                .map(|row| {
+                    lookup
+                        .table_columns
+                        .iter()
+                        .map(|column| value(column, row))
+                        .collect()
+                })
+                .collect();
+            for row in 0..self.n {
+                let inputs: Vec<F> = lookup
+                    .input_columns
+                    .iter()
+                    .map(|column| value(column, row))
+                    .collect();
+                if !table.contains(&inputs) {
+                    return Err(VerifyFailure::Lookup { lookup_index, row });
+                }
+            }
+        }
+
+        for (perm_index, copies) in self.copies.iter().enumerate() {
+            let columns = self.cs.permutations[perm_index].get_columns();
+            for &((left_column, left_row), (right_column, right_row)) in copies.iter() {
+                let left = self.advice[columns[left_column].index()][left_row];
+                let right = self.advice[columns[right_column].index()][right_row];
+                if left != right {
+                    return Err(VerifyFailure::Permutation {
+                        perm_index,
+                        column: left_column,
+                        row: left_row,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Evaluates `gate` at `row`, with rotations wrapping around the $2^k$
+    /// rows.
+    fn evaluate(&self, gate: &Expression<F>, row: usize) -> F {
+        let rotate = |at: Rotation| (row as i64 + at.0 as i64).rem_euclid(self.n as i64) as usize;
+        gate.evaluate(
+            &|index| {
+                let (column, at) = self.cs.fixed_queries[index];
+                self.fixed[column.index()][rotate(at)]
+            },
+            &|index| {
+                let (column, at) = self.cs.advice_queries[index];
+                self.advice[column.index()][rotate(at)]
+            },
+            &|index| {
+                let (column, at) = self.cs.aux_queries[index];
+                self.aux[column.index()][rotate(at)]
+            },
+            &|a, b| a + b,
+            &|a, b| a * b,
+            &|a, scalar| a * scalar,
+        )
+    }
+}
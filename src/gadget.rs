@@ -0,0 +1,70 @@
+//! Gadgets that lay out commonly-used operations in a circuit.
+
+use ff::Field;
+
+use crate::plonk::{Advice, Assignment, Column, ConstraintSystem, Error};
+
+mod arithmetic;
+mod endoscale;
+mod hash;
+mod split;
+pub mod verifier;
+
+pub use arithmetic::{ArithmeticConfig, ArithmeticRow};
+pub use endoscale::{
+    EndoscalePointConfig, EndoscaleScalarConfig, EndoscaledPoint, EndoscaledScalar,
+};
+pub use hash::{DummyHashConfig, HashState};
+pub use split::{SplitConfig, SplitSqueeze};
+
+/// An advice cell assigned by a gadget, which the caller can constrain to be
+/// equal to other cells.
+#[derive(Clone, Copy, Debug)]
+pub struct AssignedCell<F> {
+    /// The column of the cell.
+    pub column: Column<Advice>,
+    /// The row of the cell.
+    pub row: usize,
+    /// The value of the cell, or `None` when synthesizing without a witness.
+    pub value: Option<F>,
+}
+
+/// A permutation argument over some advice columns, with which cells that
+/// gadgets assign in those columns can be constrained to be equal.
+#[derive(Clone, Debug)]
+pub struct Permutation {
+    index: usize,
+    columns: Vec<Column<Advice>>,
+}
+
+impl Permutation {
+    /// Configures a permutation argument over `columns`.
+    pub fn configure<F: Field>(meta: &mut ConstraintSystem<F>, columns: &[Column<Advice>]) -> Self {
+        Permutation {
+            index: meta.permutation(columns),
+            columns: columns.to_vec(),
+        }
+    }
+
+    /// Returns whether `column` is one of the columns of this argument.
+    pub fn contains(&self, column: Column<Advice>) -> bool {
+        self.columns.contains(&column)
+    }
+
+    /// Constrains two cells to be equal. Both must be in columns of this
+    /// argument.
+    pub fn copy<F: Field>(
+        &self,
+        cs: &mut impl Assignment<F>,
+        left: &AssignedCell<F>,
+        right: &AssignedCell<F>,
+    ) -> Result<(), Error> {
+        let index = |cell: &AssignedCell<F>| {
+            self.columns
+                .iter()
+                .position(|column| *column == cell.column)
+                .ok_or(Error::SynthesisError)
+        };
+        cs.copy(self.index, index(left)?, left.row, index(right)?, right.row)
+    }
+}
@@ -0,0 +1,138 @@
+//! A gadget for arithmetic on cells, with one operation per row.
+//!
+//! Each row constrains `q_a * a + q_b * b + q_m * a * b + q_const = q_c * c`,
+//! where the coefficients are fixed and `a`, `b` and `c` are advice cells,
+//! which the caller connects to other cells with a [`Permutation`] over
+//! [`ArithmeticConfig::columns`].
+//!
+//! [`Permutation`]: super::Permutation
+
+use ff::Field;
+
+use super::AssignedCell;
+use crate::arithmetic::FieldExt;
+use crate::plonk::{Advice, Assignment, Column, ConstraintSystem, Error, Fixed};
+
+/// The coefficients of a row of [`ArithmeticConfig`].
+#[derive(Clone, Copy, Debug)]
+pub struct ArithmeticRow<F> {
+    /// The coefficient of `a`.
+    pub q_a: F,
+    /// The coefficient of `b`.
+    pub q_b: F,
+    /// The coefficient of `a * b`.
+    pub q_m: F,
+    /// The constant term.
+    pub q_const: F,
+    /// The coefficient of `c`, which is zero for rows that only constrain
+    /// `a` and `b`.
+    pub q_c: F,
+}
+
+impl<F: Field> Default for ArithmeticRow<F> {
+    fn default() -> Self {
+        ArithmeticRow {
+            q_a: F::zero(),
+            q_b: F::zero(),
+            q_m: F::zero(),
+            q_const: F::zero(),
+            q_c: F::zero(),
+        }
+    }
+}
+
+/// A gadget that constrains `q_a * a + q_b * b + q_m * a * b + q_const` to
+/// equal `q_c * c` on each row.
+#[derive(Clone, Copy, Debug)]
+pub struct ArithmeticConfig {
+    a: Column<Advice>,
+    b: Column<Advice>,
+    c: Column<Advice>,
+    q_a: Column<Fixed>,
+    q_b: Column<Fixed>,
+    q_m: Column<Fixed>,
+    q_const: Column<Fixed>,
+    q_c: Column<Fixed>,
+}
+
+impl ArithmeticConfig {
+    /// Configures the columns and gate of this gadget.
+    pub fn configure<F: FieldExt>(meta: &mut ConstraintSystem<F>) -> Self {
+        let a = meta.advice_column();
+        let b = meta.advice_column();
+        let c = meta.advice_column();
+        let q_a = meta.fixed_column();
+        let q_b = meta.fixed_column();
+        let q_m = meta.fixed_column();
+        let q_const = meta.fixed_column();
+        let q_c = meta.fixed_column();
+
+        meta.create_gate(|meta| {
+            let a = meta.query_advice(a, 0);
+            let b = meta.query_advice(b, 0);
+            let c = meta.query_advice(c, 0);
+            let q_a = meta.query_fixed(q_a, 0);
+            let q_b = meta.query_fixed(q_b, 0);
+            let q_m = meta.query_fixed(q_m, 0);
+            let q_const = meta.query_fixed(q_const, 0);
+            let q_c = meta.query_fixed(q_c, 0);
+
+            q_a * a.clone() + q_b * b.clone() + q_m * a * b + q_const - q_c * c
+        });
+
+        ArithmeticConfig {
+            a,
+            b,
+            c,
+            q_a,
+            q_b,
+            q_m,
+            q_const,
+            q_c,
+        }
+    }
+
+    /// Returns the advice columns `a`, `b` and `c`, which must be in a
+    /// permutation argument for the cells of this gadget to be connected to
+    /// other cells.
+    pub fn columns(&self) -> [Column<Advice>; 3] {
+        [self.a, self.b, self.c]
+    }
+
+    /// Assigns a row at `offset`, returning the cells of `a`, `b` and `c`.
+    /// The values must satisfy the constraint of the row for the proof to be
+    /// valid.
+    pub fn assign<F: FieldExt>(
+        &self,
+        cs: &mut impl Assignment<F>,
+        offset: usize,
+        values: [Option<F>; 3],
+        row: ArithmeticRow<F>,
+    ) -> Result<[AssignedCell<F>; 3], Error> {
+        cs.assign_fixed(self.q_a, offset, || Ok(row.q_a))?;
+        cs.assign_fixed(self.q_b, offset, || Ok(row.q_b))?;
+        cs.assign_fixed(self.q_m, offset, || Ok(row.q_m))?;
+        cs.assign_fixed(self.q_const, offset, || Ok(row.q_const))?;
+        cs.assign_fixed(self.q_c, offset, || Ok(row.q_c))?;
+
+        let mut cells = [AssignedCell {
+            column: self.a,
+            row: offset,
+            value: None,
+        }; 3];
+        for ((cell, column), value) in cells
+            .iter_mut()
+            .zip(self.columns().iter())
+            .zip(values.iter())
+        {
+            cs.assign_advice(*column, offset, || value.ok_or(Error::SynthesisError))?;
+            *cell = AssignedCell {
+                column: *column,
+                row: offset,
+                value: *value,
+            };
+        }
+
+        Ok(cells)
+    }
+}
@@ -0,0 +1,814 @@
+//! In-circuit versions of Algorithm 1 from the [Halo][halo] paper, which maps
+//! a 128-bit [`Challenge`] to a scalar in the same way as
+//! [`ChallengeScalar::from`](crate::transcript::ChallengeScalar), or multiplies
+//! a point by that scalar directly using the curve endomorphism.
+//!
+//! Both gadgets use [`ROWS`](EndoscaleScalarConfig::ROWS) rows starting at the
+//! offset they are assigned at. The first row initializes the accumulator,
+//! each of the next 64 rows consumes two bits of the challenge (most
+//! significant first), and the last row holds the outputs.
+//!
+//! [halo]: https://eprint.iacr.org/2019/1021
+
+use ff::Field;
+
+use super::AssignedCell;
+use crate::arithmetic::{CurveAffine, FieldExt};
+use crate::plonk::{Advice, Assignment, Column, ConstraintSystem, Error, Fixed};
+use crate::transcript::Challenge;
+
+/// The number of rounds of Algorithm 1, each of which consumes two bits of the
+/// challenge.
+const NUM_ROUNDS: usize = 64;
+
+/// Returns the (negation, endomorphism) bits consumed by the given round.
+fn round_bits(challenge: Challenge, round: usize) -> (bool, bool) {
+    let i = NUM_ROUNDS - 1 - round;
+    (
+        ((challenge.0 >> ((i << 1) + 1)) & 1) == 1,
+        ((challenge.0 >> (i << 1)) & 1) == 1,
+    )
+}
+
+fn bit<F: Field>(b: bool) -> F {
+    if b {
+        F::one()
+    } else {
+        F::zero()
+    }
+}
+
+fn witness<T, F>(values: &Option<T>, f: impl FnOnce(&T) -> F) -> Result<F, Error> {
+    values.as_ref().map(f).ok_or(Error::SynthesisError)
+}
+
+fn div<F: Field>(numerator: F, denominator: F) -> Result<F, Error> {
+    // Incomplete addition fails on exceptional inputs, which only occur
+    // with negligible probability for an independent point and challenge.
+    Option::from(denominator.invert())
+        .map(|inv: F| numerator * inv)
+        .ok_or(Error::SynthesisError)
+}
+
+/// The bits of a challenge, decomposed into rounds, and a running sum that
+/// reconstructs the challenge from them.
+#[derive(Clone, Copy, Debug)]
+struct ChallengeBits {
+    b_neg: Column<Advice>,
+    b_endo: Column<Advice>,
+    running_sum: Column<Advice>,
+}
+
+impl ChallengeBits {
+    fn configure<F: FieldExt>(
+        meta: &mut ConstraintSystem<F>,
+        s_init: Column<Fixed>,
+        s_round: Column<Fixed>,
+    ) -> Self {
+        let b_neg = meta.advice_column();
+        let b_endo = meta.advice_column();
+        let running_sum = meta.advice_column();
+
+        meta.create_gate(|meta| {
+            let sum_next = meta.query_advice(running_sum, 1);
+            let s_init = meta.query_fixed(s_init, 0);
+
+            s_init * sum_next
+        });
+
+        meta.create_gate(|meta| {
+            let s_round = meta.query_fixed(s_round, 0);
+            let b_neg = meta.query_advice(b_neg, 0);
+
+            s_round * (b_neg.clone() * b_neg.clone() - b_neg)
+        });
+
+        meta.create_gate(|meta| {
+            let s_round = meta.query_fixed(s_round, 0);
+            let b_endo = meta.query_advice(b_endo, 0);
+
+            s_round * (b_endo.clone() * b_endo.clone() - b_endo)
+        });
+
+        // sum_next = 4 * sum + 2 * b_neg + b_endo
+        meta.create_gate(|meta| {
+            let sum_next = meta.query_advice(running_sum, 1);
+            let s_round = meta.query_fixed(s_round, 0);
+            let b_neg = meta.query_advice(b_neg, 0);
+            let b_endo = meta.query_advice(b_endo, 0);
+            let sum = meta.query_advice(running_sum, 0);
+
+            s_round * (sum_next - sum * F::from_u64(4) - b_neg * F::from_u64(2) - b_endo)
+        });
+
+        ChallengeBits {
+            b_neg,
+            b_endo,
+            running_sum,
+        }
+    }
+
+    /// Assigns the bits of `challenge`, returning the cell holding the
+    /// challenge as a field element.
+    fn assign<F: FieldExt>(
+        &self,
+        cs: &mut impl Assignment<F>,
+        offset: usize,
+        challenge: Option<Challenge>,
+    ) -> Result<AssignedCell<F>, Error> {
+        let mut sum = challenge.map(|_| F::zero());
+        cs.assign_advice(self.running_sum, offset + 1, || {
+            sum.ok_or(Error::SynthesisError)
+        })?;
+
+        for round in 0..NUM_ROUNDS {
+            let row = offset + 1 + round;
+            let bits = challenge.map(|challenge| round_bits(challenge, round));
+
+            cs.assign_advice(self.b_neg, row, || witness(&bits, |b| bit(b.0)))?;
+            cs.assign_advice(self.b_endo, row, || witness(&bits, |b| bit(b.1)))?;
+
+            sum = sum.and_then(|sum| {
+                bits.map(|(b_neg, b_endo)| {
+                    sum * F::from_u64(4) + bit::<F>(b_neg).double() + bit::<F>(b_endo)
+                })
+            });
+            cs.assign_advice(self.running_sum, row + 1, || {
+                sum.ok_or(Error::SynthesisError)
+            })?;
+        }
+
+        Ok(AssignedCell {
+            column: self.running_sum,
+            row: offset + 1 + NUM_ROUNDS,
+            value: sum,
+        })
+    }
+}
+
+fn assign_selectors<F: Field>(
+    cs: &mut impl Assignment<F>,
+    offset: usize,
+    s_init: Column<Fixed>,
+    s_round: Column<Fixed>,
+) -> Result<(), Error> {
+    cs.assign_fixed(s_init, offset, || Ok(F::one()))?;
+    for round in 0..NUM_ROUNDS {
+        cs.assign_fixed(s_round, offset + 1 + round, || Ok(F::one()))?;
+    }
+    Ok(())
+}
+
+/// The cells assigned by [`EndoscaleScalarConfig::assign`].
+#[derive(Clone, Copy, Debug)]
+pub struct EndoscaledScalar<F> {
+    /// The challenge, as a field element.
+    pub challenge: AssignedCell<F>,
+    /// The scalar the challenge maps to.
+    pub scalar: AssignedCell<F>,
+}
+
+/// A gadget that maps a challenge to a scalar, in a circuit over the field of
+/// the scalar.
+#[derive(Clone, Copy, Debug)]
+pub struct EndoscaleScalarConfig {
+    bits: ChallengeBits,
+    acc: Column<Advice>,
+    s_init: Column<Fixed>,
+    s_round: Column<Fixed>,
+}
+
+impl EndoscaleScalarConfig {
+    /// The number of rows used by this gadget.
+    pub const ROWS: usize = NUM_ROUNDS + 2;
+
+    /// Configures the columns and gates of this gadget.
+    pub fn configure<F: FieldExt>(meta: &mut ConstraintSystem<F>) -> Self {
+        let s_init = meta.fixed_column();
+        let s_round = meta.fixed_column();
+        let bits = ChallengeBits::configure(meta, s_init, s_round);
+        let acc = meta.advice_column();
+
+        // acc_next = 2 * (zeta + 1)
+        meta.create_gate(|meta| {
+            let acc_next = meta.query_advice(acc, 1);
+            let s_init = meta.query_fixed(s_init, 0);
+
+            s_init.clone() * acc_next - s_init.clone() * s_init * (F::ZETA + F::one()).double()
+        });
+
+        // acc_next = 2 * acc + q, where
+        // q = (1 - 2 * b_neg) * (1 + (zeta - 1) * b_endo)
+        meta.create_gate(|meta| {
+            let acc_next = meta.query_advice(acc, 1);
+            let s_round = meta.query_fixed(s_round, 0);
+            let b_neg = meta.query_advice(bits.b_neg, 0);
+            let b_endo = meta.query_advice(bits.b_endo, 0);
+            let acc = meta.query_advice(acc, 0);
+
+            let q = s_round.clone() + b_endo.clone() * (F::ZETA - F::one())
+                - b_neg.clone() * F::from_u64(2)
+                - b_neg * b_endo * (F::ZETA - F::one()).double();
+            s_round * (acc_next - acc * F::from_u64(2) - q)
+        });
+
+        EndoscaleScalarConfig {
+            bits,
+            acc,
+            s_init,
+            s_round,
+        }
+    }
+
+    /// Returns the advice columns holding the challenge and the scalar
+    /// respectively, which must be in permutation arguments for them to be
+    /// connected to other cells.
+    pub fn columns(&self) -> (Column<Advice>, Column<Advice>) {
+        (self.bits.running_sum, self.acc)
+    }
+
+    /// Assigns this gadget at `offset`, mapping `challenge` to a scalar.
+    pub fn assign<F: FieldExt>(
+        &self,
+        cs: &mut impl Assignment<F>,
+        offset: usize,
+        challenge: Option<Challenge>,
+    ) -> Result<EndoscaledScalar<F>, Error> {
+        assign_selectors(cs, offset, self.s_init, self.s_round)?;
+        let challenge_cell = self.bits.assign(cs, offset, challenge)?;
+
+        let mut acc = challenge.map(|_| (F::ZETA + F::one()).double());
+        cs.assign_advice(self.acc, offset + 1, || acc.ok_or(Error::SynthesisError))?;
+
+        for round in 0..NUM_ROUNDS {
+            let row = offset + 1 + round;
+            acc = acc.and_then(|acc| {
+                challenge.map(|challenge| {
+                    let (b_neg, b_endo) = round_bits(challenge, round);
+                    let q = if b_neg { -F::one() } else { F::one() };
+                    let q = if b_endo { q * F::ZETA } else { q };
+                    acc + q + acc
+                })
+            });
+            cs.assign_advice(self.acc, row + 1, || acc.ok_or(Error::SynthesisError))?;
+        }
+
+        Ok(EndoscaledScalar {
+            challenge: challenge_cell,
+            scalar: AssignedCell {
+                column: self.acc,
+                row: offset + 1 + NUM_ROUNDS,
+                value: acc,
+            },
+        })
+    }
+}
+
+/// The cells assigned by [`EndoscalePointConfig::assign`].
+#[derive(Clone, Copy, Debug)]
+pub struct EndoscaledPoint<F> {
+    /// The challenge, as a field element.
+    pub challenge: AssignedCell<F>,
+    /// The coordinates of the point being multiplied.
+    pub base: (AssignedCell<F>, AssignedCell<F>),
+    /// The coordinates of the point multiplied by the scalar the challenge
+    /// maps to.
+    pub result: (AssignedCell<F>, AssignedCell<F>),
+}
+
+/// A gadget that multiplies a point by the scalar a challenge maps to, using
+/// the curve endomorphism, in a circuit over the base field of the curve.
+///
+/// The base point is constrained to lie on the curve, which also excludes the
+/// identity. The additions are incomplete: their slopes are unconstrained
+/// when an intermediate sum would need the addition of a point to itself or
+/// its negation. Algorithm 1 never reaches that case for a valid point that
+/// does not depend on the challenge, except with negligible probability, in
+/// which case assignment fails.
+#[derive(Clone, Copy, Debug)]
+pub struct EndoscalePointConfig {
+    bits: ChallengeBits,
+    x_p: Column<Advice>,
+    y_p: Column<Advice>,
+    x_q: Column<Advice>,
+    y_q: Column<Advice>,
+    x_acc: Column<Advice>,
+    y_acc: Column<Advice>,
+    lambda_1: Column<Advice>,
+    x_r: Column<Advice>,
+    lambda_2: Column<Advice>,
+    s_init: Column<Fixed>,
+    s_round: Column<Fixed>,
+}
+
+/// The witness for a round of [`EndoscalePointConfig`].
+struct PointRound<F> {
+    x_acc: F,
+    y_acc: F,
+    x_q: F,
+    y_q: F,
+    lambda_1: F,
+    x_r: F,
+    lambda_2: F,
+}
+
+impl EndoscalePointConfig {
+    /// The number of rows used by this gadget.
+    pub const ROWS: usize = NUM_ROUNDS + 2;
+
+    /// Configures the columns and gates of this gadget, for points of the
+    /// curve `C`.
+    pub fn configure<C: CurveAffine>(meta: &mut ConstraintSystem<C::Base>) -> Self {
+        let s_init = meta.fixed_column();
+        let s_round = meta.fixed_column();
+        let bits = ChallengeBits::configure(meta, s_init, s_round);
+        let x_p = meta.advice_column();
+        let y_p = meta.advice_column();
+        let x_q = meta.advice_column();
+        let y_q = meta.advice_column();
+        let x_acc = meta.advice_column();
+        let y_acc = meta.advice_column();
+        let lambda_1 = meta.advice_column();
+        let x_r = meta.advice_column();
+        let lambda_2 = meta.advice_column();
+
+        // The base point is the same on every row.
+        for column in [x_p, y_p].iter() {
+            meta.create_gate(|meta| {
+                let p_next = meta.query_advice(*column, 1);
+                let s = meta.query_fixed(s_init, 0) + meta.query_fixed(s_round, 0);
+                let p = meta.query_advice(*column, 0);
+
+                s * (p_next - p)
+            });
+        }
+
+        // y_p^2 = x_p^3 + b, with x_p^2 held in x_q on the first row to keep
+        // the degree of the gates at 3.
+        meta.create_gate(|meta| {
+            let s_init = meta.query_fixed(s_init, 0);
+            let x_p = meta.query_advice(x_p, 0);
+            let x_q = meta.query_advice(x_q, 0);
+
+            s_init * (x_q - x_p.clone() * x_p)
+        });
+        meta.create_gate(|meta| {
+            let s_init = meta.query_fixed(s_init, 0);
+            let x_p = meta.query_advice(x_p, 0);
+            let y_p = meta.query_advice(y_p, 0);
+            let x_q = meta.query_advice(x_q, 0);
+
+            s_init.clone() * (y_p.clone() * y_p - x_q * x_p) - s_init.clone() * s_init * C::b()
+        });
+
+        // phi(P) + P = (zeta^2 * x_p, -y_p), which is doubled with the slope
+        // held in lambda_1 to initialize the accumulator.
+        let zeta_sq = C::Base::ZETA.square();
+        meta.create_gate(|meta| {
+            let s_init = meta.query_fixed(s_init, 0);
+            let x_p = meta.query_advice(x_p, 0);
+            let y_p = meta.query_advice(y_p, 0);
+            let lambda = meta.query_advice(lambda_1, 0);
+
+            // lambda * 2 * (-y_p) = 3 * (zeta^2 * x_p)^2
+            s_init
+                * (lambda * y_p * C::Base::from_u64(2)
+                    + x_p.clone() * x_p * (C::Base::ZETA * C::Base::from_u64(3)))
+        });
+        meta.create_gate(|meta| {
+            let x_acc_next = meta.query_advice(x_acc, 1);
+            let s_init = meta.query_fixed(s_init, 0);
+            let x_p = meta.query_advice(x_p, 0);
+            let lambda = meta.query_advice(lambda_1, 0);
+
+            s_init * (x_acc_next - lambda.clone() * lambda + x_p * zeta_sq.double())
+        });
+        meta.create_gate(|meta| {
+            let x_acc_next = meta.query_advice(x_acc, 1);
+            let y_acc_next = meta.query_advice(y_acc, 1);
+            let s_init = meta.query_fixed(s_init, 0);
+            let x_p = meta.query_advice(x_p, 0);
+            let y_p = meta.query_advice(y_p, 0);
+            let lambda = meta.query_advice(lambda_1, 0);
+
+            s_init * (y_acc_next - lambda * (x_p * zeta_sq - x_acc_next) - y_p)
+        });
+
+        // Q = (x_p * (1 + (zeta - 1) * b_endo), y_p * (1 - 2 * b_neg))
+        meta.create_gate(|meta| {
+            let s_round = meta.query_fixed(s_round, 0);
+            let b_endo = meta.query_advice(bits.b_endo, 0);
+            let x_p = meta.query_advice(x_p, 0);
+            let x_q = meta.query_advice(x_q, 0);
+
+            s_round * (x_q - x_p.clone() - x_p * b_endo * (C::Base::ZETA - C::Base::one()))
+        });
+        meta.create_gate(|meta| {
+            let s_round = meta.query_fixed(s_round, 0);
+            let b_neg = meta.query_advice(bits.b_neg, 0);
+            let y_p = meta.query_advice(y_p, 0);
+            let y_q = meta.query_advice(y_q, 0);
+
+            s_round * (y_q - y_p.clone() + y_p * b_neg * C::Base::from_u64(2))
+        });
+
+        // R = Acc + Q
+        meta.create_gate(|meta| {
+            let s_round = meta.query_fixed(s_round, 0);
+            let x_acc = meta.query_advice(x_acc, 0);
+            let y_acc = meta.query_advice(y_acc, 0);
+            let x_q = meta.query_advice(x_q, 0);
+            let y_q = meta.query_advice(y_q, 0);
+            let lambda_1 = meta.query_advice(lambda_1, 0);
+
+            s_round * (lambda_1 * (x_acc - x_q) - y_acc + y_q)
+        });
+        meta.create_gate(|meta| {
+            let s_round = meta.query_fixed(s_round, 0);
+            let x_acc = meta.query_advice(x_acc, 0);
+            let x_q = meta.query_advice(x_q, 0);
+            let lambda_1 = meta.query_advice(lambda_1, 0);
+            let x_r = meta.query_advice(x_r, 0);
+
+            s_round * (x_r - lambda_1.clone() * lambda_1 + x_acc + x_q)
+        });
+
+        // Acc_next = R + Acc, where y_r is eliminated using
+        // y_r = lambda_1 * (x_acc - x_r) - y_acc
+        meta.create_gate(|meta| {
+            let s_round = meta.query_fixed(s_round, 0);
+            let x_acc = meta.query_advice(x_acc, 0);
+            let y_acc = meta.query_advice(y_acc, 0);
+            let lambda_1 = meta.query_advice(lambda_1, 0);
+            let x_r = meta.query_advice(x_r, 0);
+            let lambda_2 = meta.query_advice(lambda_2, 0);
+
+            s_round * ((lambda_1 + lambda_2) * (x_acc - x_r) - y_acc * C::Base::from_u64(2))
+        });
+        meta.create_gate(|meta| {
+            let x_acc_next = meta.query_advice(x_acc, 1);
+            let s_round = meta.query_fixed(s_round, 0);
+            let x_acc = meta.query_advice(x_acc, 0);
+            let x_r = meta.query_advice(x_r, 0);
+            let lambda_2 = meta.query_advice(lambda_2, 0);
+
+            s_round * (x_acc_next - lambda_2.clone() * lambda_2 + x_acc + x_r)
+        });
+        meta.create_gate(|meta| {
+            let x_acc_next = meta.query_advice(x_acc, 1);
+            let y_acc_next = meta.query_advice(y_acc, 1);
+            let s_round = meta.query_fixed(s_round, 0);
+            let x_acc = meta.query_advice(x_acc, 0);
+            let y_acc = meta.query_advice(y_acc, 0);
+            let lambda_2 = meta.query_advice(lambda_2, 0);
+
+            s_round * (y_acc_next - lambda_2 * (x_acc - x_acc_next) + y_acc)
+        });
+
+        EndoscalePointConfig {
+            bits,
+            x_p,
+            y_p,
+            x_q,
+            y_q,
+            x_acc,
+            y_acc,
+            lambda_1,
+            x_r,
+            lambda_2,
+            s_init,
+            s_round,
+        }
+    }
+
+    /// Assigns this gadget at `offset`, multiplying `base` by the scalar that
+    /// `challenge` maps to. `base` must not be the identity.
+    pub fn assign<C: CurveAffine>(
+        &self,
+        cs: &mut impl Assignment<C::Base>,
+        offset: usize,
+        challenge: Option<Challenge>,
+        base: Option<C>,
+    ) -> Result<EndoscaledPoint<C::Base>, Error> {
+        let base = match base {
+            Some(base) => Some(
+                Option::<(C::Base, C::Base)>::from(base.get_xy()).ok_or(Error::SynthesisError)?,
+            ),
+            None => None,
+        };
+        self.assign_xy(cs, offset, challenge, base)
+    }
+
+    /// Assigns this gadget for the base point with coordinates `base`,
+    /// whether or not it is on the curve.
+    fn assign_xy<F: FieldExt>(
+        &self,
+        cs: &mut impl Assignment<F>,
+        offset: usize,
+        challenge: Option<Challenge>,
+        base: Option<(F, F)>,
+    ) -> Result<EndoscaledPoint<F>, Error> {
+        let zeta = F::ZETA;
+
+        assign_selectors(cs, offset, self.s_init, self.s_round)?;
+        let challenge_cell = self.bits.assign(cs, offset, challenge)?;
+
+        // Double phi(P) + P to initialize the accumulator.
+        let init = match base {
+            Some((x_p, y_p)) => {
+                let x_t = zeta.square() * x_p;
+                let y_t = -y_p;
+                let lambda = div(x_t.square() * F::from_u64(3), y_t.double())?;
+                let x_acc = lambda.square() - x_t.double();
+                let y_acc = lambda * (x_t - x_acc) - y_t;
+                Some((lambda, x_acc, y_acc))
+            }
+            None => None,
+        };
+
+        let rounds = match (challenge, base, init) {
+            (Some(challenge), Some((x_p, y_p)), Some((_, mut x_acc, mut y_acc))) => {
+                let mut rounds = Vec::with_capacity(NUM_ROUNDS);
+                for round in 0..NUM_ROUNDS {
+                    let (b_neg, b_endo) = round_bits(challenge, round);
+                    let x_q = if b_endo { x_p * zeta } else { x_p };
+                    let y_q = if b_neg { -y_p } else { y_p };
+
+                    let lambda_1 = div(y_acc - y_q, x_acc - x_q)?;
+                    let x_r = lambda_1.square() - x_acc - x_q;
+                    let y_r = lambda_1 * (x_acc - x_r) - y_acc;
+
+                    let lambda_2 = div(y_acc - y_r, x_acc - x_r)?;
+                    let x_s = lambda_2.square() - x_acc - x_r;
+                    let y_s = lambda_2 * (x_acc - x_s) - y_acc;
+
+                    rounds.push(PointRound {
+                        x_acc,
+                        y_acc,
+                        x_q,
+                        y_q,
+                        lambda_1,
+                        x_r,
+                        lambda_2,
+                    });
+                    x_acc = x_s;
+                    y_acc = y_s;
+                }
+                Some((rounds, (x_acc, y_acc)))
+            }
+            _ => None,
+        };
+
+        for row in offset..(offset + Self::ROWS) {
+            cs.assign_advice(self.x_p, row, || witness(&base, |p| p.0))?;
+            cs.assign_advice(self.y_p, row, || witness(&base, |p| p.1))?;
+        }
+        cs.assign_advice(self.x_q, offset, || witness(&base, |p| p.0.square()))?;
+        cs.assign_advice(self.lambda_1, offset, || witness(&init, |i| i.0))?;
+
+        let rounds_ref = &rounds;
+        for round in 0..NUM_ROUNDS {
+            let row = offset + 1 + round;
+            let value = |f: fn(&PointRound<F>) -> F| {
+                move || witness(rounds_ref, |(rounds, _)| f(&rounds[round]))
+            };
+
+            cs.assign_advice(self.x_acc, row, value(|r| r.x_acc))?;
+            cs.assign_advice(self.y_acc, row, value(|r| r.y_acc))?;
+            cs.assign_advice(self.x_q, row, value(|r| r.x_q))?;
+            cs.assign_advice(self.y_q, row, value(|r| r.y_q))?;
+            cs.assign_advice(self.lambda_1, row, value(|r| r.lambda_1))?;
+            cs.assign_advice(self.x_r, row, value(|r| r.x_r))?;
+            cs.assign_advice(self.lambda_2, row, value(|r| r.lambda_2))?;
+        }
+
+        let last = offset + 1 + NUM_ROUNDS;
+        let result = rounds.map(|(_, result)| result);
+        cs.assign_advice(self.x_acc, last, || witness(&result, |r| r.0))?;
+        cs.assign_advice(self.y_acc, last, || witness(&result, |r| r.1))?;
+
+        let cell = |column, row, value| AssignedCell { column, row, value };
+        Ok(EndoscaledPoint {
+            challenge: challenge_cell,
+            base: (
+                cell(self.x_p, offset, base.map(|p| p.0)),
+                cell(self.y_p, offset, base.map(|p| p.1)),
+            ),
+            result: (
+                cell(self.x_acc, last, result.map(|r| r.0)),
+                cell(self.y_acc, last, result.map(|r| r.1)),
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use rand::rngs::OsRng;
+
+    use super::*;
+    use crate::arithmetic::Curve;
+    use crate::dev::{MockProver, VerifyFailure};
+    use crate::multicore::ThreadPool;
+    use crate::pasta::{EpAffine, EqAffine, Fp, Fq};
+    use crate::plonk::{keygen, Circuit, Proof};
+    use crate::poly::commitment::Params;
+    use crate::transcript::{ChallengeScalar, DummyHash};
+
+    const K: u32 = 7;
+
+    // Maps a challenge to a scalar in Fp, and multiplies a Pallas point by
+    // the scalar in Fq the same challenge maps to.
+    #[derive(Default)]
+    struct MyCircuit {
+        challenge: Option<Challenge>,
+        base: Option<EpAffine>,
+        outputs: Cell<Option<(EndoscaledScalar<Fp>, EndoscaledPoint<Fp>)>>,
+    }
+
+    impl Circuit<Fp> for MyCircuit {
+        type Config = (EndoscaleScalarConfig, EndoscalePointConfig);
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            (
+                EndoscaleScalarConfig::configure(meta),
+                EndoscalePointConfig::configure::<EpAffine>(meta),
+            )
+        }
+
+        fn synthesize(
+            &self,
+            cs: &mut impl Assignment<Fp>,
+            (scalar_config, point_config): Self::Config,
+        ) -> Result<(), Error> {
+            let scalar = scalar_config.assign(cs, 0, self.challenge)?;
+            let point = point_config.assign(cs, 0, self.challenge, self.base)?;
+            self.outputs.set(Some((scalar, point)));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_endoscale() {
+        let params: Params<EqAffine> = Params::new(K);
+        let pk = keygen(&params, &MyCircuit::default()).expect("keygen should not fail");
+
+        for challenge in [0, u128::MAX, rand::random()].iter() {
+            let challenge = Challenge(*challenge);
+            let base = (EpAffine::one() * Fq::random(OsRng)).to_affine();
+            let circuit = MyCircuit {
+                challenge: Some(challenge),
+                base: Some(base),
+                outputs: Cell::new(None),
+            };
+
+            let proof = Proof::create::<DummyHash<Fq>, DummyHash<Fp>, _, _, _>(
+                &params,
+                &pk,
+                std::slice::from_ref(&circuit),
+                &[&[]],
+                &ThreadPool::global(),
+                OsRng,
+            )
+            .expect("proof generation should not fail");
+            let guard = proof
+                .verify::<DummyHash<Fq>, DummyHash<Fp>, _>(
+                    &params,
+                    pk.get_vk(),
+                    params.empty_msm(),
+                    &[&[]],
+                    &[],
+                )
+                .unwrap();
+            assert!(guard.use_challenges().eval());
+
+            let (scalar, point) = circuit.outputs.get().unwrap();
+            assert_eq!(scalar.challenge.value, Some(Fp::from_u128(challenge.0)));
+            assert_eq!(
+                scalar.scalar.value,
+                Some(*ChallengeScalar::<Fp, ()>::from(challenge))
+            );
+
+            let expected = (base * *ChallengeScalar::<Fq, ()>::from(challenge)).to_affine();
+            let (x, y) = expected.get_xy().unwrap();
+            assert_eq!(point.challenge.value, Some(Fp::from_u128(challenge.0)));
+            assert_eq!(point.result.0.value, Some(x));
+            assert_eq!(point.result.1.value, Some(y));
+        }
+    }
+
+    // Adds one to the value assigned to `column` at `row`, and passes every
+    // other assignment through.
+    struct Tamper<'a, A> {
+        cs: &'a mut A,
+        column: Column<Advice>,
+        row: usize,
+    }
+
+    impl<'a, F: Field, A: Assignment<F>> Assignment<F> for Tamper<'a, A> {
+        fn assign_advice(
+            &mut self,
+            column: Column<Advice>,
+            row: usize,
+            to: impl FnOnce() -> Result<F, Error>,
+        ) -> Result<(), Error> {
+            let tamper = column == self.column && row == self.row;
+            self.cs.assign_advice(column, row, || {
+                to().map(|value| if tamper { value + F::one() } else { value })
+            })
+        }
+
+        fn assign_fixed(
+            &mut self,
+            column: Column<Fixed>,
+            row: usize,
+            to: impl FnOnce() -> Result<F, Error>,
+        ) -> Result<(), Error> {
+            self.cs.assign_fixed(column, row, to)
+        }
+
+        fn copy(
+            &mut self,
+            permutation: usize,
+            left_column: usize,
+            left_row: usize,
+            right_column: usize,
+            right_row: usize,
+        ) -> Result<(), Error> {
+            self.cs
+                .copy(permutation, left_column, left_row, right_column, right_row)
+        }
+    }
+
+    // Multiplies a point by the scalar a challenge maps to, with the base
+    // point given by its coordinates and one advice cell tampered with.
+    struct PointCircuit {
+        challenge: Challenge,
+        base: (Fp, Fp),
+        tamper: Option<(Column<Advice>, usize)>,
+    }
+
+    impl Circuit<Fp> for PointCircuit {
+        type Config = EndoscalePointConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            EndoscalePointConfig::configure::<EpAffine>(meta)
+        }
+
+        fn synthesize(
+            &self,
+            cs: &mut impl Assignment<Fp>,
+            config: Self::Config,
+        ) -> Result<(), Error> {
+            let (challenge, base) = (Some(self.challenge), Some(self.base));
+            match self.tamper {
+                Some((column, row)) => {
+                    let mut cs = Tamper { cs, column, row };
+                    config.assign_xy(&mut cs, 0, challenge, base)?;
+                }
+                None => {
+                    config.assign_xy(cs, 0, challenge, base)?;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_endoscale_point_failures() {
+        let config = PointCircuit::configure(&mut ConstraintSystem::default());
+        let challenge = Challenge(rand::random());
+        let base = (EpAffine::one() * Fq::random(OsRng))
+            .to_affine()
+            .get_xy()
+            .unwrap();
+        let verify = |base, tamper| {
+            let circuit = PointCircuit {
+                challenge,
+                base,
+                tamper,
+            };
+            MockProver::run(K, &circuit, vec![]).unwrap().verify()
+        };
+
+        assert_eq!(verify(base, None), Ok(()));
+
+        // A point off the curve satisfies every constraint of the additions,
+        // which do not depend on the curve constant, so only the check that
+        // the base point is on the curve fails.
+        let off_curve = (base.0, base.1 + Fp::one());
+        assert!(matches!(
+            verify(off_curve, None),
+            Err(VerifyFailure::Gate { row: 0, .. })
+        ));
+
+        // A flipped bit of the challenge, or a wrong accumulator in any round.
+        assert!(verify(base, Some((config.bits.b_endo, 5))).is_err());
+        assert!(verify(base, Some((config.bits.b_neg, 5))).is_err());
+        assert!(verify(base, Some((config.x_acc, 10))).is_err());
+        assert!(verify(base, Some((config.y_acc, 10))).is_err());
+        assert!(verify(base, Some((config.x_acc, EndoscalePointConfig::ROWS - 1))).is_err());
+    }
+}
@@ -0,0 +1,216 @@
+//! An in-circuit version of [`DummyHash`], which lets a circuit recompute the
+//! challenges of a transcript.
+//!
+//! The sponge state is held in the `state` and `power` columns, one round per
+//! row. Absorbing a value takes [`ROUNDS`](DummyHashConfig::ROUNDS) rows that
+//! hold the value in the `value` column, and leaves the new state on the row
+//! after them, where the next operation starts. Squeezing absorbs the current
+//! state, which is constrained to equal the value on its first row.
+//!
+//! [`DummyHash`]: crate::transcript::DummyHash
+
+use super::AssignedCell;
+use crate::arithmetic::FieldExt;
+use crate::plonk::{Advice, Assignment, Column, ConstraintSystem, Error, Fixed};
+
+/// The number of rounds of [`DummyHash::absorb`](crate::transcript::DummyHash).
+const ROUNDS: usize = 10;
+
+/// The state of the sponge laid out by [`DummyHashConfig`], and the row of
+/// the next operation.
+#[derive(Clone, Copy, Debug)]
+pub struct HashState<F> {
+    row: usize,
+    state: Option<F>,
+    power: Option<F>,
+}
+
+/// A gadget that lays out the operations of a [`DummyHash`] sponge.
+///
+/// [`DummyHash`]: crate::transcript::DummyHash
+#[derive(Clone, Copy, Debug)]
+pub struct DummyHashConfig {
+    value: Column<Advice>,
+    state: Column<Advice>,
+    power: Column<Advice>,
+    s_init: Column<Fixed>,
+    key: Column<Fixed>,
+    s_round: Column<Fixed>,
+    s_hold: Column<Fixed>,
+    s_squeeze: Column<Fixed>,
+}
+
+impl DummyHashConfig {
+    /// The number of rows used to absorb a value.
+    pub const ROUNDS: usize = ROUNDS;
+
+    /// Configures the columns and gates of this gadget.
+    pub fn configure<F: FieldExt>(meta: &mut ConstraintSystem<F>) -> Self {
+        let value = meta.advice_column();
+        let state = meta.advice_column();
+        let power = meta.advice_column();
+        let s_init = meta.fixed_column();
+        let key = meta.fixed_column();
+        let s_round = meta.fixed_column();
+        let s_hold = meta.fixed_column();
+        let s_squeeze = meta.fixed_column();
+
+        // state = zeta, power = zeta + 1 + key
+        meta.create_gate(|meta| {
+            let s_init = meta.query_fixed(s_init, 0);
+            let state = meta.query_advice(state, 0);
+
+            s_init.clone() * state - s_init * F::ZETA
+        });
+        meta.create_gate(|meta| {
+            let s_init = meta.query_fixed(s_init, 0);
+            let key = meta.query_fixed(key, 0);
+            let power = meta.query_advice(power, 0);
+
+            s_init.clone() * power - s_init * (F::ZETA + F::one()) - key
+        });
+
+        // power_next = power + power^2
+        meta.create_gate(|meta| {
+            let power_next = meta.query_advice(power, 1);
+            let s_round = meta.query_fixed(s_round, 0);
+            let power = meta.query_advice(power, 0);
+
+            s_round * (power_next - power.clone() - power.clone() * power)
+        });
+
+        // state_next = (state + value) * power + power_next
+        meta.create_gate(|meta| {
+            let state_next = meta.query_advice(state, 1);
+            let power_next = meta.query_advice(power, 1);
+            let s_round = meta.query_fixed(s_round, 0);
+            let state = meta.query_advice(state, 0);
+            let value = meta.query_advice(value, 0);
+            let power = meta.query_advice(power, 0);
+
+            s_round * (state_next - (state + value) * power - power_next)
+        });
+
+        // The value is the same on every round of an absorption.
+        meta.create_gate(|meta| {
+            let value_next = meta.query_advice(value, 1);
+            let s_hold = meta.query_fixed(s_hold, 0);
+            let value = meta.query_advice(value, 0);
+
+            s_hold * (value_next - value)
+        });
+
+        // A squeeze absorbs the current state.
+        meta.create_gate(|meta| {
+            let s_squeeze = meta.query_fixed(s_squeeze, 0);
+            let value = meta.query_advice(value, 0);
+            let state = meta.query_advice(state, 0);
+
+            s_squeeze * (value - state)
+        });
+
+        DummyHashConfig {
+            value,
+            state,
+            power,
+            s_init,
+            key,
+            s_round,
+            s_hold,
+            s_squeeze,
+        }
+    }
+
+    /// Returns the advice columns holding the absorbed values and the squeezed
+    /// values respectively, which must be in permutation arguments for them
+    /// to be connected to other cells.
+    pub fn columns(&self) -> (Column<Advice>, Column<Advice>) {
+        (self.value, self.state)
+    }
+
+    /// Initializes the sponge at `offset` with `key`, as
+    /// [`Hasher::init`](crate::transcript::Hasher::init) does.
+    pub fn init<F: FieldExt>(
+        &self,
+        cs: &mut impl Assignment<F>,
+        offset: usize,
+        key: F,
+    ) -> Result<HashState<F>, Error> {
+        let state = F::ZETA;
+        let power = F::ZETA + F::one() + key;
+
+        cs.assign_fixed(self.s_init, offset, || Ok(F::one()))?;
+        cs.assign_fixed(self.key, offset, || Ok(key))?;
+        cs.assign_advice(self.state, offset, || Ok(state))?;
+        cs.assign_advice(self.power, offset, || Ok(power))?;
+
+        Ok(HashState {
+            row: offset,
+            state: Some(state),
+            power: Some(power),
+        })
+    }
+
+    /// Absorbs `value` into the sponge, returning the cell that holds it on
+    /// the first row of the absorption.
+    pub fn absorb<F: FieldExt>(
+        &self,
+        cs: &mut impl Assignment<F>,
+        sponge: &mut HashState<F>,
+        value: Option<F>,
+    ) -> Result<AssignedCell<F>, Error> {
+        let offset = sponge.row;
+        let (mut state, mut power) = (sponge.state, sponge.power);
+
+        for round in 0..ROUNDS {
+            let row = offset + round;
+            cs.assign_fixed(self.s_round, row, || Ok(F::one()))?;
+            if round + 1 < ROUNDS {
+                cs.assign_fixed(self.s_hold, row, || Ok(F::one()))?;
+            }
+            cs.assign_advice(self.value, row, || value.ok_or(Error::SynthesisError))?;
+
+            let next = match (state, power, value) {
+                (Some(state), Some(power), Some(value)) => {
+                    let power_next = power + power.square();
+                    Some(((state + value) * power + power_next, power_next))
+                }
+                _ => None,
+            };
+            state = next.map(|next| next.0);
+            power = next.map(|next| next.1);
+            cs.assign_advice(self.state, row + 1, || state.ok_or(Error::SynthesisError))?;
+            cs.assign_advice(self.power, row + 1, || power.ok_or(Error::SynthesisError))?;
+        }
+
+        *sponge = HashState {
+            row: offset + ROUNDS,
+            state,
+            power,
+        };
+
+        Ok(AssignedCell {
+            column: self.value,
+            row: offset,
+            value,
+        })
+    }
+
+    /// Squeezes a value out of the sponge, returning the cell that holds it.
+    pub fn squeeze<F: FieldExt>(
+        &self,
+        cs: &mut impl Assignment<F>,
+        sponge: &mut HashState<F>,
+    ) -> Result<AssignedCell<F>, Error> {
+        let squeezed = AssignedCell {
+            column: self.state,
+            row: sponge.row,
+            value: sponge.state,
+        };
+
+        cs.assign_fixed(self.s_squeeze, sponge.row, || Ok(F::one()))?;
+        self.absorb(cs, sponge, squeezed.value)?;
+
+        Ok(squeezed)
+    }
+}
@@ -0,0 +1,193 @@
+//! An in-circuit version of [`Challenge::get`], which truncates a value
+//! squeezed from a transcript to its lower 128 bits.
+//!
+//! The gadget uses [`ROWS`](SplitConfig::ROWS) rows starting at the offset it
+//! is assigned at. Each of the first 32 rows holds a byte of the squeezed
+//! value as four 2-bit crumbs, most significant first, and a running sum
+//! reconstructs the value from them. A second running sum over the last 16 of
+//! those rows reconstructs the lower 128 bits. Both sums end on the last row.
+//!
+//! The two most significant bits must be zero, so that the crumbs are the
+//! canonical representation of the value in a field whose modulus exceeds
+//! $2^{254}$. Squeezing a larger value has negligible probability, and in that
+//! case assignment fails.
+//!
+//! [`Challenge::get`]: crate::transcript::Challenge::get
+
+use super::AssignedCell;
+use crate::arithmetic::FieldExt;
+use crate::plonk::{Advice, Assignment, Column, ConstraintSystem, Error, Fixed};
+
+/// The number of rows that each hold a byte of the value.
+const NUM_BYTES: usize = 32;
+
+/// The number of crumbs in a byte.
+const CRUMBS: usize = 4;
+
+/// The cells assigned by [`SplitConfig::assign`].
+#[derive(Clone, Copy, Debug)]
+pub struct SplitSqueeze<F> {
+    /// The squeezed value.
+    pub squeeze: AssignedCell<F>,
+    /// The challenge, which is the lower 128 bits of the squeezed value.
+    pub challenge: AssignedCell<F>,
+}
+
+/// A gadget that splits the lower 128 bits, as a
+/// [`Challenge`](crate::transcript::Challenge), off a value
+/// squeezed from a transcript.
+#[derive(Clone, Copy, Debug)]
+pub struct SplitConfig {
+    crumbs: [Column<Advice>; CRUMBS],
+    sum: Column<Advice>,
+    sum_lo: Column<Advice>,
+    s_init: Column<Fixed>,
+    s_byte: Column<Fixed>,
+    s_init_lo: Column<Fixed>,
+    s_byte_lo: Column<Fixed>,
+}
+
+impl SplitConfig {
+    /// The number of rows used by this gadget.
+    pub const ROWS: usize = NUM_BYTES + 1;
+
+    /// Configures the columns and gates of this gadget.
+    pub fn configure<F: FieldExt>(meta: &mut ConstraintSystem<F>) -> Self {
+        assert_eq!(F::NUM_BITS, 255, "the modulus must exceed 2^254");
+
+        let crumbs = [
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+        ];
+        let sum = meta.advice_column();
+        let sum_lo = meta.advice_column();
+        let s_init = meta.fixed_column();
+        let s_byte = meta.fixed_column();
+        let s_init_lo = meta.fixed_column();
+        let s_byte_lo = meta.fixed_column();
+
+        for crumb in crumbs.iter() {
+            meta.create_gate(|meta| {
+                let s_byte = meta.query_fixed(s_byte, 0);
+                let crumb = meta.query_advice(*crumb, 0);
+
+                let range = (1..4).fold(crumb.clone(), |range, i| {
+                    range * (crumb.clone() - s_byte.clone() * F::from_u64(i))
+                });
+                s_byte * range
+            });
+        }
+
+        // The top crumb of the most significant byte is zero.
+        meta.create_gate(|meta| {
+            let s_init = meta.query_fixed(s_init, 0);
+            let crumb = meta.query_advice(crumbs[CRUMBS - 1], 0);
+
+            s_init * crumb
+        });
+
+        for &(sum, s_init, s_byte) in [(sum, s_init, s_byte), (sum_lo, s_init_lo, s_byte_lo)].iter()
+        {
+            meta.create_gate(|meta| {
+                let s_init = meta.query_fixed(s_init, 0);
+                let sum = meta.query_advice(sum, 0);
+
+                s_init * sum
+            });
+
+            // sum_next = 256 * sum + byte
+            meta.create_gate(|meta| {
+                let sum_next = meta.query_advice(sum, 1);
+                let s_byte = meta.query_fixed(s_byte, 0);
+                let sum = meta.query_advice(sum, 0);
+                let sum = crumbs.iter().rev().fold(sum, |sum, crumb| {
+                    sum * F::from_u64(4) + meta.query_advice(*crumb, 0)
+                });
+
+                s_byte * (sum_next - sum)
+            });
+        }
+
+        SplitConfig {
+            crumbs,
+            sum,
+            sum_lo,
+            s_init,
+            s_byte,
+            s_init_lo,
+            s_byte_lo,
+        }
+    }
+
+    /// Returns the advice columns holding the squeezed value and the
+    /// challenge respectively, which must be in permutation arguments for
+    /// them to be connected to other cells.
+    pub fn columns(&self) -> (Column<Advice>, Column<Advice>) {
+        (self.sum, self.sum_lo)
+    }
+
+    /// Assigns this gadget at `offset`, splitting `squeeze`.
+    pub fn assign<F: FieldExt>(
+        &self,
+        cs: &mut impl Assignment<F>,
+        offset: usize,
+        squeeze: Option<F>,
+    ) -> Result<SplitSqueeze<F>, Error> {
+        let bytes = squeeze.map(|squeeze| squeeze.to_bytes());
+        if bytes.is_some_and(|bytes| bytes[NUM_BYTES - 1] >> 6 != 0) {
+            return Err(Error::SynthesisError);
+        }
+
+        let mut sum = bytes.map(|_| F::zero());
+        let mut sum_lo = sum;
+        cs.assign_fixed(self.s_init, offset, || Ok(F::one()))?;
+        cs.assign_fixed(self.s_init_lo, offset + NUM_BYTES / 2, || Ok(F::one()))?;
+        cs.assign_advice(self.sum, offset, || sum.ok_or(Error::SynthesisError))?;
+
+        for i in 0..NUM_BYTES {
+            let row = offset + i;
+            let byte = bytes.map(|bytes| bytes[NUM_BYTES - 1 - i]);
+            cs.assign_fixed(self.s_byte, row, || Ok(F::one()))?;
+            for (j, crumb) in self.crumbs.iter().enumerate() {
+                cs.assign_advice(*crumb, row, || {
+                    byte.map(|byte| F::from_u64(((byte >> (2 * j)) & 3) as u64))
+                        .ok_or(Error::SynthesisError)
+                })?;
+            }
+
+            let byte = byte.map(|byte| F::from_u64(byte as u64));
+            sum = sum.and_then(|sum| byte.map(|byte| sum * F::from_u64(256) + byte));
+            cs.assign_advice(self.sum, row + 1, || sum.ok_or(Error::SynthesisError))?;
+
+            if i >= NUM_BYTES / 2 {
+                if i == NUM_BYTES / 2 {
+                    cs.assign_advice(self.sum_lo, row, || sum_lo.ok_or(Error::SynthesisError))?;
+                }
+                cs.assign_fixed(self.s_byte_lo, row, || Ok(F::one()))?;
+                sum_lo = sum_lo.and_then(|sum| byte.map(|byte| sum * F::from_u64(256) + byte));
+                cs.assign_advice(self.sum_lo, row + 1, || sum_lo.ok_or(Error::SynthesisError))?;
+            }
+        }
+
+        debug_assert!(squeeze.zip(sum).is_none_or(|(squeeze, sum)| squeeze == sum));
+        debug_assert!(squeeze
+            .zip(sum_lo)
+            .is_none_or(|(squeeze, sum_lo)| sum_lo == F::from_u128(squeeze.get_lower_128())));
+
+        let last = offset + NUM_BYTES;
+        Ok(SplitSqueeze {
+            squeeze: AssignedCell {
+                column: self.sum,
+                row: last,
+                value: sum,
+            },
+            challenge: AssignedCell {
+                column: self.sum_lo,
+                row: last,
+                value: sum_lo,
+            },
+        })
+    }
+}
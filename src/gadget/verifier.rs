@@ -0,0 +1,1767 @@
+//! An in-circuit verifier for proofs over the inner product argument in
+//! [`commitment`](crate::poly::commitment), whose transcripts use
+//! [`DummyHash`] in both fields.
+//!
+//! Verification is split over two circuits, one over each field of the curve
+//! of the proof:
+//!
+//! - [`TranscriptCircuit`], over the base field, absorbs the commitments of
+//!   the proof and recomputes the challenges of the transcript.
+//! - [`VerifierCircuit`], over the scalar field, maps the challenges to
+//!   scalars, absorbs the evaluations of the proof, evaluates the gates of the
+//!   [`VerifyingKey`] at $x$ and checks the combination of the multi-point
+//!   opening.
+//!
+//! Each circuit has a public column, whose values are those of an aux column
+//! given by [`TranscriptCircuit::aux_column`] and
+//! [`VerifierCircuit::aux_column`]. The challenges, and the values squeezed
+//! from the sponge over the scalar field, are public in both circuits. The
+//! final check of the opening is a multi-scalar multiplication over the
+//! commitments of the proof, which neither circuit can compute, so the
+//! [`VerifierCircuit`] outputs its scalars instead and [`deferred_msm`]
+//! collects them for the verifier to decide.
+//!
+//! Two values are witnessed up to sign. The y-coordinate of $U$ may be either
+//! square root, which lets the prover pick between two points derived from
+//! the transcript. The challenges of the rounds of the opening are the
+//! squares $u_j^2$, and negating a root $u_j$ negates both $b$ and the
+//! product of the inverses of the roots, which is the same as negating $z_1$.
+//! Neither choice lets the prover open to a different value.
+//!
+//! The public values of [`TranscriptCircuit`] are, in the order of the
+//! transcript, the coordinates of each commitment, each value squeezed from
+//! the scalar sponge, each challenge and the coordinates of the point $U$ of
+//! the opening. Those of [`VerifierCircuit`] are each challenge and each value
+//! squeezed from the scalar sponge, in the same order, followed by the scalars
+//! of the multi-scalar multiplication.
+//!
+//! [`DummyHash`]: crate::transcript::DummyHash
+
+use std::cell::RefCell;
+use std::iter;
+use std::ops::Range;
+
+use ff::Field;
+
+use super::{
+    ArithmeticConfig, ArithmeticRow, AssignedCell, DummyHashConfig, EndoscaleScalarConfig,
+    HashState, Permutation, SplitConfig,
+};
+use crate::arithmetic::{CurveAffine, FieldExt};
+use crate::plonk::{
+    Advice, Any, Assignment, Circuit, Column, ConstraintSystem, Error, Fixed, Proof, VerifyingKey,
+};
+use crate::poly::{
+    self,
+    commitment::{compute_s, Params, MSM},
+    multiopen::{construct_intermediate_sets, CommitmentData, Query},
+    Rotation,
+};
+use crate::transcript::{Challenge, DummyHash, Hasher, Transcript};
+
+// The indices of the challenges, in the order they are squeezed. The
+// challenges of the rounds of the opening follow X4, and the challenge c
+// follows them.
+const THETA: usize = 0;
+const BETA: usize = 1;
+const GAMMA: usize = 2;
+const Y: usize = 3;
+const X: usize = 4;
+const X1: usize = 5;
+const X2: usize = 6;
+const X3: usize = 7;
+const X4: usize = 8;
+const ROUNDS: usize = 9;
+
+/// An operation on the transcript, in the order of verification.
+#[derive(Clone, Debug)]
+enum Op {
+    /// Absorbs the point with this index.
+    Point(usize),
+    /// Absorbs the evaluations with these indices into the scalar sponge,
+    /// which the next operation squeezes into the base sponge.
+    Scalars(Range<usize>),
+    /// Squeezes a challenge.
+    Challenge,
+    /// Squeezes the x-coordinate of the point U of the opening.
+    U,
+}
+
+/// A commitment of the proof, or of its verifying key.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Commitment {
+    /// The point of the transcript with this index.
+    Transcript(usize),
+    /// The commitment to the fixed column with this index.
+    Fixed(usize),
+    /// The commitment to the permutation of a column of a permutation
+    /// argument, by their indices.
+    Permutation(usize, usize),
+}
+
+/// A query of a commitment, whose point is $\omega^r$ for a query at $\omega^r
+/// x$ and whose evaluation is the index of the evaluation in the proof.
+///
+/// Every query is at a multiple of $x$, so the points relate to each other as
+/// those of the proof do, and [`construct_intermediate_sets`] returns the
+/// same sets for them.
+#[derive(Clone, Copy, Debug)]
+struct SymbolicQuery<F> {
+    commitment: Commitment,
+    point: F,
+    eval: usize,
+}
+
+impl<F: FieldExt> Query<F> for SymbolicQuery<F> {
+    type Commitment = Commitment;
+    type Eval = usize;
+
+    fn get_point(&self) -> F {
+        self.point
+    }
+    fn get_eval(&self) -> Self::Eval {
+        self.eval
+    }
+    fn get_commitment(&self) -> Self::Commitment {
+        self.commitment
+    }
+}
+
+/// The indices of the evaluations of a permutation argument.
+#[derive(Clone, Debug)]
+struct PermutationEvals {
+    product: usize,
+    product_inv: usize,
+    columns: Vec<usize>,
+}
+
+/// The indices of the evaluations of a lookup argument.
+#[derive(Clone, Copy, Debug)]
+struct LookupEvals {
+    product: usize,
+    product_inv: usize,
+    permuted_input: usize,
+    permuted_input_inv: usize,
+    permuted_table: usize,
+}
+
+/// The layout of the transcript of a proof for a verifying key, which
+/// determines the layout of both circuits.
+struct Layout<F> {
+    ops: Vec<Op>,
+    num_points: usize,
+    num_evals: usize,
+    num_challenges: usize,
+    k: usize,
+    f: usize,
+    rounds: Vec<(usize, usize)>,
+    delta: usize,
+    advice_evals: Vec<Vec<usize>>,
+    aux_evals: Vec<Vec<usize>>,
+    fixed_evals: Vec<usize>,
+    h_evals: Vec<usize>,
+    permutations: Vec<Vec<PermutationEvals>>,
+    lookups: Vec<Vec<LookupEvals>>,
+    q_evals: Vec<usize>,
+    commitments: Vec<CommitmentData<usize, Commitment>>,
+    point_sets: Vec<Vec<F>>,
+}
+
+impl<F: FieldExt> Layout<F> {
+    fn new<C: CurveAffine<Scalar = F>>(vk: &VerifyingKey<C>, num_instances: usize) -> Self {
+        let cs = &vk.cs;
+        let k = vk.domain.get_n().trailing_zeros() as usize;
+        let mut layout = Layout {
+            ops: vec![],
+            num_points: 0,
+            num_evals: 0,
+            num_challenges: 0,
+            k,
+            f: 0,
+            rounds: vec![],
+            delta: 0,
+            advice_evals: vec![],
+            aux_evals: vec![],
+            fixed_evals: vec![],
+            h_evals: vec![],
+            permutations: vec![],
+            lookups: vec![],
+            q_evals: vec![],
+            commitments: vec![],
+            point_sets: vec![],
+        };
+        let instances = || 0..num_instances;
+
+        let aux: Vec<Vec<_>> = instances()
+            .map(|_| (0..cs.num_aux_columns).map(|_| layout.point()).collect())
+            .collect();
+        let advice: Vec<Vec<_>> = instances()
+            .map(|_| (0..cs.num_advice_columns).map(|_| layout.point()).collect())
+            .collect();
+        layout.challenge();
+        let lookup_permuted: Vec<Vec<_>> = instances()
+            .map(|_| {
+                cs.lookups
+                    .iter()
+                    .map(|_| (layout.point(), layout.point()))
+                    .collect()
+            })
+            .collect();
+        layout.challenge();
+        layout.challenge();
+        let permutation_products: Vec<Vec<_>> = instances()
+            .map(|_| cs.permutations.iter().map(|_| layout.point()).collect())
+            .collect();
+        let lookup_products: Vec<Vec<_>> = instances()
+            .map(|_| cs.lookups.iter().map(|_| layout.point()).collect())
+            .collect();
+        layout.challenge();
+        let h: Vec<_> = (0..vk.domain.get_quotient_poly_degree())
+            .map(|_| layout.point())
+            .collect();
+        layout.challenge();
+
+        layout.advice_evals = instances()
+            .map(|_| cs.advice_queries.iter().map(|_| layout.eval()).collect())
+            .collect();
+        layout.aux_evals = instances()
+            .map(|_| cs.aux_queries.iter().map(|_| layout.eval()).collect())
+            .collect();
+        layout.fixed_evals = cs.fixed_queries.iter().map(|_| layout.eval()).collect();
+        layout.h_evals = h.iter().map(|_| layout.eval()).collect();
+        layout.permutations = instances()
+            .map(|_| {
+                cs.permutations
+                    .iter()
+                    .map(|argument| PermutationEvals {
+                        product: layout.eval(),
+                        product_inv: layout.eval(),
+                        columns: argument
+                            .get_columns()
+                            .iter()
+                            .map(|_| layout.eval())
+                            .collect(),
+                    })
+                    .collect()
+            })
+            .collect();
+        layout.lookups = instances()
+            .map(|_| {
+                cs.lookups
+                    .iter()
+                    .map(|_| LookupEvals {
+                        product: layout.eval(),
+                        product_inv: layout.eval(),
+                        permuted_input: layout.eval(),
+                        permuted_input_inv: layout.eval(),
+                        permuted_table: layout.eval(),
+                    })
+                    .collect()
+            })
+            .collect();
+        layout.ops.push(Op::Scalars(0..layout.num_evals));
+
+        // The queries, in the order the verifier makes them.
+        let point = |at| vk.domain.rotate_omega(F::one(), at);
+        let query = |commitment, point, eval| SymbolicQuery {
+            commitment,
+            point,
+            eval,
+        };
+        let current = F::one();
+        let previous = point(Rotation(-1));
+        let mut queries = vec![];
+        for (advice, evals) in advice.iter().zip(layout.advice_evals.iter()) {
+            queries.extend(cs.advice_queries.iter().zip(evals.iter()).map(
+                |(&(column, at), &eval)| {
+                    query(
+                        Commitment::Transcript(advice[column.index()]),
+                        point(at),
+                        eval,
+                    )
+                },
+            ));
+        }
+        for (aux, evals) in aux.iter().zip(layout.aux_evals.iter()) {
+            queries.extend(cs.aux_queries.iter().zip(evals.iter()).map(
+                |(&(column, at), &eval)| {
+                    query(Commitment::Transcript(aux[column.index()]), point(at), eval)
+                },
+            ));
+        }
+        queries.extend(cs.fixed_queries.iter().zip(layout.fixed_evals.iter()).map(
+            |(&(column, at), &eval)| query(Commitment::Fixed(column.index()), point(at), eval),
+        ));
+        queries.extend(
+            h.iter()
+                .zip(layout.h_evals.iter())
+                .map(|(&h, &eval)| query(Commitment::Transcript(h), current, eval)),
+        );
+        for (products, permutations) in permutation_products.iter().zip(layout.permutations.iter())
+        {
+            for (argument, (&product, evals)) in
+                products.iter().zip(permutations.iter()).enumerate()
+            {
+                let product = Commitment::Transcript(product);
+                queries.push(query(product, current, evals.product));
+                queries.push(query(product, previous, evals.product_inv));
+                queries.extend(evals.columns.iter().enumerate().map(|(column, &eval)| {
+                    query(Commitment::Permutation(argument, column), current, eval)
+                }));
+            }
+        }
+        for ((permuted, products), lookups) in lookup_permuted
+            .iter()
+            .zip(lookup_products.iter())
+            .zip(layout.lookups.iter())
+        {
+            for ((&(input, table), &product), evals) in
+                permuted.iter().zip(products.iter()).zip(lookups.iter())
+            {
+                let (input, table) = (Commitment::Transcript(input), Commitment::Transcript(table));
+                let product = Commitment::Transcript(product);
+                queries.push(query(product, current, evals.product));
+                queries.push(query(input, current, evals.permuted_input));
+                queries.push(query(table, current, evals.permuted_table));
+                queries.push(query(input, previous, evals.permuted_input_inv));
+                queries.push(query(product, previous, evals.product_inv));
+            }
+        }
+        let (commitments, point_sets) = construct_intermediate_sets(queries);
+        layout.commitments = commitments;
+        layout.point_sets = point_sets;
+
+        layout.challenge();
+        layout.challenge();
+        layout.f = layout.point();
+        layout.challenge();
+        let start = layout.num_evals;
+        layout.q_evals = (0..layout.point_sets.len())
+            .map(|_| layout.eval())
+            .collect();
+        layout.ops.push(Op::Scalars(start..layout.num_evals));
+        layout.challenge();
+
+        layout.ops.push(Op::U);
+        for _ in 0..k {
+            let round = (layout.point(), layout.point());
+            layout.rounds.push(round);
+            layout.challenge();
+        }
+        layout.delta = layout.point();
+        layout.challenge();
+
+        layout
+    }
+
+    fn point(&mut self) -> usize {
+        self.ops.push(Op::Point(self.num_points));
+        self.num_points += 1;
+        self.num_points - 1
+    }
+
+    fn eval(&mut self) -> usize {
+        self.num_evals += 1;
+        self.num_evals - 1
+    }
+
+    fn challenge(&mut self) {
+        self.ops.push(Op::Challenge);
+        self.num_challenges += 1;
+    }
+}
+
+/// The values of a proof, and of its transcript, that the circuits of this
+/// module are synthesized with.
+#[derive(Clone, Debug)]
+pub struct ProofWitness<C: CurveAffine> {
+    points: Vec<C>,
+    evals: Vec<C::Scalar>,
+    z1: C::Scalar,
+    z2: C::Scalar,
+    challenges: Vec<Challenge>,
+    scalar_squeezes: Vec<C::Scalar>,
+    u: C,
+}
+
+impl<C: CurveAffine> ProofWitness<C> {
+    /// Reads the witness from `proof`, for the circuit instances whose aux
+    /// columns commit to `aux_commitments`, and replays its transcript to
+    /// obtain the challenges.
+    ///
+    /// This does not verify the proof.
+    pub fn new(
+        vk: &VerifyingKey<C>,
+        proof: &Proof<C>,
+        aux_commitments: &[&[C]],
+    ) -> Result<Self, Error> {
+        let circuit = &proof.circuit;
+        let multiopening = &proof.multiopening;
+        let opening = &multiopening.opening;
+        circuit.check_lengths(vk, aux_commitments)?;
+
+        let layout = Layout::new(vk, aux_commitments.len());
+        if multiopening.q_evals.len() != layout.q_evals.len() || opening.rounds.len() != layout.k {
+            return Err(Error::Opening(poly::Error::InvalidProofLength));
+        }
+
+        let points: Vec<C> = aux_commitments
+            .iter()
+            .flat_map(|commitments| commitments.iter())
+            .chain(circuit.advice_commitments.iter().flatten())
+            .chain(circuit.lookups.iter().flatten().flat_map(|lookup| {
+                iter::once(&lookup.permuted_input_commitment)
+                    .chain(Some(&lookup.permuted_table_commitment))
+            }))
+            .chain(
+                circuit
+                    .permutations
+                    .iter()
+                    .flatten()
+                    .map(|permutation| &permutation.permutation_product_commitment),
+            )
+            .chain(
+                circuit
+                    .lookups
+                    .iter()
+                    .flatten()
+                    .map(|lookup| &lookup.product_commitment),
+            )
+            .chain(circuit.vanishing.h_commitments.iter())
+            .chain(Some(&multiopening.f_commitment))
+            .chain(
+                opening
+                    .rounds
+                    .iter()
+                    .flat_map(|(l, r)| iter::once(l).chain(Some(r))),
+            )
+            .chain(Some(&opening.delta))
+            .cloned()
+            .collect();
+        let evals: Vec<C::Scalar> = circuit
+            .evals()
+            .chain(multiopening.q_evals.iter())
+            .cloned()
+            .collect();
+        debug_assert_eq!(points.len(), layout.num_points);
+        debug_assert_eq!(evals.len(), layout.num_evals);
+
+        // The transcript keeps its scalar sponge to itself, so the values
+        // squeezed from it are recomputed alongside.
+        let mut transcript = Transcript::<C, DummyHash<C::Base>, DummyHash<C::Scalar>>::new();
+        let mut scalar_hasher = DummyHash::init(C::Scalar::one());
+        let mut challenges = Vec::with_capacity(layout.num_challenges);
+        let mut scalar_squeezes = vec![];
+        let mut u = None;
+        for op in layout.ops.iter() {
+            match op {
+                Op::Point(index) => transcript
+                    .absorb_point(&points[*index])
+                    .map_err(|_| Error::InvalidPoint)?,
+                Op::Scalars(range) => {
+                    for eval in evals[range.clone()].iter() {
+                        transcript.absorb_scalar(*eval);
+                        scalar_hasher.absorb(*eval);
+                    }
+                    scalar_squeezes.push(scalar_hasher.squeeze());
+                }
+                Op::Challenge => challenges.push(Challenge::get(&mut transcript)),
+                Op::U => {
+                    let u_x = transcript.squeeze();
+                    let u_y = (u_x.square() * u_x + C::b())
+                        .deterministic_sqrt()
+                        .ok_or(Error::Opening(poly::Error::InvalidChallenge))?;
+                    u = Some(C::from_xy(u_x, u_y).unwrap());
+                }
+            }
+        }
+
+        Ok(ProofWitness {
+            points,
+            evals,
+            z1: opening.z1,
+            z2: opening.z2,
+            challenges,
+            scalar_squeezes,
+            u: u.unwrap(),
+        })
+    }
+}
+
+/// A public column, whose values are constrained to equal those of an aux
+/// column.
+#[derive(Clone, Copy, Debug)]
+struct PublicConfig {
+    public: Column<Advice>,
+    s_public: Column<Fixed>,
+}
+
+impl PublicConfig {
+    fn configure<F: FieldExt>(meta: &mut ConstraintSystem<F>) -> Self {
+        let public = meta.advice_column();
+        let aux = meta.aux_column();
+        let s_public = meta.fixed_column();
+
+        meta.create_gate(|meta| {
+            let s_public = meta.query_fixed(s_public, 0);
+            let public = meta.query_advice(public, 0);
+            let aux = meta.query_aux(aux, 0);
+
+            s_public * (public - aux)
+        });
+
+        PublicConfig { public, s_public }
+    }
+
+    fn assign<F: FieldExt>(
+        &self,
+        cs: &mut impl Assignment<F>,
+        row: usize,
+        value: Option<F>,
+    ) -> Result<AssignedCell<F>, Error> {
+        cs.assign_fixed(self.s_public, row, || Ok(F::one()))?;
+        cs.assign_advice(self.public, row, || value.ok_or(Error::SynthesisError))?;
+
+        Ok(AssignedCell {
+            column: self.public,
+            row,
+            value,
+        })
+    }
+}
+
+/// An [`Assignment`] that records the values assigned to an advice column,
+/// and ignores everything else.
+struct ColumnValues<F> {
+    column: Column<Advice>,
+    values: Vec<F>,
+}
+
+impl<F: Field> Assignment<F> for ColumnValues<F> {
+    fn assign_advice(
+        &mut self,
+        column: Column<Advice>,
+        row: usize,
+        to: impl FnOnce() -> Result<F, Error>,
+    ) -> Result<(), Error> {
+        if column == self.column {
+            if self.values.len() <= row {
+                self.values.resize(row + 1, F::zero());
+            }
+            self.values[row] = to()?;
+        }
+        Ok(())
+    }
+
+    fn assign_fixed(
+        &mut self,
+        _: Column<Fixed>,
+        _: usize,
+        _: impl FnOnce() -> Result<F, Error>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn copy(&mut self, _: usize, _: usize, _: usize, _: usize, _: usize) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Returns the values of the public column of `circuit`, which are those its
+/// aux column must have.
+fn public_values<F: Field, ConcreteCircuit: Circuit<F>>(
+    circuit: &ConcreteCircuit,
+    config: ConcreteCircuit::Config,
+    public: Column<Advice>,
+) -> Result<Vec<F>, Error> {
+    let mut values = ColumnValues {
+        column: public,
+        values: vec![],
+    };
+    circuit.synthesize(&mut values, config)?;
+    Ok(values.values)
+}
+
+/// Constrains two cells to be equal, using any of `permutations` that
+/// contains both of their columns.
+fn copy<F: Field>(
+    cs: &mut impl Assignment<F>,
+    permutations: &[Permutation],
+    left: &AssignedCell<F>,
+    right: &AssignedCell<F>,
+) -> Result<(), Error> {
+    permutations
+        .iter()
+        .find(|p| p.contains(left.column) && p.contains(right.column))
+        .ok_or(Error::SynthesisError)?
+        .copy(cs, left, right)
+}
+
+/// The configuration of a [`TranscriptCircuit`].
+#[derive(Clone, Debug)]
+pub struct TranscriptConfig {
+    hash: DummyHashConfig,
+    split: SplitConfig,
+    public: PublicConfig,
+    s_u: Column<Fixed>,
+    permutations: Vec<Permutation>,
+}
+
+impl TranscriptConfig {
+    /// Makes `cell` the public value at `row`, and moves `row` to the next
+    /// one.
+    fn publish<F: FieldExt>(
+        &self,
+        cs: &mut impl Assignment<F>,
+        row: &mut usize,
+        cell: &AssignedCell<F>,
+    ) -> Result<(), Error> {
+        let public = self.public.assign(cs, *row, cell.value)?;
+        *row += 1;
+        copy(cs, &self.permutations, cell, &public)
+    }
+}
+
+/// A circuit over the base field of `C` that recomputes the challenges of the
+/// transcript of a proof for a [`VerifyingKey`].
+#[derive(Debug)]
+pub struct TranscriptCircuit<'a, C: CurveAffine> {
+    vk: &'a VerifyingKey<C>,
+    num_instances: usize,
+    witness: Option<&'a ProofWitness<C>>,
+}
+
+impl<'a, C: CurveAffine> TranscriptCircuit<'a, C> {
+    /// Returns the circuit for proofs of `num_instances` instances of the
+    /// circuit of `vk`, synthesized with `witness` if given.
+    pub fn new(
+        vk: &'a VerifyingKey<C>,
+        num_instances: usize,
+        witness: Option<&'a ProofWitness<C>>,
+    ) -> Self {
+        TranscriptCircuit {
+            vk,
+            num_instances,
+            witness,
+        }
+    }
+
+    /// Returns the values of the aux column of this circuit, which must be
+    /// synthesized with a witness.
+    pub fn aux_column(&self) -> Result<Vec<C::Base>, Error> {
+        let config = Self::configure(&mut ConstraintSystem::default());
+        let public = config.public.public;
+        public_values(self, config, public)
+    }
+}
+
+impl<'a, C: CurveAffine> Circuit<C::Base> for TranscriptCircuit<'a, C> {
+    type Config = TranscriptConfig;
+
+    fn configure(meta: &mut ConstraintSystem<C::Base>) -> Self::Config {
+        let hash = DummyHashConfig::configure(meta);
+        let split = SplitConfig::configure(meta);
+        let public = PublicConfig::configure(meta);
+        let s_u = meta.fixed_column();
+
+        // The public values on the rows of U are its coordinates.
+        meta.create_gate(|meta| {
+            let y = meta.query_advice(public.public, 1);
+            let s_u = meta.query_fixed(s_u, 0);
+            let x = meta.query_advice(public.public, 0);
+
+            s_u.clone() * (y.clone() * y - x.clone() * x.clone() * x) - s_u * C::b()
+        });
+
+        let (value, state) = hash.columns();
+        let (sum, sum_lo) = split.columns();
+        let permutations = vec![
+            Permutation::configure(meta, &[value, public.public, sum_lo]),
+            Permutation::configure(meta, &[state, sum, public.public]),
+        ];
+
+        TranscriptConfig {
+            hash,
+            split,
+            public,
+            s_u,
+            permutations,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        cs: &mut impl Assignment<C::Base>,
+        config: Self::Config,
+    ) -> Result<(), Error> {
+        let layout = Layout::new(self.vk, self.num_instances);
+        let mut sponge = config.hash.init(cs, 0, C::Base::one())?;
+        let mut public_row = 0;
+        let mut split_row = 0;
+        let mut scalar_squeezes = 0;
+        for op in layout.ops.iter() {
+            match op {
+                Op::Point(index) => {
+                    let point = match self.witness {
+                        Some(witness) => Some(
+                            Option::<(C::Base, C::Base)>::from(witness.points[*index].get_xy())
+                                .ok_or(Error::InvalidPoint)?,
+                        ),
+                        None => None,
+                    };
+                    for coordinate in [point.map(|p| p.0), point.map(|p| p.1)].iter() {
+                        let cell = config.hash.absorb(cs, &mut sponge, *coordinate)?;
+                        config.publish(cs, &mut public_row, &cell)?;
+                    }
+                }
+                Op::Scalars(_) => {
+                    let value = match self.witness {
+                        Some(witness) => Some(
+                            Option::from(C::Base::from_bytes(
+                                &witness.scalar_squeezes[scalar_squeezes].to_bytes(),
+                            ))
+                            .ok_or(Error::SynthesisError)?,
+                        ),
+                        None => None,
+                    };
+                    scalar_squeezes += 1;
+                    let cell = config.hash.absorb(cs, &mut sponge, value)?;
+                    config.publish(cs, &mut public_row, &cell)?;
+                }
+                Op::Challenge => {
+                    let squeeze = config.hash.squeeze(cs, &mut sponge)?;
+                    let split = config.split.assign(cs, split_row, squeeze.value)?;
+                    split_row += SplitConfig::ROWS;
+                    copy(cs, &config.permutations, &squeeze, &split.squeeze)?;
+                    config.publish(cs, &mut public_row, &split.challenge)?;
+                }
+                Op::U => {
+                    // The sign of the y-coordinate is not constrained, which
+                    // lets the prover choose between the two points with
+                    // this x-coordinate, both of which are derived from the
+                    // transcript.
+                    let u_y = self.witness.map(|witness| witness.u.get_xy().unwrap().1);
+                    let u_x = config.hash.squeeze(cs, &mut sponge)?;
+                    cs.assign_fixed(config.s_u, public_row, || Ok(C::Base::one()))?;
+                    config.publish(cs, &mut public_row, &u_x)?;
+                    config.public.assign(cs, public_row, u_y)?;
+                    public_row += 1;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The configuration of a [`VerifierCircuit`].
+#[derive(Clone, Debug)]
+pub struct VerifierConfig {
+    arithmetic: ArithmeticConfig,
+    hash: DummyHashConfig,
+    endoscale: EndoscaleScalarConfig,
+    public: PublicConfig,
+    permutations: Vec<Permutation>,
+}
+
+/// A circuit over the scalar field of `C` that checks the evaluations of a
+/// proof for a [`VerifyingKey`], and computes the scalars of the final check
+/// of its opening.
+#[derive(Debug)]
+pub struct VerifierCircuit<'a, C: CurveAffine> {
+    vk: &'a VerifyingKey<C>,
+    num_instances: usize,
+    witness: Option<&'a ProofWitness<C>>,
+}
+
+impl<'a, C: CurveAffine> VerifierCircuit<'a, C> {
+    /// Returns the circuit for proofs of `num_instances` instances of the
+    /// circuit of `vk`, synthesized with `witness` if given.
+    pub fn new(
+        vk: &'a VerifyingKey<C>,
+        num_instances: usize,
+        witness: Option<&'a ProofWitness<C>>,
+    ) -> Self {
+        VerifierCircuit {
+            vk,
+            num_instances,
+            witness,
+        }
+    }
+
+    /// Returns the values of the aux column of this circuit, which must be
+    /// synthesized with a witness.
+    pub fn aux_column(&self) -> Result<Vec<C::Scalar>, Error> {
+        let config = Self::configure(&mut ConstraintSystem::default());
+        let public = config.public.public;
+        public_values(self, config, public)
+    }
+}
+
+/// Lays out the operations of a [`VerifierCircuit`], each of which takes a
+/// row of its [`ArithmeticConfig`] and returns the cell of the result.
+struct Region<'r, F: FieldExt, CS: Assignment<F>> {
+    cs: &'r mut CS,
+    config: &'r VerifierConfig,
+    row: usize,
+    public_row: usize,
+    endoscale_row: usize,
+    sponge: HashState<F>,
+}
+
+impl<'r, F: FieldExt, CS: Assignment<F>> Region<'r, F, CS> {
+    fn copy(&mut self, left: &AssignedCell<F>, right: &AssignedCell<F>) -> Result<(), Error> {
+        copy(self.cs, &self.config.permutations, left, right)
+    }
+
+    fn assign(
+        &mut self,
+        values: [Option<F>; 3],
+        row: ArithmeticRow<F>,
+    ) -> Result<[AssignedCell<F>; 3], Error> {
+        let cells = self
+            .config
+            .arithmetic
+            .assign(self.cs, self.row, values, row)?;
+        self.row += 1;
+        Ok(cells)
+    }
+
+    /// Returns `q_a * a + q_b * b + q_m * a * b + q_const`, where `b` is zero
+    /// if not given.
+    fn combine(
+        &mut self,
+        a: &AssignedCell<F>,
+        b: Option<&AssignedCell<F>>,
+        row: ArithmeticRow<F>,
+    ) -> Result<AssignedCell<F>, Error> {
+        let b_value = b.map_or(Some(F::zero()), |b| b.value);
+        let c = a
+            .value
+            .zip(b_value)
+            .map(|(a, b)| row.q_a * a + row.q_b * b + row.q_m * a * b + row.q_const);
+        let [a_cell, b_cell, c_cell] = self.assign(
+            [a.value, b_value, c],
+            ArithmeticRow {
+                q_c: F::one(),
+                ..row
+            },
+        )?;
+
+        self.copy(a, &a_cell)?;
+        if let Some(b) = b {
+            self.copy(b, &b_cell)?;
+        }
+        Ok(c_cell)
+    }
+
+    fn add(&mut self, a: &AssignedCell<F>, b: &AssignedCell<F>) -> Result<AssignedCell<F>, Error> {
+        self.combine(
+            a,
+            Some(b),
+            ArithmeticRow {
+                q_a: F::one(),
+                q_b: F::one(),
+                ..Default::default()
+            },
+        )
+    }
+
+    fn sub(&mut self, a: &AssignedCell<F>, b: &AssignedCell<F>) -> Result<AssignedCell<F>, Error> {
+        self.combine(
+            a,
+            Some(b),
+            ArithmeticRow {
+                q_a: F::one(),
+                q_b: -F::one(),
+                ..Default::default()
+            },
+        )
+    }
+
+    fn mul(&mut self, a: &AssignedCell<F>, b: &AssignedCell<F>) -> Result<AssignedCell<F>, Error> {
+        self.combine(
+            a,
+            Some(b),
+            ArithmeticRow {
+                q_m: F::one(),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Returns `scale * a + offset`.
+    fn affine(
+        &mut self,
+        a: &AssignedCell<F>,
+        scale: F,
+        offset: F,
+    ) -> Result<AssignedCell<F>, Error> {
+        self.combine(
+            a,
+            None,
+            ArithmeticRow {
+                q_a: scale,
+                q_const: offset,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Returns the inverse of `a`, which must not be zero.
+    fn inv(&mut self, a: &AssignedCell<F>) -> Result<AssignedCell<F>, Error> {
+        let inv = match a.value {
+            Some(a) => Some(Option::from(a.invert()).ok_or(Error::SynthesisError)?),
+            None => None,
+        };
+        let [a_cell, inv, _] = self.assign(
+            [a.value, inv, Some(F::zero())],
+            ArithmeticRow {
+                q_m: F::one(),
+                q_const: -F::one(),
+                ..Default::default()
+            },
+        )?;
+
+        self.copy(a, &a_cell)?;
+        Ok(inv)
+    }
+
+    /// Returns `numerator / denominator`, where the denominator must not be
+    /// zero.
+    fn div(
+        &mut self,
+        numerator: &AssignedCell<F>,
+        denominator: &AssignedCell<F>,
+    ) -> Result<AssignedCell<F>, Error> {
+        let quotient = match (numerator.value, denominator.value) {
+            (Some(numerator), Some(denominator)) => Some(
+                numerator * Option::<F>::from(denominator.invert()).ok_or(Error::SynthesisError)?,
+            ),
+            _ => None,
+        };
+        let [quotient, denominator_cell, numerator_cell] = self.assign(
+            [quotient, denominator.value, numerator.value],
+            ArithmeticRow {
+                q_m: F::one(),
+                q_c: F::one(),
+                ..Default::default()
+            },
+        )?;
+
+        self.copy(denominator, &denominator_cell)?;
+        self.copy(numerator, &numerator_cell)?;
+        Ok(quotient)
+    }
+
+    /// Returns a cell holding `value`, which is not constrained.
+    fn witness(&mut self, value: Option<F>) -> Result<AssignedCell<F>, Error> {
+        let [_, _, cell] = self.assign(
+            [Some(F::zero()), Some(F::zero()), value],
+            ArithmeticRow::default(),
+        )?;
+        Ok(cell)
+    }
+
+    /// Makes `cell` the next public value.
+    fn publish(&mut self, cell: &AssignedCell<F>) -> Result<(), Error> {
+        // Cells in columns that are not in a permutation argument with the
+        // public column are copied into one first.
+        let public = self.config.public.public;
+        let cell = if self
+            .config
+            .permutations
+            .iter()
+            .any(|p| p.contains(cell.column) && p.contains(public))
+        {
+            *cell
+        } else {
+            self.affine(cell, F::one(), F::zero())?
+        };
+
+        let public = self
+            .config
+            .public
+            .assign(self.cs, self.public_row, cell.value)?;
+        self.public_row += 1;
+        self.copy(&cell, &public)
+    }
+
+    /// Absorbs `value` into the scalar sponge, returning a cell holding it.
+    fn absorb(&mut self, value: Option<F>) -> Result<AssignedCell<F>, Error> {
+        let absorbed = self.config.hash.absorb(self.cs, &mut self.sponge, value)?;
+        let cell = self.witness(value)?;
+        self.copy(&absorbed, &cell)?;
+        Ok(cell)
+    }
+
+    /// Squeezes the scalar sponge, making the squeezed value public.
+    fn squeeze(&mut self) -> Result<(), Error> {
+        let squeezed = self.config.hash.squeeze(self.cs, &mut self.sponge)?;
+        self.publish(&squeezed)
+    }
+
+    /// Takes `challenge` as the next public value, returning the scalar it
+    /// maps to.
+    fn challenge(&mut self, challenge: Option<Challenge>) -> Result<AssignedCell<F>, Error> {
+        let public = self.config.public.assign(
+            self.cs,
+            self.public_row,
+            challenge.map(|challenge| F::from_u128(challenge.0)),
+        )?;
+        self.public_row += 1;
+
+        let endoscaled = self
+            .config
+            .endoscale
+            .assign(self.cs, self.endoscale_row, challenge)?;
+        self.endoscale_row += EndoscaleScalarConfig::ROWS;
+        self.copy(&public, &endoscaled.challenge)?;
+
+        Ok(endoscaled.scalar)
+    }
+}
+
+impl<'a, C: CurveAffine> Circuit<C::Scalar> for VerifierCircuit<'a, C> {
+    type Config = VerifierConfig;
+
+    fn configure(meta: &mut ConstraintSystem<C::Scalar>) -> Self::Config {
+        let arithmetic = ArithmeticConfig::configure(meta);
+        let hash = DummyHashConfig::configure(meta);
+        let endoscale = EndoscaleScalarConfig::configure(meta);
+        let public = PublicConfig::configure(meta);
+
+        let [a, b, c] = arithmetic.columns();
+        let (value, state) = hash.columns();
+        let (running_sum, acc) = endoscale.columns();
+        let permutations = vec![
+            Permutation::configure(meta, &[a, b, c, acc]),
+            Permutation::configure(meta, &[c, value, state, public.public]),
+            Permutation::configure(meta, &[running_sum, public.public]),
+        ];
+
+        VerifierConfig {
+            arithmetic,
+            hash,
+            endoscale,
+            public,
+            permutations,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        cs: &mut impl Assignment<C::Scalar>,
+        config: Self::Config,
+    ) -> Result<(), Error> {
+        let vk = self.vk;
+        let layout = Layout::new(vk, self.num_instances);
+        let witness = |f: &dyn Fn(&ProofWitness<C>) -> C::Scalar| self.witness.map(f);
+        let sponge = config.hash.init(cs, 0, C::Scalar::one())?;
+        let mut r = Region {
+            cs,
+            config: &config,
+            row: 0,
+            public_row: 0,
+            endoscale_row: 0,
+            sponge,
+        };
+
+        let mut challenges = Vec::with_capacity(layout.num_challenges);
+        let mut evals = Vec::with_capacity(layout.num_evals);
+        for op in layout.ops.iter() {
+            match op {
+                Op::Scalars(range) => {
+                    for index in range.clone() {
+                        evals.push(r.absorb(witness(&|w| w.evals[index]))?);
+                    }
+                    r.squeeze()?;
+                }
+                Op::Challenge => {
+                    let index = challenges.len();
+                    let challenge = self.witness.map(|w| w.challenges[index]);
+                    challenges.push(r.challenge(challenge)?);
+                }
+                Op::Point(_) | Op::U => (),
+            }
+        }
+        let z1 = r.witness(witness(&|w| w.z1))?;
+        let z2 = r.witness(witness(&|w| w.z2))?;
+
+        let [theta, beta, gamma, y, x] = [
+            challenges[THETA],
+            challenges[BETA],
+            challenges[GAMMA],
+            challenges[Y],
+            challenges[X],
+        ];
+        let one = C::Scalar::one();
+        let zero = C::Scalar::zero();
+
+        // x^n
+        let xn = (0..layout.k).try_fold(x, |xn, _| r.mul(&xn, &xn))?;
+        let xn_minus_one = r.affine(&xn, one, -one)?;
+
+        // l_0(x)
+        let l_0 = {
+            let x_minus_one = r.affine(&x, one, -one)?;
+            let l_0 = r.div(&xn_minus_one, &x_minus_one)?;
+            r.affine(&l_0, vk.domain.get_barycentric_weight(), zero)?
+        };
+
+        // The constraints of each instance, as in the verifier.
+        let mut expressions = vec![];
+        let fixed_evals: Vec<_> = layout.fixed_evals.iter().map(|&i| evals[i]).collect();
+        for instance in 0..self.num_instances {
+            let advice_evals: Vec<_> = layout.advice_evals[instance]
+                .iter()
+                .map(|&i| evals[i])
+                .collect();
+            let aux_evals: Vec<_> = layout.aux_evals[instance]
+                .iter()
+                .map(|&i| evals[i])
+                .collect();
+
+            {
+                let region = RefCell::new(&mut r);
+                for gate in vk.cs.gates.iter() {
+                    expressions.push(gate.evaluate(
+                        &|index| Ok(fixed_evals[index]),
+                        &|index| Ok(advice_evals[index]),
+                        &|index| Ok(aux_evals[index]),
+                        &|a, b| region.borrow_mut().add(&a?, &b?),
+                        &|a, b| region.borrow_mut().mul(&a?, &b?),
+                        &|a, scalar| region.borrow_mut().affine(&a?, scalar, zero),
+                    )?);
+                }
+            }
+
+            for (p, argument) in layout.permutations[instance]
+                .iter()
+                .zip(vk.cs.permutations.iter())
+            {
+                let product = evals[p.product];
+
+                // l_0(X) * (1 - z(X)) = 0
+                let expression = r.affine(&product, -one, one)?;
+                expressions.push(r.mul(&l_0, &expression)?);
+
+                // z(X) \prod (p(X) + \beta s_i(X) + \gamma)
+                // - z(omega^{-1} X) \prod (p(X) + \delta^i \beta X + \gamma)
+                let mut left = product;
+                let mut right = evals[p.product_inv];
+                let mut current_delta = r.mul(&beta, &x)?;
+                for (&column, &permutation_eval) in
+                    argument.get_columns().iter().zip(p.columns.iter())
+                {
+                    let advice_eval = advice_evals[vk.cs.get_advice_query_index(column, 0)];
+
+                    let term = r.mul(&beta, &evals[permutation_eval])?;
+                    let term = r.add(&advice_eval, &term)?;
+                    let term = r.add(&term, &gamma)?;
+                    left = r.mul(&left, &term)?;
+
+                    let term = r.add(&advice_eval, &current_delta)?;
+                    let term = r.add(&term, &gamma)?;
+                    right = r.mul(&right, &term)?;
+                    current_delta = r.affine(&current_delta, C::Scalar::DELTA, zero)?;
+                }
+                expressions.push(r.sub(&left, &right)?);
+            }
+
+            for (p, argument) in layout.lookups[instance].iter().zip(vk.cs.lookups.iter()) {
+                let product = evals[p.product];
+                let permuted_input = evals[p.permuted_input];
+                let permuted_table = evals[p.permuted_table];
+
+                // l_0(X) * (1 - z'(X)) = 0
+                let expression = r.affine(&product, -one, one)?;
+                expressions.push(r.mul(&l_0, &expression)?);
+
+                // z'(X) (a'(X) + \beta) (s'(X) + \gamma)
+                // - z'(\omega^{-1} X) (\theta^{m-1} a_0(X) + ... + a_{m-1}(X) + \beta) (\theta^{m-1} s_0(X) + ... + s_{m-1}(X) + \gamma)
+                let term = r.add(&permuted_input, &beta)?;
+                let left = r.mul(&product, &term)?;
+                let term = r.add(&permuted_table, &gamma)?;
+                let left = r.mul(&left, &term)?;
+
+                let mut compress_columns = |columns: &[Column<Any>]| {
+                    columns.iter().try_fold(None, |acc, column| {
+                        let index = vk.cs.get_any_query_index(*column, 0);
+                        let eval = match column.column_type() {
+                            Any::Advice => advice_evals[index],
+                            Any::Fixed => fixed_evals[index],
+                            Any::Aux => aux_evals[index],
+                        };
+                        match acc {
+                            None => Ok(Some(eval)),
+                            Some(acc) => {
+                                let acc = r.mul(&acc, &theta)?;
+                                r.add(&acc, &eval).map(Some)
+                            }
+                        }
+                    })
+                };
+                let input = compress_columns(&argument.input_columns)?;
+                let table = compress_columns(&argument.table_columns)?;
+                let (input, table) = match (input, table) {
+                    (Some(input), Some(table)) => (input, table),
+                    _ => return Err(Error::SynthesisError),
+                };
+                let term = r.add(&input, &beta)?;
+                let right = r.mul(&evals[p.product_inv], &term)?;
+                let term = r.add(&table, &gamma)?;
+                let right = r.mul(&right, &term)?;
+                expressions.push(r.sub(&left, &right)?);
+
+                // l_0(X) * (a'(X) - s'(X)) = 0
+                let difference = r.sub(&permuted_input, &permuted_table)?;
+                expressions.push(r.mul(&l_0, &difference)?);
+
+                // (a′(X)−s′(X))⋅(a′(X)−a′(\omega{-1} X)) = 0
+                let term = r.sub(&permuted_input, &evals[p.permuted_input_inv])?;
+                expressions.push(r.mul(&difference, &term)?);
+            }
+        }
+
+        // The expected value of h(x), against the one the prover committed to.
+        let mut expected_h_eval = expressions[0];
+        for expression in expressions[1..].iter() {
+            let acc = r.mul(&expected_h_eval, &y)?;
+            expected_h_eval = r.add(&acc, expression)?;
+        }
+        let mut h_evals = layout.h_evals.iter().rev().map(|&i| evals[i]);
+        let mut h_eval = h_evals.next().ok_or(Error::SynthesisError)?;
+        for eval in h_evals {
+            let acc = r.mul(&h_eval, &xn)?;
+            h_eval = r.add(&acc, &eval)?;
+        }
+        let h_eval = r.mul(&h_eval, &xn_minus_one)?;
+        r.copy(&expected_h_eval, &h_eval)?;
+
+        // The multi-point opening, where each point of a set is x scaled by a
+        // constant.
+        let [x1, x2, x3, x4] = [
+            challenges[X1],
+            challenges[X2],
+            challenges[X3],
+            challenges[X4],
+        ];
+        let mut q_eval_sets: Vec<Vec<Option<AssignedCell<_>>>> = layout
+            .point_sets
+            .iter()
+            .map(|points| vec![None; points.len()])
+            .collect();
+        for commitment_data in layout.commitments.iter() {
+            for (&eval, set_eval) in commitment_data
+                .evals
+                .iter()
+                .zip(q_eval_sets[commitment_data.set_index].iter_mut())
+            {
+                *set_eval = Some(match set_eval {
+                    None => evals[eval],
+                    Some(acc) => {
+                        let acc = r.mul(acc, &x1)?;
+                        r.add(&acc, &evals[eval])?
+                    }
+                });
+            }
+        }
+
+        // (q(x_3) - r(x_3)) / \prod (x_3 - p_i), where r interpolates the
+        // evaluations at the points p_i of the set, is
+        // q(x_3) / \prod (x_3 - p_i) - \sum e_i / ((x_3 - p_i) \prod_{j \neq i} (p_i - p_j)).
+        let mut msm_eval: Option<AssignedCell<C::Scalar>> = None;
+        for ((points, set_evals), &q_eval) in layout
+            .point_sets
+            .iter()
+            .zip(q_eval_sets.iter())
+            .zip(layout.q_evals.iter())
+        {
+            let mut differences = vec![];
+            for point in points.iter() {
+                let point = r.affine(&x, *point, zero)?;
+                differences.push(r.sub(&x3, &point)?);
+            }
+            let mut denominator = differences[0];
+            for difference in differences[1..].iter() {
+                denominator = r.mul(&denominator, difference)?;
+            }
+
+            let mut eval = r.div(&evals[q_eval], &denominator)?;
+            for (i, (point, set_eval)) in points.iter().zip(set_evals.iter()).enumerate() {
+                let mut denominator = differences[i];
+                for (j, other) in points.iter().enumerate() {
+                    if i != j {
+                        let difference = r.affine(&x, *point - other, zero)?;
+                        denominator = r.mul(&denominator, &difference)?;
+                    }
+                }
+                let set_eval = set_eval.ok_or(Error::SynthesisError)?;
+                let term = r.div(&set_eval, &denominator)?;
+                eval = r.sub(&eval, &term)?;
+            }
+
+            msm_eval = Some(match msm_eval {
+                None => eval,
+                Some(acc) => {
+                    let acc = r.mul(&acc, &x2)?;
+                    r.add(&acc, &eval)?
+                }
+            });
+        }
+        let mut v = msm_eval.ok_or(Error::SynthesisError)?;
+        for &q_eval in layout.q_evals.iter() {
+            let acc = r.mul(&v, &x4)?;
+            v = r.add(&acc, &evals[q_eval])?;
+        }
+
+        // The opening, with the challenges of its rounds as witnessed square
+        // roots. The sign of each root is not constrained: negating it
+        // negates both b and the product of the inverses of the roots, which
+        // is the same as negating z1.
+        let c = challenges[ROUNDS + layout.k];
+        let mut x3_powers = vec![x3];
+        for _ in 1..layout.k {
+            let power = *x3_powers.last().unwrap();
+            x3_powers.push(r.mul(&power, &power)?);
+        }
+        let mut round_scalars = vec![];
+        let mut allinv: Option<AssignedCell<C::Scalar>> = None;
+        let mut b: Option<AssignedCell<C::Scalar>> = None;
+        for (j, challenge_sq) in challenges[ROUNDS..(ROUNDS + layout.k)].iter().enumerate() {
+            let challenge = match challenge_sq.value {
+                Some(challenge_sq) => Some(
+                    challenge_sq
+                        .deterministic_sqrt()
+                        .ok_or(Error::SynthesisError)?,
+                ),
+                None => None,
+            };
+            let challenge = r.witness(challenge)?;
+            let square = r.mul(&challenge, &challenge)?;
+            r.copy(&square, challenge_sq)?;
+            let challenge_inv = r.inv(&challenge)?;
+            let challenge_sq_inv = r.mul(&challenge_inv, &challenge_inv)?;
+
+            round_scalars.push(r.mul(&c, challenge_sq)?);
+            round_scalars.push(r.mul(&c, &challenge_sq_inv)?);
+
+            allinv = Some(match allinv {
+                None => challenge_inv,
+                Some(acc) => r.mul(&acc, &challenge_inv)?,
+            });
+
+            // b = \prod (u_j^{-1} + u_j x_3^{2^{k - 1 - j}})
+            let term = r.mul(&challenge, &x3_powers[layout.k - 1 - j])?;
+            let term = r.add(&challenge_inv, &term)?;
+            b = Some(match b {
+                None => term,
+                Some(acc) => r.mul(&acc, &term)?,
+            });
+        }
+        let (allinv, b) = match (allinv, b) {
+            (Some(allinv), Some(b)) => (allinv, b),
+            _ => return Err(Error::SynthesisError),
+        };
+
+        // [c * v - z1 * b] U
+        let u_scalar = {
+            let cv = r.mul(&c, &v)?;
+            let z1b = r.mul(&z1, &b)?;
+            r.sub(&cv, &z1b)?
+        };
+        // [-z2] H
+        let h_scalar = r.affine(&z2, -one, zero)?;
+        // [-z1 * allinv] G, with the scalars of G computed from the rounds
+        let g_scalar = {
+            let g_scalar = r.mul(&allinv, &z1)?;
+            r.affine(&g_scalar, -one, zero)?
+        };
+
+        // The scalars of the commitments, each in the set it is opened with.
+        // The one of the t-th of m commitments in the s-th of S sets is
+        // c x_1^{m - 1 - t} x_4^{S - 1 - s}, and the one of f is c x_4^S.
+        let num_sets = layout.point_sets.len();
+        let mut set_scalars = vec![c];
+        for _ in 0..num_sets {
+            let scalar = *set_scalars.last().unwrap();
+            set_scalars.push(r.mul(&scalar, &x4)?);
+        }
+        let mut commitment_scalars = vec![None; layout.commitments.len()];
+        let mut current: Vec<Option<AssignedCell<C::Scalar>>> = vec![None; num_sets];
+        for (i, commitment_data) in layout.commitments.iter().enumerate().rev() {
+            let set = commitment_data.set_index;
+            let scalar = match current[set] {
+                None => set_scalars[num_sets - 1 - set],
+                Some(scalar) => r.mul(&scalar, &x1)?,
+            };
+            current[set] = Some(scalar);
+            commitment_scalars[i] = Some(scalar);
+        }
+
+        for scalar in commitment_scalars.iter() {
+            r.publish(&scalar.ok_or(Error::SynthesisError)?)?;
+        }
+        r.publish(&set_scalars[num_sets])?;
+        for scalar in round_scalars.iter() {
+            r.publish(scalar)?;
+        }
+        r.publish(&u_scalar)?;
+        r.publish(&h_scalar)?;
+        r.publish(&g_scalar)?;
+        for challenge_sq in challenges[ROUNDS..(ROUNDS + layout.k)].iter() {
+            r.publish(challenge_sq)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the final check of the opening of a proof for `vk`, for the
+/// circuit instances whose aux columns commit to `aux_commitments`, from the
+/// aux columns of a [`TranscriptCircuit`] and a [`VerifierCircuit`] for it.
+///
+/// The proof is valid if both circuits are satisfied and the returned
+/// multi-scalar multiplication evaluates to zero. This checks that the
+/// circuits agree on the challenges and on the values squeezed from the
+/// scalar sponge, and that the aux commitments they absorbed are the given
+/// ones.
+pub fn deferred_msm<'p, C: CurveAffine>(
+    params: &'p Params<C>,
+    vk: &VerifyingKey<C>,
+    aux_commitments: &[&[C]],
+    transcript_values: &[C::Base],
+    verifier_values: &[C::Scalar],
+) -> Result<MSM<'p, C>, Error> {
+    let layout = Layout::new(vk, aux_commitments.len());
+    if params.k as usize != layout.k {
+        return Err(Error::IncompatibleParams);
+    }
+
+    let mut base_values = transcript_values.iter();
+    let mut scalar_values = verifier_values.iter();
+    let mut next_base = || base_values.next().ok_or(Error::DeferredValuesLength);
+    let mut next_scalar = || scalar_values.next().ok_or(Error::DeferredValuesLength);
+
+    let mut points = Vec::with_capacity(layout.num_points);
+    let mut u = None;
+    for op in layout.ops.iter() {
+        match op {
+            Op::Point(_) => {
+                let (x, y) = (*next_base()?, *next_base()?);
+                points.push(Option::from(C::from_xy(x, y)).ok_or(Error::InvalidPoint)?);
+            }
+            Op::Scalars(_) | Op::Challenge => {
+                if next_base()?.to_bytes() != next_scalar()?.to_bytes() {
+                    return Err(Error::DeferredChallengeMismatch);
+                }
+            }
+            Op::U => {
+                let (x, y) = (*next_base()?, *next_base()?);
+                u = Some(Option::<C>::from(C::from_xy(x, y)).ok_or(Error::InvalidPoint)?);
+            }
+        }
+    }
+    if aux_commitments
+        .iter()
+        .flat_map(|commitments| commitments.iter())
+        .zip(points.iter())
+        .any(|(commitment, point)| commitment != point)
+    {
+        return Err(Error::AuxCommitmentMismatch);
+    }
+
+    let mut msm = params.empty_msm();
+    for commitment_data in layout.commitments.iter() {
+        let point = match commitment_data.commitment {
+            Commitment::Transcript(index) => points[index],
+            Commitment::Fixed(column) => vk.fixed_commitments[column],
+            Commitment::Permutation(argument, column) => {
+                vk.permutations[argument].commitments[column]
+            }
+        };
+        msm.append_term(*next_scalar()?, point);
+    }
+    msm.append_term(*next_scalar()?, points[layout.f]);
+    for &(l, r) in layout.rounds.iter() {
+        msm.append_term(*next_scalar()?, points[l]);
+        msm.append_term(*next_scalar()?, points[r]);
+    }
+    msm.append_term(*next_scalar()?, u.expect("the layout has a point U"));
+    msm.append_term(C::Scalar::one(), points[layout.delta]);
+    msm.add_to_h_scalar(*next_scalar()?);
+
+    let g_scalar = *next_scalar()?;
+    let mut challenges_sq = Vec::with_capacity(layout.k);
+    for _ in 0..layout.k {
+        challenges_sq.push(*next_scalar()?);
+    }
+    msm.add_to_g_scalars(&compute_s(&challenges_sq, g_scalar));
+
+    if next_base().is_ok() || next_scalar().is_ok() {
+        return Err(Error::DeferredValuesLength);
+    }
+
+    Ok(msm)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::OsRng;
+
+    use super::*;
+    use crate::arithmetic::Curve;
+    use crate::dev::MockProver;
+    use crate::multicore::ThreadPool;
+    use crate::pasta::{EpAffine, EqAffine, Fp, Fq};
+    use crate::plonk::{keygen, ProvingKey};
+    use crate::poly::commitment::Blind;
+
+    #[test]
+    fn test_verifier() {
+        const INNER_K: u32 = 4;
+        const K: u32 = 10;
+
+        // Multiplies two values and the product by the second one again, with
+        // the first value public and looked up in a table, so that the proof
+        // has gates, permutation and lookup arguments, and queries at x, \omega x
+        // and \omega^{-1} x.
+        struct InnerCircuit;
+
+        #[derive(Clone, Copy)]
+        struct InnerConfig {
+            a: Column<Advice>,
+            b: Column<Advice>,
+            c: Column<Advice>,
+            sm: Column<Fixed>,
+            sn: Column<Fixed>,
+            sp: Column<Fixed>,
+            sl: Column<Fixed>,
+            perm: usize,
+        }
+
+        impl Circuit<Fp> for InnerCircuit {
+            type Config = InnerConfig;
+
+            fn configure(meta: &mut ConstraintSystem<Fp>) -> InnerConfig {
+                let a = meta.advice_column();
+                let b = meta.advice_column();
+                let c = meta.advice_column();
+                let p = meta.aux_column();
+                let sm = meta.fixed_column();
+                let sn = meta.fixed_column();
+                let sp = meta.fixed_column();
+                let sl = meta.fixed_column();
+                let perm = meta.permutation(&[a, b, c]);
+                meta.lookup(&[a.into()], &[sl.into()]);
+
+                meta.create_gate(|meta| {
+                    let sm = meta.query_fixed(sm, 0);
+                    let a = meta.query_advice(a, 0);
+                    let b = meta.query_advice(b, 0);
+                    let c = meta.query_advice(c, 0);
+
+                    sm * (a * b - c)
+                });
+                meta.create_gate(|meta| {
+                    let a_next = meta.query_advice(a, 1);
+                    let sn = meta.query_fixed(sn, 0);
+                    let c = meta.query_advice(c, 0);
+
+                    sn * (a_next - c)
+                });
+                meta.create_gate(|meta| {
+                    let sp = meta.query_fixed(sp, 0);
+                    let a = meta.query_advice(a, 0);
+                    let p = meta.query_aux(p, 0);
+
+                    sp * (a - p)
+                });
+
+                InnerConfig {
+                    a,
+                    b,
+                    c,
+                    sm,
+                    sn,
+                    sp,
+                    sl,
+                    perm,
+                }
+            }
+
+            fn synthesize(
+                &self,
+                cs: &mut impl Assignment<Fp>,
+                config: InnerConfig,
+            ) -> Result<(), Error> {
+                let (two, three) = (Fp::from_u64(2), Fp::from_u64(3));
+                for (row, &(a, b)) in [(two, three), (two * three, three)].iter().enumerate() {
+                    cs.assign_fixed(config.sm, row, || Ok(Fp::one()))?;
+                    cs.assign_fixed(config.sl, row, || Ok(a))?;
+                    cs.assign_advice(config.a, row, || Ok(a))?;
+                    cs.assign_advice(config.b, row, || Ok(b))?;
+                    cs.assign_advice(config.c, row, || Ok(a * b))?;
+                }
+                cs.assign_fixed(config.sn, 0, || Ok(Fp::one()))?;
+                cs.assign_fixed(config.sp, 0, || Ok(Fp::one()))?;
+                cs.copy(config.perm, 2, 0, 0, 1)
+            }
+        }
+
+        // Proves `circuit`, whose only aux column has the given values, and
+        // returns whether the proof verifies.
+        fn verifies<C: CurveAffine, ConcreteCircuit: Circuit<C::Scalar>>(
+            params: &Params<C>,
+            pk: &ProvingKey<C>,
+            circuit: ConcreteCircuit,
+            values: &[C::Scalar],
+        ) -> bool {
+            let mut aux = pk.get_vk().get_domain().empty_lagrange();
+            for (aux, value) in aux.iter_mut().zip(values.iter()) {
+                *aux = *value;
+            }
+            let aux_commitment = params.commit_lagrange(&aux, Blind::default()).to_affine();
+
+            let proof = match Proof::create::<DummyHash<C::Base>, DummyHash<C::Scalar>, _, _, _>(
+                params,
+                pk,
+                &[circuit],
+                &[&[aux]],
+                &ThreadPool::global(),
+                OsRng,
+            ) {
+                Ok(proof) => proof,
+                Err(_) => return false,
+            };
+            let aux_commitments: &[&[C]] = &[&[aux_commitment]];
+            proof
+                .verify::<DummyHash<C::Base>, DummyHash<C::Scalar>, _>(
+                    params,
+                    pk.get_vk(),
+                    params.empty_msm(),
+                    aux_commitments,
+                    &[],
+                )
+                .is_ok_and(|guard| guard.use_challenges().eval())
+        }
+
+        // Checks `circuit`, whose only aux column has the given values, with
+        // the mock prover.
+        fn mock_verify<F: FieldExt, ConcreteCircuit: Circuit<F>>(
+            circuit: &ConcreteCircuit,
+            values: &[F],
+        ) -> bool {
+            let mut aux = values.to_vec();
+            aux.resize(1 << K, F::zero());
+            MockProver::run(K, circuit, vec![aux])
+                .expect("synthesis should not fail")
+                .verify()
+                .is_ok()
+        }
+
+        let inner_params: Params<EqAffine> = Params::new(INNER_K);
+        let inner_pk = keygen(&inner_params, &InnerCircuit).expect("keygen should not fail");
+        let inner_vk = inner_pk.get_vk();
+
+        let mut aux = inner_vk.get_domain().empty_lagrange();
+        aux[0] = Fp::from_u64(2);
+        let aux_commitment = inner_params
+            .commit_lagrange(&aux, Blind::default())
+            .to_affine();
+        let aux_commitments: &[&[EqAffine]] = &[&[aux_commitment]];
+        let proof = Proof::create::<DummyHash<Fq>, DummyHash<Fp>, _, _, _>(
+            &inner_params,
+            &inner_pk,
+            &[InnerCircuit],
+            &[&[aux]],
+            &ThreadPool::global(),
+            OsRng,
+        )
+        .expect("proof generation should not fail");
+        let witness = ProofWitness::new(inner_vk, &proof, aux_commitments).unwrap();
+
+        // The transcript circuit is over the scalar field of Pallas, and the
+        // verifier circuit over that of Vesta.
+        let transcript_circuit = TranscriptCircuit::new(inner_vk, 1, Some(&witness));
+        let transcript_values = transcript_circuit.aux_column().unwrap();
+        let params: Params<EpAffine> = Params::new(K);
+        let pk = keygen(&params, &TranscriptCircuit::new(inner_vk, 1, None))
+            .expect("keygen should not fail");
+        assert!(verifies(
+            &params,
+            &pk,
+            transcript_circuit,
+            &transcript_values
+        ));
+
+        let verifier_circuit = VerifierCircuit::new(inner_vk, 1, Some(&witness));
+        let verifier_values = verifier_circuit.aux_column().unwrap();
+        let params: Params<EqAffine> = Params::new(K);
+        let pk = keygen(&params, &VerifierCircuit::new(inner_vk, 1, None))
+            .expect("keygen should not fail");
+        assert!(verifies(&params, &pk, verifier_circuit, &verifier_values));
+
+        let msm = deferred_msm(
+            &inner_params,
+            inner_vk,
+            aux_commitments,
+            &transcript_values,
+            &verifier_values,
+        )
+        .unwrap();
+        assert!(msm.eval());
+
+        // The last output is the challenge of the last round of the opening.
+        let mut wrong_values = verifier_values.clone();
+        *wrong_values.last_mut().unwrap() += Fp::one();
+        let msm = deferred_msm(
+            &inner_params,
+            inner_vk,
+            aux_commitments,
+            &transcript_values,
+            &wrong_values,
+        )
+        .unwrap();
+        assert!(!msm.eval());
+
+        // The circuits must agree on the challenges, the first of which is theta.
+        let mut wrong_values = verifier_values.clone();
+        wrong_values[THETA] += Fp::one();
+        assert_eq!(
+            deferred_msm(
+                &inner_params,
+                inner_vk,
+                aux_commitments,
+                &transcript_values,
+                &wrong_values,
+            )
+            .map(|msm| msm.eval()),
+            Err(Error::DeferredChallengeMismatch)
+        );
+
+        // The verifier circuit is not satisfied by a proof whose evaluations do
+        // not satisfy the constraints.
+        let mut wrong_witness = witness.clone();
+        wrong_witness.evals[0] += Fp::one();
+        let verifier_circuit = VerifierCircuit::new(inner_vk, 1, Some(&wrong_witness));
+        let wrong_values = verifier_circuit.aux_column().unwrap();
+        assert!(!mock_verify(&verifier_circuit, &wrong_values));
+        assert!(!verifies(&params, &pk, verifier_circuit, &wrong_values));
+
+        // Neither circuit is satisfied with public values other than those of
+        // the proof.
+        let transcript_circuit = TranscriptCircuit::new(inner_vk, 1, Some(&witness));
+        assert!(mock_verify(&transcript_circuit, &transcript_values));
+        let mut wrong_values = transcript_values.clone();
+        wrong_values[0] += Fq::one();
+        assert!(!mock_verify(&transcript_circuit, &wrong_values));
+
+        let verifier_circuit = VerifierCircuit::new(inner_vk, 1, Some(&witness));
+        assert!(mock_verify(&verifier_circuit, &verifier_values));
+        let mut wrong_values = verifier_values.clone();
+        wrong_values[THETA] += Fp::one();
+        assert!(!mock_verify(&verifier_circuit, &wrong_values));
+    }
+}
@@ -13,10 +13,18 @@
 #![deny(missing_docs)]
 #![deny(unsafe_code)]
 
+#[macro_use]
+mod macros;
+
 pub mod arithmetic;
+pub mod bn254;
+pub mod dev;
+pub mod gadget;
+pub mod multicore;
 pub mod pasta;
 pub mod plonk;
 pub mod poly;
+pub mod secp;
 pub mod transcript;
 
 pub mod model;
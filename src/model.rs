@@ -7,6 +7,11 @@ use std::sync::Arc;
 
 use metrics::{GaugeValue, Key, Recorder, Unit};
 
+mod cost;
+mod degree;
+pub use cost::{CircuitCost, ProofSize, ProverCost, VerifierCost};
+pub use degree::{Constraint, DegreeReport};
+
 /// A [`metrics`] recorder for examining halo2 metrics.
 ///
 /// # Examples
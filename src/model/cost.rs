@@ -0,0 +1,603 @@
+//! Analytic estimates of the cost of proving and verifying a circuit.
+
+use std::collections::BTreeSet;
+use std::marker::PhantomData;
+
+use crate::arithmetic::CurveAffine;
+use crate::plonk::{Circuit, ConstraintSystem};
+use crate::poly::Rotation;
+
+/// The size in bytes of a scalar in a proof.
+const SCALAR_BYTES: usize = 32;
+
+/// The cost of a circuit, computed directly from its [`ConstraintSystem`], the
+/// size of the evaluation domain and the number of circuit instances in a
+/// proof, without synthesizing a witness or creating a proof.
+///
+/// # Examples
+///
+/// ```
+/// use halo2::model::CircuitCost;
+/// # use halo2::arithmetic::FieldExt;
+/// # use halo2::pasta::EqAffine;
+/// # use halo2::plonk::{Advice, Assignment, Circuit, Column, ConstraintSystem, Error};
+/// #
+/// # #[derive(Default)]
+/// # struct MyCircuit;
+/// #
+/// # impl<F: FieldExt> Circuit<F> for MyCircuit {
+/// #     type Config = Column<Advice>;
+/// #
+/// #     fn configure(meta: &mut ConstraintSystem<F>) -> Column<Advice> {
+/// #         let a = meta.advice_column();
+/// #         meta.create_gate(|meta| {
+/// #             let a = meta.query_advice(a, 0);
+/// #             a.clone() * a.clone() - a
+/// #         });
+/// #         a
+/// #     }
+/// #
+/// #     fn synthesize(&self, cs: &mut impl Assignment<F>, a: Column<Advice>) -> Result<(), Error> {
+/// #         cs.assign_advice(a, 0, || Ok(F::one()))
+/// #     }
+/// # }
+/// #
+///
+/// let cost = CircuitCost::<EqAffine>::measure::<MyCircuit>(11, 1);
+/// println!("proof size: {} bytes", cost.proof_size().total());
+/// ```
+#[derive(Debug)]
+pub struct CircuitCost<C: CurveAffine> {
+    /// Power-of-2 bound on the number of rows in the circuit.
+    k: u32,
+    /// Number of circuit instances in a proof.
+    instances: usize,
+    /// Maximum degree of the circuit.
+    max_deg: usize,
+    /// Number of advice columns.
+    advice_columns: usize,
+    /// Number of aux columns.
+    aux_columns: usize,
+    /// Number of advice queries.
+    advice_queries: usize,
+    /// Number of aux queries.
+    aux_queries: usize,
+    /// Number of fixed queries.
+    fixed_queries: usize,
+    /// Number of commitments opened by the multiopen argument for each
+    /// circuit instance.
+    instance_commitments: usize,
+    /// Number of commitments opened by the multiopen argument once for all
+    /// circuit instances.
+    shared_commitments: usize,
+    /// Number of distinct point sets in the multiopen argument.
+    point_sets: usize,
+    /// Number of gates.
+    gates: usize,
+    /// Field multiplications needed to evaluate the gates at a point.
+    gate_muls: usize,
+    /// Field additions needed to evaluate the gates at a point.
+    gate_adds: usize,
+    /// Number of columns in each permutation argument.
+    permutation_columns: Vec<usize>,
+    /// Number of input columns in each lookup argument.
+    lookup_columns: Vec<usize>,
+    _marker: PhantomData<C>,
+}
+
+/// The size of a proof, broken down by the part of the argument that
+/// contributes to it. All values are in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofSize {
+    /// Commitments to the advice columns.
+    pub advice_commitments: usize,
+    /// Evaluations of the advice, aux and fixed columns.
+    pub column_evals: usize,
+    /// Commitments and evaluations of the permutation arguments.
+    pub permutations: usize,
+    /// Commitments and evaluations of the lookup arguments.
+    pub lookups: usize,
+    /// Commitments to the pieces of h(X) and their evaluations.
+    pub vanishing: usize,
+    /// The multi-point opening, excluding its inner product argument.
+    pub multiopen: usize,
+    /// The inner product argument opening.
+    pub polycommit: usize,
+}
+
+impl ProofSize {
+    /// Returns the total size of the proof in bytes.
+    pub fn total(&self) -> usize {
+        self.advice_commitments
+            + self.column_evals
+            + self.permutations
+            + self.lookups
+            + self.vanishing
+            + self.multiopen
+            + self.polycommit
+    }
+}
+
+/// The work done by the verifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifierCost {
+    /// Number of terms in the final multiscalar multiplication when the
+    /// verifier computes G itself (as in `Guard::use_challenges`).
+    pub msm_terms: usize,
+    /// Number of terms in the multiscalar multiplication when G is supplied
+    /// by the caller (as in `Guard::use_g`) and checked later.
+    pub deferred_msm_terms: usize,
+    /// Estimated field multiplications spent checking h(x), excluding the
+    /// inner product argument.
+    pub field_muls: usize,
+    /// Estimated field additions spent checking h(x), excluding the inner
+    /// product argument.
+    pub field_adds: usize,
+}
+
+/// The work done by the prover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProverCost {
+    /// Number of inverse FFTs over the $2^k$ size domain.
+    pub iffts: usize,
+    /// Number of FFTs over the extended domain.
+    pub extended_ffts: usize,
+    /// Number of inverse FFTs over the extended domain.
+    pub extended_iffts: usize,
+    /// Size of the extended domain.
+    pub extended_len: usize,
+    /// Number of multiscalar multiplications of size $2^k + 1$ used to
+    /// commit to polynomials.
+    pub commitment_msms: usize,
+    /// Number of multiscalar multiplications performed by the inner product
+    /// argument, which halve in size every round.
+    pub polycommit_msms: usize,
+}
+
+impl<C: CurveAffine> CircuitCost<C> {
+    /// Measures a circuit with parameter constant `k`, for proofs of
+    /// `instances` circuit instances.
+    pub fn measure<ConcreteCircuit: Circuit<C::Scalar>>(k: u32, instances: usize) -> Self {
+        let mut cs = ConstraintSystem::default();
+        let _ = ConcreteCircuit::configure(&mut cs);
+
+        // Collect the rotations each column is queried at; every queried column
+        // is a distinct commitment in the multiopen argument.
+        let column_sets = |queries: Vec<(usize, Rotation)>| {
+            let mut sets: Vec<BTreeSet<i32>> = vec![];
+            for (index, at) in queries {
+                if sets.len() <= index {
+                    sets.resize(index + 1, BTreeSet::new());
+                }
+                sets[index].insert(at.0);
+            }
+            sets.into_iter().filter(|set| !set.is_empty())
+        };
+
+        let cur: BTreeSet<i32> = Some(0).into_iter().collect();
+        let cur_and_prev: BTreeSet<i32> = vec![0, -1].into_iter().collect();
+        let quotient_pieces = cs.degree() - 1;
+
+        // The commitments opened for each instance, then those opened once.
+        let instance_sets: Vec<BTreeSet<i32>> = column_sets(
+            cs.advice_queries
+                .iter()
+                .map(|(c, at)| (c.index(), *at))
+                .collect(),
+        )
+        .chain(column_sets(
+            cs.aux_queries
+                .iter()
+                .map(|(c, at)| (c.index(), *at))
+                .collect(),
+        ))
+        // Permutation product and permutation polynomials
+        .chain(cs.permutations.iter().flat_map(|p| {
+            Some(cur_and_prev.clone())
+                .into_iter()
+                .chain((0..p.get_columns().len()).map(|_| cur.clone()))
+        }))
+        // Lookup product, permuted input and permuted table
+        .chain(cs.lookups.iter().flat_map(|_| {
+            vec![cur_and_prev.clone(), cur_and_prev.clone(), cur.clone()].into_iter()
+        }))
+        .collect();
+        let shared_sets: Vec<BTreeSet<i32>> = column_sets(
+            cs.fixed_queries
+                .iter()
+                .map(|(c, at)| (c.index(), *at))
+                .collect(),
+        )
+        // h(X) pieces
+        .chain((0..quotient_pieces).map(|_| cur.clone()))
+        .collect();
+
+        let instance_commitments = instance_sets.len();
+        let shared_commitments = shared_sets.len();
+        let point_sets = instance_sets
+            .into_iter()
+            .chain(shared_sets)
+            .collect::<BTreeSet<_>>()
+            .len();
+
+        let (gate_adds, gate_muls) = cs.gates.iter().fold((0, 0), |(adds, muls), gate| {
+            let (a, m) = gate.evaluate(
+                &|_| (0, 0),
+                &|_| (0, 0),
+                &|_| (0, 0),
+                &|(a1, m1), (a2, m2)| (a1 + a2 + 1, m1 + m2),
+                &|(a1, m1), (a2, m2)| (a1 + a2, m1 + m2 + 1),
+                &|(a, m), _| (a, m + 1),
+            );
+            (adds + a, muls + m)
+        });
+
+        CircuitCost {
+            k,
+            instances,
+            max_deg: cs.degree(),
+            advice_columns: cs.num_advice_columns,
+            aux_columns: cs.num_aux_columns,
+            advice_queries: cs.advice_queries.len(),
+            aux_queries: cs.aux_queries.len(),
+            fixed_queries: cs.fixed_queries.len(),
+            instance_commitments,
+            shared_commitments,
+            point_sets,
+            gates: cs.gates.len(),
+            gate_muls,
+            gate_adds,
+            permutation_columns: cs
+                .permutations
+                .iter()
+                .map(|p| p.get_columns().len())
+                .collect(),
+            lookup_columns: cs.lookups.iter().map(|l| l.input_columns.len()).collect(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the size in bytes of a compressed curve point in a proof.
+    fn point_bytes() -> usize {
+        C::Repr::default().as_ref().len()
+    }
+
+    /// Returns the number of pieces the quotient polynomial h(X) is split into.
+    fn quotient_pieces(&self) -> usize {
+        self.max_deg - 1
+    }
+
+    /// Returns the size of the extended evaluation domain.
+    fn extended_len(&self) -> usize {
+        let n = 1usize << self.k;
+        let mut extended_len = n;
+        while extended_len < n * self.quotient_pieces() {
+            extended_len <<= 1;
+        }
+        extended_len
+    }
+
+    /// Returns the size of a proof for this circuit.
+    pub fn proof_size(&self) -> ProofSize {
+        let point_bytes = Self::point_bytes();
+        let permutations: usize = self
+            .permutation_columns
+            .iter()
+            .map(|columns| point_bytes + (2 + columns) * SCALAR_BYTES)
+            .sum();
+        let lookups = self.lookup_columns.len() * (3 * point_bytes + 5 * SCALAR_BYTES);
+
+        // The advice, aux, permutation and lookup parts are given once per
+        // instance.
+        ProofSize {
+            advice_commitments: self.instances * self.advice_columns * point_bytes,
+            column_evals: (self.instances * (self.advice_queries + self.aux_queries)
+                + self.fixed_queries)
+                * SCALAR_BYTES,
+            permutations: self.instances * permutations,
+            lookups: self.instances * lookups,
+            vanishing: self.quotient_pieces() * (point_bytes + SCALAR_BYTES),
+            // q_evals and the commitment to f(X)
+            multiopen: self.point_sets * SCALAR_BYTES + point_bytes,
+            // (L, R) for each round, delta, z1 and z2
+            polycommit: (self.k as usize) * 2 * point_bytes + point_bytes + 2 * SCALAR_BYTES,
+        }
+    }
+
+    /// Returns an estimate of the work done by the verifier.
+    pub fn verifier_cost(&self) -> VerifierCost {
+        let n = 1usize << self.k;
+
+        // Opened commitments, f(X), (L, R) for each round, U, delta and H.
+        let fixed_terms = self.instances * self.instance_commitments
+            + self.shared_commitments
+            + 1
+            + 2 * (self.k as usize)
+            + 3;
+
+        let permutation_muls: usize = self
+            .permutation_columns
+            .iter()
+            .map(|columns| 4 * columns + 2)
+            .sum();
+        let lookup_muls: usize = self
+            .lookup_columns
+            .iter()
+            .map(|columns| 2 * columns + 7)
+            .sum();
+        let expressions =
+            self.gates + 2 * self.permutation_columns.len() + 4 * self.lookup_columns.len();
+
+        VerifierCost {
+            msm_terms: fixed_terms + n,
+            deferred_msm_terms: fixed_terms + 1,
+            // Each expression of each instance is folded into h(x) with one
+            // multiplication by y.
+            field_muls: self.instances
+                * (self.gate_muls + permutation_muls + lookup_muls + expressions)
+                + self.quotient_pieces(),
+            field_adds: self.instances * (self.gate_adds + expressions) + self.quotient_pieces(),
+        }
+    }
+
+    /// Returns an estimate of the work done by the prover.
+    pub fn prover_cost(&self) -> ProverCost {
+        let permutations = self.permutation_columns.len();
+        let lookups = self.lookup_columns.len();
+
+        // Everything but h(X) and the opening is done once per instance.
+        ProverCost {
+            // Advice and aux columns, permutation products, and the permuted
+            // columns and products of each lookup.
+            iffts: self.instances
+                * (self.advice_columns + self.aux_columns + permutations + 3 * lookups),
+            // Every advice and aux query, z(X) and z(omega^{-1} X) for each
+            // permutation, and five cosets for each lookup.
+            extended_ffts: self.instances
+                * (self.advice_queries + self.aux_queries + 2 * permutations + 5 * lookups),
+            extended_iffts: 1,
+            extended_len: self.extended_len(),
+            commitment_msms: self.instances
+                * (self.aux_columns + self.advice_columns + permutations + 3 * lookups)
+                + self.quotient_pieces()
+                + 1,
+            // L and R in each round
+            polycommit_msms: 2 * (self.k as usize),
+        }
+    }
+}
+
+#[test]
+fn test_circuit_cost() {
+    use crate::pasta::{EqAffine, Fp};
+    use crate::plonk::{Advice, Assignment, Column, Error, Fixed};
+
+    #[allow(dead_code)]
+    struct TestConfig {
+        a: Column<Advice>,
+        b: Column<Advice>,
+        s: Column<Fixed>,
+    }
+
+    struct TestCircuit;
+
+    impl Circuit<Fp> for TestCircuit {
+        type Config = TestConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TestConfig {
+            let a = meta.advice_column();
+            let b = meta.advice_column();
+            let s = meta.fixed_column();
+
+            meta.permutation(&[a, b]);
+
+            meta.create_gate(|meta| {
+                let a_next = meta.query_advice(a, 1);
+                let a = meta.query_advice(a, 0);
+                let b = meta.query_advice(b, 0);
+                let s = meta.query_fixed(s, 0);
+
+                s * (a * b + a_next)
+            });
+
+            TestConfig { a, b, s }
+        }
+
+        fn synthesize(&self, _: &mut impl Assignment<Fp>, _: TestConfig) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    const K: u32 = 4;
+    let cost = CircuitCost::<EqAffine>::measure::<TestCircuit>(K, 1);
+    let point_bytes = CircuitCost::<EqAffine>::point_bytes();
+    assert_eq!(point_bytes, 32);
+
+    // The gate and the permutation are both degree 3.
+    assert_eq!(cost.quotient_pieces(), 2);
+
+    let proof_size = cost.proof_size();
+    assert_eq!(proof_size.advice_commitments, 2 * point_bytes);
+    // a, a_next, b and s
+    assert_eq!(proof_size.column_evals, 4 * SCALAR_BYTES);
+    assert_eq!(proof_size.permutations, point_bytes + 4 * SCALAR_BYTES);
+    assert_eq!(proof_size.lookups, 0);
+    assert_eq!(proof_size.vanishing, 2 * (point_bytes + SCALAR_BYTES));
+    // Point sets {cur, next}, {cur} and {cur, prev}
+    assert_eq!(proof_size.multiopen, 3 * SCALAR_BYTES + point_bytes);
+    assert_eq!(
+        proof_size.polycommit,
+        (2 * K as usize + 1) * point_bytes + 2 * SCALAR_BYTES
+    );
+
+    // a, b, s, two h(X) pieces, the permutation product and two permutation
+    // polynomials, then f(X), (L, R) for each round, U, delta and H.
+    let verifier_cost = cost.verifier_cost();
+    assert_eq!(
+        verifier_cost.deferred_msm_terms,
+        8 + 1 + 2 * K as usize + 3 + 1
+    );
+    assert_eq!(
+        verifier_cost.msm_terms,
+        verifier_cost.deferred_msm_terms - 1 + (1 << K)
+    );
+
+    let prover_cost = cost.prover_cost();
+    assert_eq!(prover_cost.iffts, 3);
+    assert_eq!(prover_cost.extended_ffts, 5);
+    assert_eq!(prover_cost.extended_len, 2 << K);
+}
+
+#[test]
+fn test_proof_size() {
+    use std::iter;
+
+    use rand::rngs::OsRng;
+
+    use crate::arithmetic::{Curve, FieldExt};
+    use crate::multicore::ThreadPool;
+    use crate::pasta::{EqAffine, Fp, Fq};
+    use crate::plonk::{keygen, Advice, Assignment, Aux, Column, Error, Fixed, Proof};
+    use crate::poly::commitment::{Blind, Params};
+    use crate::transcript::DummyHash;
+
+    #[derive(Clone, Copy)]
+    struct TestConfig {
+        a: Column<Advice>,
+        b: Column<Advice>,
+        p: Column<Aux>,
+        s: Column<Fixed>,
+        perm: usize,
+    }
+
+    // Has every part of a proof: a gate with queries at two rotations, an aux
+    // column, and permutation and lookup arguments.
+    struct TestCircuit(Fp);
+
+    impl Circuit<Fp> for TestCircuit {
+        type Config = TestConfig;
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TestConfig {
+            let a = meta.advice_column();
+            let b = meta.advice_column();
+            let p = meta.aux_column();
+            let s = meta.fixed_column();
+            let sl = meta.fixed_column();
+
+            let perm = meta.permutation(&[a, b]);
+            meta.lookup(&[a.into()], &[sl.into()]);
+
+            meta.create_gate(|meta| {
+                let a_next = meta.query_advice(a, 1);
+                let a = meta.query_advice(a, 0);
+                let b = meta.query_advice(b, 0);
+                let p = meta.query_aux(p, 0);
+                let s = meta.query_fixed(s, 0);
+
+                s * (a * b - a_next - p)
+            });
+
+            TestConfig { a, b, p, s, perm }
+        }
+
+        fn synthesize(
+            &self,
+            cs: &mut impl Assignment<Fp>,
+            config: TestConfig,
+        ) -> Result<(), Error> {
+            cs.assign_fixed(config.s, 0, || Ok(Fp::one()))?;
+            cs.assign_advice(config.a, 0, || Ok(Fp::zero()))?;
+            cs.assign_advice(config.b, 0, || Ok(self.0))?;
+            cs.assign_advice(config.b, 1, || Ok(self.0))?;
+            cs.copy(config.perm, 1, 0, 1, 1)
+        }
+    }
+
+    const K: u32 = 4;
+    let params: Params<EqAffine> = Params::new(K);
+    let pk = keygen(&params, &TestCircuit(Fp::zero())).expect("keygen should not fail");
+
+    for instances in 1..=3 {
+        let circuits: Vec<_> = (0..instances)
+            .map(|i| TestCircuit(Fp::from_u64(i as u64)))
+            .collect();
+        // The verifier tells commitments apart by address, so each instance
+        // needs its own aux commitment.
+        let aux: Vec<_> = (0..instances)
+            .map(|_| [pk.get_vk().get_domain().empty_lagrange()])
+            .collect();
+        let aux_commitment: Vec<_> = aux
+            .iter()
+            .map(|aux| {
+                [params
+                    .commit_lagrange(&aux[0], Blind::default())
+                    .to_affine()]
+            })
+            .collect();
+        let aux: Vec<_> = aux.iter().map(|aux| &aux[..]).collect();
+        let aux_commitments: Vec<_> = aux_commitment.iter().map(|c| &c[..]).collect();
+        let proof = Proof::create::<DummyHash<Fq>, DummyHash<Fp>, _, _, _>(
+            &params,
+            &pk,
+            &circuits,
+            &aux,
+            &ThreadPool::global(),
+            OsRng,
+        )
+        .expect("proof generation should not fail");
+        let guard = proof
+            .verify::<DummyHash<Fq>, DummyHash<Fp>, _>(
+                &params,
+                pk.get_vk(),
+                params.empty_msm(),
+                &aux_commitments,
+                &[],
+            )
+            .unwrap();
+        assert!(guard.use_challenges().eval());
+
+        // Serialize every point and scalar of the proof.
+        let (circuit, multiopening) = (&proof.circuit, &proof.multiopening);
+        let opening = &multiopening.opening;
+        let points = circuit
+            .advice_commitments
+            .iter()
+            .flatten()
+            .chain(
+                circuit
+                    .permutations
+                    .iter()
+                    .flatten()
+                    .map(|permutation| &permutation.permutation_product_commitment),
+            )
+            .chain(circuit.lookups.iter().flatten().flat_map(|lookup| {
+                iter::once(&lookup.product_commitment)
+                    .chain(Some(&lookup.permuted_input_commitment))
+                    .chain(Some(&lookup.permuted_table_commitment))
+            }))
+            .chain(circuit.vanishing.h_commitments.iter())
+            .chain(Some(&multiopening.f_commitment))
+            .chain(
+                opening
+                    .rounds
+                    .iter()
+                    .flat_map(|(l, r)| iter::once(l).chain(Some(r))),
+            )
+            .chain(Some(&opening.delta));
+        let scalars = circuit
+            .evals()
+            .chain(multiopening.q_evals.iter())
+            .chain(Some(&opening.z1))
+            .chain(Some(&opening.z2));
+        let mut bytes = vec![];
+        for point in points {
+            bytes.extend_from_slice(point.to_bytes().as_ref());
+        }
+        for scalar in scalars {
+            bytes.extend_from_slice(&scalar.to_bytes());
+        }
+
+        let cost = CircuitCost::<EqAffine>::measure::<TestCircuit>(K, instances);
+        assert_eq!(bytes.len(), cost.proof_size().total());
+    }
+}
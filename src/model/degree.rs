@@ -0,0 +1,250 @@
+//! Reports on the degree of a circuit's constraints.
+
+use std::fmt;
+
+use ff::Field;
+
+use crate::plonk::{Circuit, ConstraintSystem};
+
+/// A constraint that contributes to the degree of a circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    /// The gate with the given index.
+    Gate(usize),
+    /// The permutation argument with the given index.
+    Permutation(usize),
+    /// The lookup argument with the given index.
+    Lookup(usize),
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Constraint::Gate(index) => write!(f, "gate {}", index),
+            Constraint::Permutation(index) => write!(f, "permutation {}", index),
+            Constraint::Lookup(index) => write!(f, "lookup {}", index),
+        }
+    }
+}
+
+/// The degree of each constraint in a circuit, and the effect they have on the
+/// size of the extended evaluation domain used by the prover.
+///
+/// # Examples
+///
+/// ```
+/// use halo2::model::DegreeReport;
+/// # use halo2::arithmetic::FieldExt;
+/// # use halo2::pasta::Fp;
+/// # use halo2::plonk::{Advice, Assignment, Circuit, Column, ConstraintSystem, Error};
+/// #
+/// # #[derive(Default)]
+/// # struct MyCircuit;
+/// #
+/// # impl<F: FieldExt> Circuit<F> for MyCircuit {
+/// #     type Config = Column<Advice>;
+/// #
+/// #     fn configure(meta: &mut ConstraintSystem<F>) -> Column<Advice> {
+/// #         let a = meta.advice_column();
+/// #         meta.create_gate(|meta| {
+/// #             let a = meta.query_advice(a, 0);
+/// #             a.clone() * a.clone() - a
+/// #         });
+/// #         a
+/// #     }
+/// #
+/// #     fn synthesize(&self, cs: &mut impl Assignment<F>, a: Column<Advice>) -> Result<(), Error> {
+/// #         cs.assign_advice(a, 0, || Ok(F::one()))
+/// #     }
+/// # }
+/// #
+///
+/// let report = DegreeReport::measure::<Fp, MyCircuit>();
+/// println!("{}", report);
+/// ```
+#[derive(Debug)]
+pub struct DegreeReport {
+    constraints: Vec<(Constraint, usize)>,
+    gates: Vec<String>,
+    degree: usize,
+}
+
+impl DegreeReport {
+    /// Measures the constraint degrees of a circuit.
+    pub fn measure<F: Field, ConcreteCircuit: Circuit<F>>() -> Self {
+        let mut cs = ConstraintSystem::default();
+        let _ = ConcreteCircuit::configure(&mut cs);
+        Self::from_cs(&cs)
+    }
+
+    /// Builds a report from an already-configured constraint system.
+    pub fn from_cs<F: Field>(cs: &ConstraintSystem<F>) -> Self {
+        let constraints = cs
+            .gates
+            .iter()
+            .enumerate()
+            .map(|(i, gate)| (Constraint::Gate(i), gate.degree()))
+            .chain(
+                cs.permutations
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| (Constraint::Permutation(i), p.required_degree())),
+            )
+            .chain(
+                cs.lookups
+                    .iter()
+                    .enumerate()
+                    .map(|(i, l)| (Constraint::Lookup(i), l.required_degree())),
+            )
+            .collect();
+
+        DegreeReport {
+            constraints,
+            gates: cs
+                .gates
+                .iter()
+                .map(|gate| gate.display(cs).to_string())
+                .collect(),
+            degree: cs.degree(),
+        }
+    }
+
+    /// Returns each constraint along with its degree.
+    pub fn constraints(&self) -> &[(Constraint, usize)] {
+        &self.constraints
+    }
+
+    /// Returns the degree of the circuit, which is the maximum degree of all
+    /// of its constraints.
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Returns the constraints that determine the degree of the circuit.
+    pub fn bottlenecks(&self) -> Vec<Constraint> {
+        self.constraints
+            .iter()
+            .filter(|(_, degree)| *degree == self.degree)
+            .map(|(constraint, _)| *constraint)
+            .collect()
+    }
+
+    /// Returns the degree the circuit would have if every bottleneck were
+    /// lowered to the degree of the next-highest constraint.
+    pub fn lowered_degree(&self) -> usize {
+        self.constraints
+            .iter()
+            .map(|(_, degree)| *degree)
+            .filter(|degree| *degree < self.degree)
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// Returns the number of pieces the quotient polynomial is split into for a
+    /// circuit of the given degree.
+    pub fn quotient_pieces(degree: usize) -> usize {
+        degree.saturating_sub(1)
+    }
+
+    /// Returns the factor by which the extended evaluation domain is larger
+    /// than the $2^k$ size domain, for a circuit of the given degree.
+    pub fn extension_factor(degree: usize) -> usize {
+        Self::quotient_pieces(degree).max(1).next_power_of_two()
+    }
+}
+
+impl fmt::Display for DegreeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Constraint degrees:")?;
+        for (constraint, degree) in self.constraints.iter() {
+            write!(f, "- {}: {}", constraint, degree)?;
+            if *degree == self.degree {
+                write!(f, " (bottleneck)")?;
+            }
+            if let Constraint::Gate(index) = constraint {
+                write!(f, ": {}", self.gates[*index])?;
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(
+            f,
+            "Circuit degree {}: {} quotient pieces, extended domain is {}x",
+            self.degree,
+            Self::quotient_pieces(self.degree),
+            Self::extension_factor(self.degree),
+        )?;
+
+        let lowered = self.lowered_degree();
+        if lowered < self.degree {
+            writeln!(
+                f,
+                "Lowering the bottlenecks to degree {}: {} quotient pieces, extended domain is {}x",
+                lowered,
+                Self::quotient_pieces(lowered),
+                Self::extension_factor(lowered),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_degree_report() {
+    use crate::pasta::Fp;
+    use crate::plonk::{Assignment, Error};
+
+    struct TestCircuit;
+
+    impl Circuit<Fp> for TestCircuit {
+        type Config = ();
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) {
+            let a = meta.advice_column();
+            let b = meta.advice_column();
+            let s = meta.fixed_column();
+
+            meta.permutation(&[a, b]);
+
+            meta.create_gate(|meta| {
+                let a = meta.query_advice(a, 0);
+                let b = meta.query_advice(b, 0);
+                let s = meta.query_fixed(s, 0);
+
+                s * a * b
+            });
+
+            meta.create_gate(|meta| {
+                let a = meta.query_advice(a, 0);
+                let s = meta.query_fixed(s, 0);
+
+                s * a.clone() * a.clone() * a.clone() * a
+            });
+        }
+
+        fn synthesize(&self, _: &mut impl Assignment<Fp>, _: ()) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    let report = DegreeReport::measure::<Fp, TestCircuit>();
+    assert_eq!(
+        report.constraints(),
+        &[
+            (Constraint::Gate(0), 3),
+            (Constraint::Gate(1), 5),
+            (Constraint::Permutation(0), 3),
+        ]
+    );
+    assert_eq!(report.degree(), 5);
+    assert_eq!(report.bottlenecks(), vec![Constraint::Gate(1)]);
+    assert_eq!(report.lowered_degree(), 3);
+    assert!(report
+        .to_string()
+        .contains("- gate 1: 5 (bottleneck): F0[cur] * A0[cur] * A0[cur] * A0[cur] * A0[cur]\n"));
+
+    assert_eq!(DegreeReport::quotient_pieces(5), 4);
+    assert_eq!(DegreeReport::extension_factor(5), 4);
+    assert_eq!(DegreeReport::quotient_pieces(3), 2);
+    assert_eq!(DegreeReport::extension_factor(3), 2);
+}
@@ -0,0 +1,217 @@
+//! This module provides the thread pools on which the parallel parts of proof
+//! creation run.
+//!
+//! Parallel code in this crate runs on the current pool of the calling thread,
+//! which is the pool that the thread has [installed](ThreadPool::install), or
+//! else the global pool. Tasks spawned on a pool inherit it, and the threads
+//! of a pool balance the tasks between them by work stealing.
+
+use once_cell::sync::Lazy;
+use std::cell::RefCell;
+use std::sync::Arc;
+
+/// A handle to a pool of threads, on which to run the parallel parts of proof
+/// creation.
+///
+/// Cloning a handle gives another handle to the same threads.
+#[derive(Clone, Debug)]
+pub struct ThreadPool {
+    kind: Kind,
+}
+
+#[derive(Clone, Debug)]
+enum Kind {
+    Global,
+    Pool(Arc<rayon::ThreadPool>),
+    SingleThreaded,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<ThreadPool>> = const { RefCell::new(None) };
+}
+
+/// The threads of the global pool, which are spawned on first use. This is a
+/// pool of its own rather than rayon's global pool, so that work dispatched to
+/// it does not end up on the pool of a calling rayon task instead.
+static GLOBAL: Lazy<rayon::ThreadPool> = Lazy::new(|| {
+    rayon::ThreadPoolBuilder::new()
+        .thread_name(|i| format!("halo2-global-{}", i))
+        .build()
+        .expect("failed to spawn the threads of the global pool")
+});
+
+impl ThreadPool {
+    /// Returns a handle to the global pool, which is shared by every thread
+    /// that has not installed another pool. It has one thread per CPU, unless
+    /// the `RAYON_NUM_THREADS` environment variable says otherwise.
+    pub fn global() -> Self {
+        ThreadPool { kind: Kind::Global }
+    }
+
+    /// Creates a pool of `num_threads` threads of its own.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_threads` is zero, or if the threads cannot be spawned.
+    pub fn new(num_threads: usize) -> Self {
+        assert!(num_threads > 0);
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .expect("failed to spawn the threads of the pool");
+        ThreadPool {
+            kind: Kind::Pool(Arc::new(pool)),
+        }
+    }
+
+    /// Returns a handle that runs everything on the calling thread.
+    pub fn single_threaded() -> Self {
+        ThreadPool {
+            kind: Kind::SingleThreaded,
+        }
+    }
+
+    /// Returns the number of threads of the pool.
+    pub fn num_threads(&self) -> usize {
+        self.threads()
+            .map_or(1, |threads| threads.current_num_threads())
+    }
+
+    /// Returns the threads that tasks spawned on this pool run on, or `None`
+    /// in single-threaded mode.
+    fn threads(&self) -> Option<&rayon::ThreadPool> {
+        match &self.kind {
+            Kind::Global => Some(&GLOBAL),
+            Kind::Pool(pool) => Some(pool),
+            Kind::SingleThreaded => None,
+        }
+    }
+
+    /// Runs `f` on the calling thread, with this pool as the current pool of
+    /// the thread until `f` returns.
+    pub fn install<R>(&self, f: impl FnOnce() -> R) -> R {
+        struct Restore(Option<ThreadPool>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                CURRENT.with(|current| *current.borrow_mut() = previous);
+            }
+        }
+
+        let _restore = Restore(CURRENT.with(|current| current.replace(Some(self.clone()))));
+        f()
+    }
+}
+
+impl Default for ThreadPool {
+    fn default() -> Self {
+        ThreadPool::global()
+    }
+}
+
+/// Returns the current pool of the calling thread.
+fn current() -> ThreadPool {
+    CURRENT
+        .with(|current| current.borrow().clone())
+        .unwrap_or_else(ThreadPool::global)
+}
+
+/// Returns the number of threads of the current pool.
+pub(crate) fn current_num_threads() -> usize {
+    current().num_threads()
+}
+
+/// A scope in which to spawn tasks that borrow from the enclosing stack
+/// frame, created by [`scope`].
+pub(crate) struct Scope<'a, 'scope> {
+    inner: Option<&'a rayon::Scope<'scope>>,
+    pool: &'a ThreadPool,
+}
+
+impl<'a, 'scope> Scope<'a, 'scope> {
+    /// Spawns a task on the current pool, or runs it right away in
+    /// single-threaded mode.
+    pub(crate) fn spawn<F: FnOnce() + Send + 'scope>(&self, f: F) {
+        match self.inner {
+            Some(scope) => {
+                let pool = self.pool.clone();
+                scope.spawn(move |_| pool.install(f));
+            }
+            None => f(),
+        }
+    }
+}
+
+/// Runs `op` with a [`Scope`] on the current pool, and waits for all of the
+/// tasks that it spawns to complete.
+pub(crate) fn scope<'scope, OP, R>(op: OP) -> R
+where
+    OP: FnOnce(&Scope<'_, 'scope>) -> R + Send,
+    R: Send,
+{
+    let pool = current();
+    match pool.threads() {
+        // `op` runs on one of the threads, which must see the same pool as
+        // the caller.
+        Some(threads) => threads.scope(|scope| {
+            pool.install(|| {
+                op(&Scope {
+                    inner: Some(scope),
+                    pool: &pool,
+                })
+            })
+        }),
+        None => op(&Scope {
+            inner: None,
+            pool: &pool,
+        }),
+    }
+}
+
+#[test]
+fn test_install() {
+    let pool = ThreadPool::new(3);
+    assert_eq!(current_num_threads(), ThreadPool::global().num_threads());
+    pool.install(|| {
+        assert_eq!(current_num_threads(), 3);
+
+        // Tasks run on the threads of the installed pool.
+        scope(|scope| {
+            scope.spawn(|| assert_eq!(current_num_threads(), 3));
+        });
+
+        ThreadPool::single_threaded().install(|| {
+            let caller = std::thread::current().id();
+            scope(|scope| {
+                scope.spawn(move || assert_eq!(std::thread::current().id(), caller));
+            });
+        });
+        assert_eq!(current_num_threads(), 3);
+    });
+    assert_eq!(current_num_threads(), ThreadPool::global().num_threads());
+
+    // Work dispatched to the global pool from a task of another pool runs on
+    // the global pool.
+    let global = ThreadPool::global();
+    pool.install(|| {
+        scope(|scope| {
+            scope.spawn(|| {
+                global.install(|| {
+                    scope_on_global_threads();
+                    assert_eq!(current_num_threads(), global.num_threads());
+                });
+            });
+        });
+    });
+
+    fn scope_on_global_threads() {
+        scope(|scope| {
+            scope.spawn(|| {
+                let name = std::thread::current().name().map(String::from);
+                assert!(name.unwrap().starts_with("halo2-global-"));
+            });
+        });
+    }
+}
@@ -1,10 +1,9 @@
 //! This module contains implementations for the Pallas and Vesta elliptic curve
 //! groups.
 
-#[macro_use]
-mod macros;
 mod curves;
 mod fields;
+mod hashtocurve;
 
 pub mod pallas;
 pub mod vesta;
@@ -21,3 +20,9 @@ fn test_endo_consistency() {
     let a = vesta::Point::one();
     assert_eq!(a * vesta::Scalar::ZETA, a.endo());
 }
+
+#[test]
+fn test_curves() {
+    crate::arithmetic::test_curve::<Ep>();
+    crate::arithmetic::test_curve::<Eq>();
+}
@@ -0,0 +1,373 @@
+//! This module implements hashing to the Pallas and Vesta curves, following
+//! the `_XMD:BLAKE2b_SSWU_RO_` suites of [draft-irtf-cfrg-hash-to-curve]. As
+//! the simplified SWU map needs a curve $y^2 = x^3 + A'x + B'$ with
+//! $A'B' \neq 0$, it maps to the curves iso-Pallas and iso-Vesta, which are
+//! 3-isogenous to Pallas and Vesta, and the isogeny carries the points over.
+//!
+//! [draft-irtf-cfrg-hash-to-curve]: https://datatracker.ietf.org/doc/draft-irtf-cfrg-hash-to-curve/
+
+use subtle::ConstantTimeEq;
+
+use super::{Ep, Eq, Fp, Fq};
+use crate::arithmetic::{hash_to_field, sgn0, Curve, CurveAffine, FieldExt};
+
+/// The parameters of the simplified SWU map onto a curve $E'$ with equation
+/// $y^2 = x^3 + A'x + B'$, and of the isogeny of degree 3 from $E'$ to the
+/// curve $E$ being hashed to.
+#[derive(Clone, Copy, Debug)]
+struct Swu<F> {
+    /// The coefficient $A'$ of $E'$.
+    a: F,
+    /// The coefficient $B'$ of $E'$.
+    b: F,
+    /// The non-square $Z$ used by the map.
+    z: F,
+    /// The coefficients of the isogeny, which maps $(x, y)$ to
+    /// $(x_{num}(x) / x_{den}(x), y \cdot y_{num}(x) / y_{den}(x))$. These are
+    /// the coefficients of $x_{num}$, $x_{den}$, $y_{num}$ and $y_{den}$ in
+    /// turn, from the highest degree down, omitting the leading coefficients
+    /// of the denominators, which are monic.
+    isogeny: [F; 13],
+}
+
+/// iso-Pallas, with $B' = 1265$ and $Z = -13$.
+//
+// The kernel of the isogeny is generated by the point of order 3 of iso-Pallas
+// with x = 0x115468c111fb318052cfc0198fdb5ac34301a71d1ff0c7cd6a57031b4ba19471.
+// Vélu's formulas give a map to y^2 = x^3 + 5 * 3^6, which is composed with
+// (x, y) -> (x / 3^2, y / 3^3).
+const ISO_PALLAS: Swu<Fp> = Swu {
+    a: Fp::from_raw([
+        0x92bb4b0b657a014b,
+        0xb74134581a27a59f,
+        0x49be2d7258370742,
+        0x18354a2eb0ea8c9c,
+    ]),
+    b: Fp::from_raw([1265, 0, 0, 0]),
+    z: Fp::neg(&Fp::from_raw([13, 0, 0, 0])),
+    isogeny: [
+        Fp::from_raw([
+            0x775f6034aaaaaaab,
+            0x4081775473d8375b,
+            0xe38e38e38e38e38e,
+            0x0e38e38e38e38e38,
+        ]),
+        Fp::from_raw([
+            0x8cf863b02814fb76,
+            0x0f93b82ee4b99495,
+            0x267c7ffa51cf412a,
+            0x3509afd51872d88e,
+        ]),
+        Fp::from_raw([
+            0x0eb64faef37ea4f7,
+            0x380af066cfeb6d69,
+            0x98c7d7ac3d98fd13,
+            0x17329b9ec5253753,
+        ]),
+        Fp::from_raw([
+            0xeebec06955555580,
+            0x8102eea8e7b06eb6,
+            0xc71c71c71c71c71c,
+            0x1c71c71c71c71c71,
+        ]),
+        Fp::from_raw([
+            0xc47f2ab668bcd71f,
+            0x9c434ac1c96b6980,
+            0x5a607fcce0494a79,
+            0x1d572e7ddc099cff,
+        ]),
+        Fp::from_raw([
+            0x2aa3af1eae5b6604,
+            0xb4abf9fb9a1fc81c,
+            0x1d13bf2a7f22b105,
+            0x325669becaecd5d1,
+        ]),
+        Fp::from_raw([
+            0x5ad985b5e38e38e4,
+            0x7642b01ad461bad2,
+            0x4bda12f684bda12f,
+            0x1a12f684bda12f68,
+        ]),
+        Fp::from_raw([
+            0xc67c31d8140a7dbb,
+            0x07c9dc17725cca4a,
+            0x133e3ffd28e7a095,
+            0x1a84d7ea8c396c47,
+        ]),
+        Fp::from_raw([
+            0x02e2be87d225b234,
+            0x1765e924f7459378,
+            0x303216cce1db9ff1,
+            0x3fb98ff0d2ddcadd,
+        ]),
+        Fp::from_raw([
+            0x93e53ab371c71c4f,
+            0x0ac03e8e134eb3e4,
+            0x7b425ed097b425ed,
+            0x025ed097b425ed09,
+        ]),
+        Fp::from_raw([
+            0x5a28279b1d1b42ae,
+            0x5941a3a4a97aa1b3,
+            0x0790bfb3506defb6,
+            0x0c02c5bcca0e6b7f,
+        ]),
+        Fp::from_raw([
+            0x4d90ab820b12320a,
+            0xd976bbfabbc5661d,
+            0x573b3d7f7d681310,
+            0x17033d3c60c68173,
+        ]),
+        Fp::from_raw([
+            0x992d30ecfffffde5,
+            0x224698fc094cf91b,
+            0x0000000000000000,
+            0x4000000000000000,
+        ]),
+    ],
+};
+
+/// iso-Vesta, with $B' = 1265$ and $Z = -13$.
+//
+// The kernel of the isogeny is generated by the point of order 3 of iso-Vesta
+// with x = 0x1ae90dbd54bf6d1575d5c33ad251d4a6bf4c98bd6fef5204ea8f4dd1286f2e8c,
+// and the isogeny is obtained as for iso-Pallas.
+const ISO_VESTA: Swu<Fq> = Swu {
+    a: Fq::from_raw([
+        0xc515ad7242eaa6b1,
+        0x9673928c7d01b212,
+        0x81639c4d96f78773,
+        0x267f9b2ee592271a,
+    ]),
+    b: Fq::from_raw([1265, 0, 0, 0]),
+    z: Fq::neg(&Fq::from_raw([13, 0, 0, 0])),
+    isogeny: [
+        Fq::from_raw([
+            0x43cd42c800000001,
+            0x0205dd51cfa0961a,
+            0x8e38e38e38e38e39,
+            0x38e38e38e38e38e3,
+        ]),
+        Fq::from_raw([
+            0x8b95c6aaf703bcc5,
+            0x216b8861ec72bd5d,
+            0xacecf10f5f7c09a2,
+            0x1d935247b4473d17,
+        ]),
+        Fq::from_raw([
+            0xaeac67bbeb586a3d,
+            0xd59d03d23b39cb11,
+            0xed7ee4a9cdf78f8f,
+            0x18760c7f7a9ad20d,
+        ]),
+        Fq::from_raw([
+            0xfb539a6f0000002b,
+            0xe1c521a795ac8356,
+            0x1c71c71c71c71c71,
+            0x31c71c71c71c71c7,
+        ]),
+        Fq::from_raw([
+            0xb7284f7eaf21a2e9,
+            0xa3ad678129b604d3,
+            0x1454798a5b5c56b2,
+            0x0a2de485568125d5,
+        ]),
+        Fq::from_raw([
+            0xf169c187d2533465,
+            0x30cd6d53df49d235,
+            0x0c621de8b91c242a,
+            0x14735171ee542778,
+        ]),
+        Fq::from_raw([
+            0x6bef1642aaaaaaab,
+            0x5601f4709a8adcb3,
+            0xda12f684bda12f68,
+            0x12f684bda12f684b,
+        ]),
+        Fq::from_raw([
+            0x8bee58e5fb81de63,
+            0x21d910aefb03b31d,
+            0xd6767887afbe04d1,
+            0x2ec9a923da239e8b,
+        ]),
+        Fq::from_raw([
+            0x4986913ab4443034,
+            0x97a3ca5c24e9ea63,
+            0x66d1466e9de10e64,
+            0x19b0d87e16e25788,
+        ]),
+        Fq::from_raw([
+            0x8f64842c55555533,
+            0x8bc32d36fb21a6a3,
+            0x425ed097b425ed09,
+            0x1ed097b425ed097b,
+        ]),
+        Fq::from_raw([
+            0x58dfecce86b2745e,
+            0x06a767bfc35b5bac,
+            0x9e7eb64f890a820c,
+            0x2f44d6c801c1b8bf,
+        ]),
+        Fq::from_raw([
+            0xd43d449776f99d2f,
+            0x926847fb9ddd76a1,
+            0x252659ba2b546c7e,
+            0x3d59f455cafc7668,
+        ]),
+        Fq::from_raw([
+            0x8c46eb20fffffde5,
+            0x224698fc0994a8dd,
+            0x0000000000000000,
+            0x4000000000000000,
+        ]),
+    ],
+};
+
+impl<F: FieldExt> Swu<F> {
+    /// Maps `u` to a point of $E'$ with the simplified SWU map.
+    fn map_to_curve(&self, u: &F) -> (F, F) {
+        let g = |x: F| (x.square() + self.a) * x + self.b;
+
+        // 1. tv1 = inv0(Z^2 * u^4 + Z * u^2)
+        let z_u2 = self.z * u.square();
+        let tv1 = (z_u2.square() + z_u2).invert();
+        // 2. x1 = (-B / A) * (1 + tv1)
+        // 3. If tv1 == 0, set x1 = B / (Z * A)
+        let a_inv = self.a.invert().unwrap();
+        let x1 = F::conditional_select(
+            &(self.b * self.z.invert().unwrap() * a_inv),
+            &(-self.b * a_inv * (F::one() + tv1.unwrap_or(F::zero()))),
+            tv1.is_some(),
+        );
+        // 4. gx1 = x1^3 + A * x1 + B
+        let y1 = g(x1).sqrt();
+        // 5. x2 = Z * u^2 * x1
+        // 6. gx2 = x2^3 + A * x2 + B
+        let x2 = z_u2 * x1;
+        let y2 = g(x2).sqrt();
+        // 7. If is_square(gx1), set x = x1 and y = sqrt(gx1)
+        // 8. Else set x = x2 and y = sqrt(gx2)
+        let x = F::conditional_select(&x2, &x1, y1.is_some());
+        let y = F::conditional_select(
+            &y2.unwrap_or(F::zero()),
+            &y1.unwrap_or(F::zero()),
+            y1.is_some(),
+        );
+        // 9. If sgn0(u) != sgn0(y), set y = -y
+        let y = F::conditional_select(&-y, &y, sgn0(u).ct_eq(&sgn0(&y)));
+
+        (x, y)
+    }
+
+    /// Maps a point of $E'$ to $E$ with the isogeny.
+    fn isogeny<C: CurveAffine<Base = F>>(&self, x: F, y: F) -> C {
+        let iso = &self.isogeny;
+        let x_num = ((iso[0] * x + iso[1]) * x + iso[2]) * x + iso[3];
+        let x_den = (x + iso[4]) * x + iso[5];
+        let y_num = (((iso[6] * x + iso[7]) * x + iso[8]) * x + iso[9]) * y;
+        let y_den = ((x + iso[10]) * x + iso[11]) * x + iso[12];
+
+        // The denominators vanish exactly on the kernel, which is mapped to
+        // the identity.
+        let t = (x_den * y_den).invert().unwrap_or(F::zero());
+        C::from_xy(x_num * y_den * t, y_num * x_den * t).unwrap_or(C::zero())
+    }
+}
+
+/// Returns a function hashing messages to the group `C`, which must be of
+/// prime order, using the parameters `swu`.
+fn hash_to_curve<'a, C: Curve>(
+    swu: Swu<C::Base>,
+    domain_prefix: &'a str,
+) -> impl Fn(&[u8]) -> C + 'a {
+    let dst = format!("{}-{}_XMD:BLAKE2b_SSWU_RO_", domain_prefix, C::CURVE_ID);
+
+    move |message| {
+        let us = hash_to_field::<C::Base>(&dst, message);
+        // As the isogeny is a group homomorphism, the points can be added on E
+        // rather than on E'.
+        let mut res = C::zero();
+        for u in us.iter() {
+            let (x, y) = swu.map_to_curve(u);
+            res += swu.isogeny::<C::Affine>(x, y);
+        }
+        res
+    }
+}
+
+/// Returns a function hashing messages to the Pallas curve, with domain
+/// separation given by `domain_prefix`.
+pub(crate) fn hash_to_pallas<'a>(domain_prefix: &'a str) -> impl Fn(&[u8]) -> Ep + 'a {
+    hash_to_curve(ISO_PALLAS, domain_prefix)
+}
+
+/// Returns a function hashing messages to the Vesta curve, with domain
+/// separation given by `domain_prefix`.
+pub(crate) fn hash_to_vesta<'a>(domain_prefix: &'a str) -> impl Fn(&[u8]) -> Eq + 'a {
+    hash_to_curve(ISO_VESTA, domain_prefix)
+}
+
+#[cfg(test)]
+use ff::Field;
+
+#[test]
+fn test_map_to_curve() {
+    fn check<C: Curve>(swu: &Swu<C::Base>) {
+        let on_iso_curve = |(x, y): (C::Base, C::Base)| {
+            assert_eq!(y.square(), (x.square() + swu.a) * x + swu.b);
+            (x, y)
+        };
+
+        for _ in 0..10 {
+            let (x, y) = on_iso_curve(swu.map_to_curve(&C::Base::rand()));
+            assert!(!bool::from(swu.isogeny::<C::Affine>(x, y).is_zero()));
+        }
+        // Z^2 * u^4 + Z * u^2 = 0 is the exceptional case of the map.
+        on_iso_curve(swu.map_to_curve(&C::Base::zero()));
+
+        // The points of order 3 in the kernel of the isogeny have this
+        // x-coordinate, which alone determines whether the image is the
+        // identity.
+        let x = -swu.isogeny[4] * C::Base::TWO_INV;
+        assert!(bool::from(
+            swu.isogeny::<C::Affine>(x, C::Base::one()).is_zero()
+        ));
+    }
+
+    check::<Ep>(&ISO_PALLAS);
+    check::<Eq>(&ISO_VESTA);
+}
+
+#[test]
+fn test_hash_to_curve() {
+    // The map takes the square branch for the first field element and the
+    // non-square one for the second for Pallas, and the opposite for Vesta.
+    let hash = hash_to_pallas("z.cash:test");
+    let (x, y) = hash(b"Trans rights now!").to_affine().get_xy().unwrap();
+    assert_eq!(
+        format!("{:?}", x),
+        "0x1818cda31ffdc8c3ff23df3d88c26f952340257d0f187a0236695c9b640b6bd3"
+    );
+    assert_eq!(
+        format!("{:?}", y),
+        "0x01e20888510123752166a0306332e126289f6f9a2774160395f2f1efc9b1280c"
+    );
+
+    let hash = hash_to_vesta("z.cash:test");
+    let (x, y) = hash(b"hello").to_affine().get_xy().unwrap();
+    assert_eq!(
+        format!("{:?}", x),
+        "0x2e983e009cf3b86bc95f91b3411bd6cbd0a87f8c3c3dae80f3f2637084849204"
+    );
+    assert_eq!(
+        format!("{:?}", y),
+        "0x310fb8f3316d069a1fb9374bdbc0fb1391c864a5208b2a812341db7f50b2e106"
+    );
+
+    // Messages and domains are separated.
+    let hash = hash_to_pallas("z.cash:test");
+    let other = hash_to_pallas("z.cash:other");
+    assert_eq!(hash(b"hello"), hash(b"hello"));
+    assert!(hash(b"hello") != hash(b"hello!"));
+    assert!(hash(b"hello") != other(b"hello"));
+}
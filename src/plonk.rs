@@ -7,32 +7,36 @@
 
 use crate::arithmetic::CurveAffine;
 use crate::poly::{
-    multiopen, Coeff, EvaluationDomain, ExtendedLagrangeCoeff, LagrangeCoeff, Polynomial,
+    self, commitment, multiopen, Coeff, EvaluationDomain, ExtendedLagrangeCoeff, LagrangeCoeff,
+    Polynomial,
 };
 use crate::transcript::ChallengeScalar;
 
+mod aggregate;
 mod circuit;
 mod keygen;
 mod lookup;
 mod permutation;
 mod vanishing;
+mod witness;
 
 mod prover;
 mod verifier;
 
+pub use aggregate::{AggregateCircuit, AggregateProver};
 pub use circuit::*;
 pub use keygen::*;
-pub use prover::*;
 pub use verifier::*;
+pub use witness::*;
 
 /// This is a verifying key which allows for the verification of proofs for a
 /// particular circuit.
 #[derive(Debug)]
 pub struct VerifyingKey<C: CurveAffine> {
-    domain: EvaluationDomain<C::Scalar>,
-    fixed_commitments: Vec<C>,
-    permutations: Vec<permutation::VerifyingKey<C>>,
-    cs: ConstraintSystem<C::Scalar>,
+    pub(crate) domain: EvaluationDomain<C::Scalar>,
+    pub(crate) fixed_commitments: Vec<C>,
+    pub(crate) permutations: Vec<permutation::VerifyingKey<C>>,
+    pub(crate) cs: ConstraintSystem<C::Scalar>,
 }
 
 /// This is a proving key which allows for the creation of proofs for a
@@ -48,38 +52,105 @@ pub struct ProvingKey<C: CurveAffine> {
     permutations: Vec<permutation::ProvingKey<C>>,
 }
 
-/// This is an object which represents a (Turbo)PLONK proof.
+/// This is an object which represents a (Turbo)PLONK proof, where `O` is the
+/// opening proof of the polynomial commitment scheme it was created with.
 // This structure must never allow points at infinity.
 #[derive(Debug, Clone)]
-pub struct Proof<C: CurveAffine> {
-    advice_commitments: Vec<C>,
-    permutations: Vec<permutation::Proof<C>>,
-    lookups: Vec<lookup::Proof<C>>,
-    advice_evals: Vec<C::Scalar>,
-    aux_evals: Vec<C::Scalar>,
-    fixed_evals: Vec<C::Scalar>,
-    vanishing: vanishing::Proof<C>,
-    multiopening: multiopen::Proof<C>,
+pub struct Proof<C: CurveAffine, O = commitment::Proof<C>> {
+    pub(crate) circuit: CircuitProof<C>,
+    pub(crate) multiopening: multiopen::Proof<C, O>,
 }
 
-/// This is an error that could occur during proving or circuit synthesis.
-// TODO: these errors need to be cleaned up
-#[derive(Debug)]
+/// This is an object which represents several (Turbo)PLONK proofs, of
+/// possibly different circuits, that share a single multi-point opening. As
+/// for [`Proof`], `O` is the opening proof of the commitment scheme.
+// This structure must never allow points at infinity.
+#[derive(Debug, Clone)]
+pub struct AggregateProof<C: CurveAffine, O = commitment::Proof<C>> {
+    circuits: Vec<CircuitProof<C>>,
+    multiopening: multiopen::Proof<C, O>,
+}
+
+/// The parts of a proof that are specific to one circuit, which is everything
+/// but the multi-point opening.
+// This structure must never allow points at infinity.
+#[derive(Debug, Clone)]
+pub(crate) struct CircuitProof<C: CurveAffine> {
+    // The advice, permutation, lookup and aux parts of the proof are given
+    // once per circuit instance.
+    pub(crate) advice_commitments: Vec<Vec<C>>,
+    pub(crate) permutations: Vec<Vec<permutation::Proof<C>>>,
+    pub(crate) lookups: Vec<Vec<lookup::Proof<C>>>,
+    pub(crate) advice_evals: Vec<Vec<C::Scalar>>,
+    pub(crate) aux_evals: Vec<Vec<C::Scalar>>,
+    pub(crate) fixed_evals: Vec<C::Scalar>,
+    pub(crate) vanishing: vanishing::Proof<C>,
+}
+
+/// This is an error that could occur during proving, verification or circuit
+/// synthesis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Error {
     /// This is an error that can occur during synthesis of the circuit, for
     /// example, when the witness is not present.
     SynthesisError,
     /// The structured reference string or the parameters are not compatible
-    /// with the circuit being synthesized.
+    /// with the circuit being synthesized, or the aux columns passed to the
+    /// prover or verifier do not match the circuit.
     IncompatibleParams,
-    /// The constraint system is not satisfied.
+    /// The witness does not satisfy the constraint system.
     ConstraintSystemFailure,
     /// Out of bounds index passed to a backend
     BoundsFailure,
-    /// Opening error
-    OpeningError,
-    /// Transcript error
-    TranscriptError,
+    /// The proof does not have the number of elements required by the
+    /// verifying key for the given component.
+    InvalidProofLength(ProofComponent),
+    /// The proof contains a point at infinity, which cannot be absorbed into
+    /// the transcript.
+    InvalidPoint,
+    /// The prover's evaluation of h(x) does not match the constraints
+    /// evaluated at x, so the proof is not for this circuit or its witness
+    /// does not satisfy it.
+    VanishingCheckFailed,
+    /// A precommitted advice column is not committed to the expected value.
+    PrecommitmentMismatch,
+    /// The public values of the circuits of the in-circuit verifier do not
+    /// have the number of elements required by the verifying key.
+    DeferredValuesLength,
+    /// The transcript and verifier circuits of the in-circuit verifier do not
+    /// agree on a challenge or on a value squeezed from the scalar sponge.
+    DeferredChallengeMismatch,
+    /// The aux commitments absorbed by the in-circuit verifier are not the
+    /// expected ones.
+    AuxCommitmentMismatch,
+    /// Creating or verifying the multi-point opening failed.
+    Opening(poly::Error),
+}
+
+/// A component of a [`Proof`] whose length is checked against the
+/// [`VerifyingKey`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProofComponent {
+    /// The number of circuits an [`AggregateProof`] is for.
+    Circuits,
+    /// The number of circuit instances the proof is for.
+    Instances,
+    /// The commitments to the advice columns.
+    AdviceCommitments,
+    /// The evaluations of the advice queries.
+    AdviceEvals,
+    /// The evaluations of the aux queries.
+    AuxEvals,
+    /// The evaluations of the fixed queries.
+    FixedEvals,
+    /// The number of permutation arguments.
+    Permutations,
+    /// The evaluations of the permutation argument with the given index.
+    PermutationEvals(usize),
+    /// The number of lookup arguments.
+    Lookups,
+    /// The commitments to and evaluations of the pieces of h(X).
+    Vanishing,
 }
 
 impl<C: CurveAffine> ProvingKey<C> {
@@ -119,10 +190,15 @@ type ChallengeX<F> = ChallengeScalar<F, X>;
 #[test]
 fn test_proving() {
     use crate::arithmetic::{Curve, FieldExt};
+    use crate::multicore::ThreadPool;
     use crate::pasta::{EqAffine, Fp, Fq};
     use crate::poly::commitment::{Blind, Params};
     use crate::transcript::DummyHash;
     use circuit::{Advice, Column, Fixed};
+    use rand::{
+        rngs::{OsRng, StdRng},
+        SeedableRng,
+    };
     use std::marker::PhantomData;
     const K: u32 = 5;
 
@@ -131,7 +207,7 @@ fn test_proving() {
     pub struct Variable(Column<Advice>, usize);
 
     // Initialize the polynomial commitment parameters
-    let params: Params<EqAffine> = Params::new::<DummyHash<Fq>>(K);
+    let params: Params<EqAffine> = Params::new(K);
 
     struct PLONKConfig {
         a: Column<Advice>,
@@ -166,6 +242,7 @@ fn test_proving() {
         fn lookup_table(&mut self, values: &[Vec<FF>]) -> Result<(), Error>;
     }
 
+    #[derive(Clone)]
     struct MyCircuit<F: FieldExt> {
         a: Option<F>,
         lookup_tables: Vec<Vec<F>>,
@@ -464,18 +541,26 @@ fn test_proving() {
 
     for _ in 0..100 {
         // Create a proof
-        let proof = Proof::create::<DummyHash<Fq>, DummyHash<Fp>, _>(
+        let proof = Proof::create::<DummyHash<Fq>, DummyHash<Fp>, _, _, _>(
             &params,
             &pk,
-            &circuit,
-            &[pubinputs.clone()],
+            &[circuit.clone()],
+            &[&[pubinputs.clone()]],
+            &ThreadPool::global(),
+            OsRng,
         )
         .expect("proof generation should not fail");
 
-        let pubinput_slice = &[pubinput];
+        let pubinput_slice = &[&[pubinput][..]];
         let msm = params.empty_msm();
         let guard = proof
-            .verify::<DummyHash<Fq>, DummyHash<Fp>>(&params, pk.get_vk(), msm, pubinput_slice)
+            .verify::<DummyHash<Fq>, DummyHash<Fp>, _>(
+                &params,
+                pk.get_vk(),
+                msm,
+                pubinput_slice,
+                &[],
+            )
             .unwrap();
         {
             let msm = guard.clone().use_challenges();
@@ -489,7 +574,13 @@ fn test_proving() {
         let msm = guard.clone().use_challenges();
         assert!(msm.clone().eval());
         let guard = proof
-            .verify::<DummyHash<Fq>, DummyHash<Fp>>(&params, pk.get_vk(), msm, pubinput_slice)
+            .verify::<DummyHash<Fq>, DummyHash<Fp>, _>(
+                &params,
+                pk.get_vk(),
+                msm,
+                pubinput_slice,
+                &[],
+            )
             .unwrap();
         {
             let msm = guard.clone().use_challenges();
@@ -501,4 +592,169 @@ fn test_proving() {
             assert!(msm.eval());
         }
     }
+
+    // Round-trip the witness through its serialization and prove from it
+    // without the circuit.
+    let witness = Witness::synthesize(&pk, &circuit).expect("synthesis should not fail");
+    let mut witness_bytes = vec![];
+    witness.write(&mut witness_bytes).unwrap();
+    let witness = Witness::<Fp>::read(&witness_bytes[..]).unwrap();
+
+    let proof = Proof::create_from_witness::<DummyHash<Fq>, DummyHash<Fp>, _, _>(
+        &params,
+        &pk,
+        &[witness],
+        &[&[pubinputs.clone()]],
+        &ThreadPool::global(),
+        OsRng,
+    )
+    .expect("proof generation should not fail");
+    let pubinput_slice = &[&[pubinput][..]];
+    let msm = params.empty_msm();
+    let guard = proof
+        .verify::<DummyHash<Fq>, DummyHash<Fp>, _>(&params, pk.get_vk(), msm, pubinput_slice, &[])
+        .unwrap();
+    assert!(guard.use_challenges().eval());
+
+    // Two proofs can share a precommitted advice column, which survives the
+    // serialization of the witness.
+    let column = MyCircuit::<Fp>::configure(&mut ConstraintSystem::default()).a;
+    let mut witness = Witness::synthesize(&pk, &circuit).expect("synthesis should not fail");
+    let precommitment = witness
+        .precommit(&params, &pk, column, Blind(Fp::rand()))
+        .expect("precommitment should not fail");
+    let mut precommitted_bytes = vec![];
+    witness.write(&mut precommitted_bytes).unwrap();
+    let witness = Witness::<Fp>::read(&precommitted_bytes[..]).unwrap();
+    let verify_precommitted = |proof: &Proof<EqAffine>, precommitments: &[_]| {
+        proof
+            .verify::<DummyHash<Fq>, DummyHash<Fp>, _>(
+                &params,
+                pk.get_vk(),
+                params.empty_msm(),
+                pubinput_slice,
+                precommitments,
+            )
+            .map(|guard| guard.use_challenges().eval())
+    };
+    for _ in 0..2 {
+        let proof = Proof::create_from_witness::<DummyHash<Fq>, DummyHash<Fp>, _, _>(
+            &params,
+            &pk,
+            &[witness.clone()],
+            &[&[pubinputs.clone()]],
+            &ThreadPool::global(),
+            OsRng,
+        )
+        .expect("proof generation should not fail");
+        assert_eq!(
+            verify_precommitted(&proof, &[(0, column, precommitment)]),
+            Ok(true)
+        );
+        assert_eq!(
+            verify_precommitted(&proof, &[(0, column, pubinput)]),
+            Err(Error::PrecommitmentMismatch)
+        );
+        assert_eq!(
+            verify_precommitted(&proof, &[(1, column, precommitment)]),
+            Err(Error::BoundsFailure)
+        );
+    }
+    assert_eq!(
+        verify_precommitted(&proof, &[(0, column, precommitment)]),
+        Err(Error::PrecommitmentMismatch)
+    );
+
+    // Malformed proofs are rejected with the reason they are malformed.
+    let verify = |proof: &Proof<EqAffine>| {
+        proof
+            .verify::<DummyHash<Fq>, DummyHash<Fp>, _>(
+                &params,
+                pk.get_vk(),
+                params.empty_msm(),
+                pubinput_slice,
+                &[],
+            )
+            .map(|_| ())
+    };
+    let mut truncated = proof.clone();
+    truncated.circuit.advice_evals[0].pop();
+    assert_eq!(
+        verify(&truncated),
+        Err(Error::InvalidProofLength(ProofComponent::AdviceEvals))
+    );
+    let mut tampered = proof.clone();
+    tampered.circuit.advice_evals[0][0] += &Fp::one();
+    assert_eq!(verify(&tampered), Err(Error::VanishingCheckFailed));
+
+    // A witness for a different number of rows is rejected.
+    let truncated = Witness::<Fp>::read(&witness_bytes[..4]);
+    assert!(truncated.is_err());
+    witness_bytes[4] -= 1;
+    let mismatched = Witness::<Fp>::read(&witness_bytes[..]).unwrap();
+    assert!(matches!(
+        Proof::create_from_witness::<DummyHash<Fq>, DummyHash<Fp>, _, _>(
+            &params,
+            &pk,
+            &[mismatched],
+            &[&[pubinputs.clone()]],
+            &ThreadPool::global(),
+            OsRng,
+        ),
+        Err(Error::IncompatibleParams)
+    ));
+
+    // Proofs created from identically-seeded RNGs are identical.
+    let create_seeded = || {
+        Proof::create::<DummyHash<Fq>, DummyHash<Fp>, _, _, _>(
+            &params,
+            &pk,
+            &[circuit.clone()],
+            &[&[pubinputs.clone()]],
+            &ThreadPool::global(),
+            StdRng::seed_from_u64(42),
+        )
+        .expect("proof generation should not fail")
+    };
+    assert_eq!(
+        format!("{:?}", create_seeded()),
+        format!("{:?}", create_seeded())
+    );
+
+    // Several instances of the circuit can be proven together, and each
+    // instance's aux columns are bound to the proof.
+    let proof = Proof::create::<DummyHash<Fq>, DummyHash<Fp>, _, _, _>(
+        &params,
+        &pk,
+        &[circuit.clone(), circuit],
+        &[&[pubinputs.clone()], &[pubinputs.clone()]],
+        &ThreadPool::global(),
+        OsRng,
+    )
+    .expect("proof generation should not fail");
+    let verify_instances = |aux_commitments: &[&[EqAffine]]| {
+        proof
+            .verify::<DummyHash<Fq>, DummyHash<Fp>, _>(
+                &params,
+                pk.get_vk(),
+                params.empty_msm(),
+                aux_commitments,
+                &[],
+            )
+            .map(|guard| guard.use_challenges().eval())
+    };
+    assert_eq!(verify_instances(&[&[pubinput], &[pubinput]]), Ok(true));
+    assert_eq!(
+        verify_instances(&[&[pubinput]]),
+        Err(Error::InvalidProofLength(ProofComponent::Instances))
+    );
+    let mut wrong_pubinputs = pk.get_vk().get_domain().empty_lagrange();
+    wrong_pubinputs[0] = aux + Fp::one();
+    let wrong_pubinput = params
+        .commit_lagrange(&wrong_pubinputs, Blind::default())
+        .to_affine();
+    assert_ne!(
+        verify_instances(&[&[pubinput], &[wrong_pubinput]]),
+        Ok(true)
+    );
 }
@@ -0,0 +1,344 @@
+use rand::{CryptoRng, RngCore};
+
+use super::{
+    circuit::{Advice, Column},
+    prover::CircuitProver,
+    AggregateProof, Error, ProofComponent, ProvingKey, VerifyingKey, Witness,
+};
+use crate::arithmetic::CurveAffine;
+use crate::multicore::ThreadPool;
+use crate::poly::{
+    commitment::Params,
+    multiopen,
+    scheme::{CommitmentScheme, CommitmentSchemeVerifier},
+    LagrangeCoeff, Polynomial,
+};
+use crate::transcript::{Hasher, Transcript};
+
+/// A circuit that an [`AggregateProof`] is verified for: its verifying key,
+/// the commitments to the aux columns of each of its instances, and its
+/// expected precommitments.
+pub type AggregateCircuit<'a, 'b, C> = (
+    &'a VerifyingKey<C>,
+    &'a [&'a [C]],
+    &'b [(usize, Column<Advice>, C)],
+);
+
+/// The instances of one circuit queued in an [`AggregateProver`].
+#[derive(Debug)]
+struct AggregateItem<'a, C: CurveAffine, P> {
+    params: &'a P,
+    pk: &'a ProvingKey<C>,
+    witnesses: &'a [Witness<C::Scalar>],
+    aux: &'a [&'a [Polynomial<C::Scalar, LagrangeCoeff>]],
+}
+
+/// A prover that creates an [`AggregateProof`] for the instances of several
+/// circuits at once.
+///
+/// Each circuit is proven as it would be on its own, except that all of them
+/// share a transcript and contribute their queries to a single multi-point
+/// opening, so the verifier computes a single final multiexp. The circuits
+/// may have different domain sizes, as long as their parameters are all
+/// [downsized](Params::downsize) from the parameters of the opening, as
+/// checked by [`CommitmentScheme::is_downsized_from`].
+#[derive(Debug)]
+pub struct AggregateProver<'a, C: CurveAffine, P = Params<C>> {
+    params: &'a P,
+    items: Vec<AggregateItem<'a, C, P>>,
+}
+
+impl<'a, C: CurveAffine, P: CommitmentScheme<C>> AggregateProver<'a, C, P> {
+    /// Constructs a new aggregate prover, which opens every circuit's
+    /// commitments with `params`.
+    pub fn new(params: &'a P) -> Self {
+        AggregateProver {
+            params,
+            items: vec![],
+        }
+    }
+
+    /// Adds the instances of the circuit described by `pk`, given by their
+    /// `witnesses` and the aux columns in `aux`, to the proof. `params` are the
+    /// parameters `pk` was generated with.
+    pub fn add_circuit(
+        &mut self,
+        params: &'a P,
+        pk: &'a ProvingKey<C>,
+        witnesses: &'a [Witness<C::Scalar>],
+        aux: &'a [&'a [Polynomial<C::Scalar, LagrangeCoeff>]],
+    ) {
+        self.items.push(AggregateItem {
+            params,
+            pk,
+            witnesses,
+            aux,
+        })
+    }
+
+    /// Returns the number of circuits in the proof.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if no circuits have been added to the proof.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Creates the proof, sampling blinding factors from `rng`. The parallel
+    /// work runs on `pool`.
+    pub fn create<HBase: Hasher<C::Base>, HScalar: Hasher<C::Scalar>, R: RngCore + CryptoRng>(
+        self,
+        pool: &ThreadPool,
+        mut rng: R,
+    ) -> Result<AggregateProof<C, P::Proof>, Error> {
+        if self.items.is_empty() {
+            return Err(Error::IncompatibleParams);
+        }
+
+        // Commitments made with each circuit's parameters must also be
+        // commitments with the parameters of the opening.
+        for item in self.items.iter() {
+            if item.params.n() != item.pk.vk.domain.get_n()
+                || !item.params.is_downsized_from(self.params)
+            {
+                return Err(Error::IncompatibleParams);
+            }
+        }
+
+        pool.install(|| {
+            // Create a transcript for obtaining Fiat-Shamir challenges.
+            let mut transcript = Transcript::<C, HBase, HScalar>::new();
+
+            let provers = self
+                .items
+                .iter()
+                .map(|item| {
+                    CircuitProver::new(
+                        item.params,
+                        item.pk,
+                        item.witnesses,
+                        item.aux,
+                        &mut transcript,
+                        &mut rng,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let multiopening = multiopen::Proof::create(
+                self.params,
+                &mut transcript,
+                provers.iter().flat_map(|prover| prover.queries()),
+                &mut rng,
+            )
+            .map_err(Error::Opening)?;
+
+            Ok(AggregateProof {
+                circuits: provers.into_iter().map(|prover| prover.build()).collect(),
+                multiopening,
+            })
+        })
+    }
+}
+
+impl<'a, C: CurveAffine, O> AggregateProof<C, O> {
+    /// Returns a boolean indicating whether or not the proof is valid for the
+    /// given circuits, in the order they were added to the
+    /// [`AggregateProver`]. The expected precommitments of each circuit are
+    /// as in [`Proof::verify`](super::Proof::verify).
+    pub fn verify<HBase, HScalar, P>(
+        &'a self,
+        params: &'a P,
+        circuits: &[AggregateCircuit<'a, '_, C>],
+        msm: P::MSM,
+    ) -> Result<P::Guard, Error>
+    where
+        HBase: Hasher<C::Base>,
+        HScalar: Hasher<C::Scalar>,
+        P: CommitmentSchemeVerifier<'a, C, Proof = O>,
+    {
+        if circuits.len() != self.circuits.len() {
+            return Err(Error::InvalidProofLength(ProofComponent::Circuits));
+        }
+        for (vk, _, _) in circuits.iter() {
+            if vk.domain.get_n() > params.n() {
+                return Err(Error::IncompatibleParams);
+            }
+        }
+
+        // Create a transcript for obtaining Fiat-Shamir challenges.
+        let mut transcript = Transcript::<C, HBase, HScalar>::new();
+
+        let queries = self
+            .circuits
+            .iter()
+            .zip(circuits.iter())
+            .map(|(proof, &(vk, aux_commitments, precommitments))| {
+                proof.verify_circuit(vk, aux_commitments, precommitments, &mut transcript)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // We are now convinced every circuit is satisfied so long as the
+        // polynomial commitments open to the correct values.
+        self.multiopening
+            .verify(params, &mut transcript, queries.into_iter().flatten(), msm)
+            .map_err(Error::Opening)
+    }
+}
+
+#[test]
+fn test_aggregate_proof() {
+    use crate::arithmetic::{Curve, FieldExt};
+    use crate::multicore::ThreadPool;
+    use crate::pasta::{EqAffine, Fp, Fq};
+    use crate::plonk::{keygen, Advice, Assignment, Aux, Circuit, Column, ConstraintSystem, Fixed};
+    use crate::poly::commitment::Blind;
+    use crate::transcript::DummyHash;
+    use rand::rngs::OsRng;
+
+    const K: u32 = 4;
+
+    // Constrains the advice column to be constant and to equal the aux column
+    // over `2^k` rows.
+    struct MyCircuit {
+        k: u32,
+        value: Option<Fp>,
+    }
+
+    impl Circuit<Fp> for MyCircuit {
+        type Config = (Column<Advice>, Column<Aux>, Column<Fixed>);
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let a = meta.advice_column();
+            let p = meta.aux_column();
+            let f = meta.fixed_column();
+
+            meta.create_gate(|meta| {
+                let a = meta.query_advice(a, 0);
+                let p = meta.query_aux(p, 0);
+                let f = meta.query_fixed(f, 0);
+
+                f * (a - p)
+            });
+
+            meta.create_gate(|meta| {
+                let a_next = meta.query_advice(a, 1);
+                let a = meta.query_advice(a, 0);
+                let f = meta.query_fixed(f, 0);
+
+                f * (a_next - a)
+            });
+
+            (a, p, f)
+        }
+
+        fn synthesize(
+            &self,
+            cs: &mut impl Assignment<Fp>,
+            (a, _, f): Self::Config,
+        ) -> Result<(), Error> {
+            for row in 0..(1 << self.k) {
+                cs.assign_advice(a, row, || self.value.ok_or(Error::SynthesisError))?;
+                cs.assign_fixed(f, row, || Ok(Fp::one()))?;
+            }
+            Ok(())
+        }
+    }
+
+    let params: Params<EqAffine> = Params::new(K);
+    let sizes = [K - 1, K];
+    let circuit_params = sizes
+        .iter()
+        .map(|&k| params.downsize(k))
+        .collect::<Vec<_>>();
+    let pks = sizes
+        .iter()
+        .zip(circuit_params.iter())
+        .map(|(&k, params)| {
+            keygen(params, &MyCircuit { k, value: None }).expect("keygen should not fail")
+        })
+        .collect::<Vec<_>>();
+
+    let witnesses = sizes
+        .iter()
+        .zip(pks.iter())
+        .map(|(&k, pk)| {
+            vec![Witness::synthesize(
+                pk,
+                &MyCircuit {
+                    k,
+                    value: Some(Fp::from_u64(k as u64)),
+                },
+            )
+            .expect("synthesis should not fail")]
+        })
+        .collect::<Vec<_>>();
+    let aux = sizes
+        .iter()
+        .zip(pks.iter())
+        .map(|(&k, pk)| {
+            let mut aux = pk.get_vk().get_domain().empty_lagrange();
+            for value in aux.iter_mut() {
+                *value = Fp::from_u64(k as u64);
+            }
+            aux
+        })
+        .collect::<Vec<_>>();
+    let aux_commitments = aux
+        .iter()
+        .zip(circuit_params.iter())
+        .map(|(aux, params)| [params.commit_lagrange(aux, Blind::default()).to_affine()])
+        .collect::<Vec<_>>();
+    let aux = aux.into_iter().map(|aux| [aux]).collect::<Vec<_>>();
+    let aux = aux.iter().map(|aux| [&aux[..]]).collect::<Vec<_>>();
+    let aux_commitments = aux_commitments
+        .iter()
+        .map(|aux_commitments| [&aux_commitments[..]])
+        .collect::<Vec<_>>();
+
+    let mut prover = AggregateProver::new(&params);
+    for i in 0..sizes.len() {
+        prover.add_circuit(&circuit_params[i], &pks[i], &witnesses[i], &aux[i]);
+    }
+    assert_eq!(prover.len(), 2);
+    let proof = prover
+        .create::<DummyHash<Fq>, DummyHash<Fp>, _>(&ThreadPool::global(), OsRng)
+        .expect("proof generation should not fail");
+
+    let verify = |circuits: &[(&VerifyingKey<EqAffine>, &[&[EqAffine]])]| {
+        let circuits: Vec<_> = circuits
+            .iter()
+            .map(|&(vk, aux_commitments)| (vk, aux_commitments, &[][..]))
+            .collect();
+        proof
+            .verify::<DummyHash<Fq>, DummyHash<Fp>, _>(&params, &circuits, params.empty_msm())
+            .map(|guard| guard.use_challenges().eval())
+    };
+    assert_eq!(
+        verify(&[
+            (pks[0].get_vk(), &aux_commitments[0]),
+            (pks[1].get_vk(), &aux_commitments[1]),
+        ]),
+        Ok(true)
+    );
+    assert_eq!(
+        verify(&[(pks[0].get_vk(), &aux_commitments[0])]),
+        Err(Error::InvalidProofLength(ProofComponent::Circuits))
+    );
+    assert_ne!(
+        verify(&[
+            (pks[0].get_vk(), &aux_commitments[0]),
+            (pks[1].get_vk(), &aux_commitments[0]),
+        ]),
+        Ok(true)
+    );
+
+    // The circuits' parameters must be downsized from the opening's.
+    let mut prover = AggregateProver::new(&circuit_params[0]);
+    prover.add_circuit(&circuit_params[1], &pks[1], &witnesses[1], &aux[1]);
+    assert!(matches!(
+        prover.create::<DummyHash<Fq>, DummyHash<Fp>, _>(&ThreadPool::global(), OsRng),
+        Err(Error::IncompatibleParams)
+    ));
+}
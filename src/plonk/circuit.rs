@@ -1,8 +1,9 @@
 use core::cmp::max;
-use core::ops::{Add, Mul};
+use core::ops::{Add, Mul, Neg, Sub};
 use ff::Field;
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
+use std::fmt;
 
 use super::{lookup, permutation, Error};
 use crate::poly::Rotation;
@@ -276,6 +277,20 @@ impl<F> Add for Expression<F> {
     }
 }
 
+impl<F: Field> Sub for Expression<F> {
+    type Output = Expression<F>;
+    fn sub(self, rhs: Expression<F>) -> Expression<F> {
+        Expression::Sum(Box::new(self), Box::new(-rhs))
+    }
+}
+
+impl<F: Field> Neg for Expression<F> {
+    type Output = Expression<F>;
+    fn neg(self) -> Self::Output {
+        Expression::Scaled(Box::new(self), -F::one())
+    }
+}
+
 impl<F> Mul for Expression<F> {
     type Output = Expression<F>;
     fn mul(self, rhs: Expression<F>) -> Expression<F> {
@@ -290,6 +305,123 @@ impl<F> Mul<F> for Expression<F> {
     }
 }
 
+impl<F: Field> Expression<F> {
+    /// Returns a value that displays this expression in terms of the columns
+    /// and rotations it queries in `cs`, such as `A0[cur] * A1[next] - A2[prev]`.
+    ///
+    /// Advice, aux and fixed columns are named `A`, `X` and `F` followed by
+    /// their index.
+    pub fn display<'a>(&'a self, cs: &'a ConstraintSystem<F>) -> impl fmt::Display + 'a {
+        ExpressionDisplay {
+            expression: self,
+            cs,
+        }
+    }
+}
+
+struct ExpressionDisplay<'a, F> {
+    expression: &'a Expression<F>,
+    cs: &'a ConstraintSystem<F>,
+}
+
+impl<'a, F: Field> fmt::Display for ExpressionDisplay<'a, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_at(self.expression, 0, f)
+    }
+}
+
+impl<'a, F: Field> ExpressionDisplay<'a, F> {
+    /// Writes `expression`, adding parentheses if it binds less tightly than
+    /// `precedence` (0 for sums, 1 for products).
+    fn fmt_at(
+        &self,
+        expression: &Expression<F>,
+        precedence: usize,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        fn query(
+            f: &mut fmt::Formatter<'_>,
+            name: &str,
+            index: usize,
+            at: Rotation,
+        ) -> fmt::Result {
+            match at.0 {
+                0 => write!(f, "{}{}[cur]", name, index),
+                1 => write!(f, "{}{}[next]", name, index),
+                -1 => write!(f, "{}{}[prev]", name, index),
+                at if at > 0 => write!(f, "{}{}[cur+{}]", name, index, at),
+                at => write!(f, "{}{}[cur{}]", name, index, at),
+            }
+        }
+
+        match expression {
+            Expression::Fixed(index) => {
+                let (column, at) = self.cs.fixed_queries[*index];
+                query(f, "F", column.index(), at)
+            }
+            Expression::Advice(index) => {
+                let (column, at) = self.cs.advice_queries[*index];
+                query(f, "A", column.index(), at)
+            }
+            Expression::Aux(index) => {
+                let (column, at) = self.cs.aux_queries[*index];
+                query(f, "X", column.index(), at)
+            }
+            Expression::Sum(a, b) => {
+                if precedence > 0 {
+                    write!(f, "(")?;
+                }
+                self.fmt_at(a, 0, f)?;
+                match &**b {
+                    Expression::Scaled(b, scalar) if *scalar == -F::one() => {
+                        write!(f, " - ")?;
+                        self.fmt_at(b, 1, f)?;
+                    }
+                    b => {
+                        write!(f, " + ")?;
+                        self.fmt_at(b, 0, f)?;
+                    }
+                }
+                if precedence > 0 {
+                    write!(f, ")")?;
+                }
+                Ok(())
+            }
+            Expression::Product(a, b) => {
+                self.fmt_at(a, 1, f)?;
+                write!(f, " * ")?;
+                self.fmt_at(b, 1, f)
+            }
+            Expression::Scaled(a, scalar) => {
+                if *scalar == -F::one() {
+                    write!(f, "-")?;
+                    self.fmt_at(a, 1, f)
+                } else {
+                    self.fmt_at(a, 1, f)?;
+                    write!(f, " * ")?;
+                    fmt_scalar(scalar, f)
+                }
+            }
+        }
+    }
+}
+
+/// Writes small scalars (and their negations) as integers, and anything else
+/// using its `Debug` representation.
+fn fmt_scalar<F: Field>(scalar: &F, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut small = F::zero();
+    for i in 0..=16 {
+        if *scalar == small {
+            return write!(f, "{}", i);
+        }
+        if *scalar == -small {
+            return write!(f, "-{}", i);
+        }
+        small += F::one();
+    }
+    write!(f, "{:?}", scalar)
+}
+
 /// Represents an index into a vector where each entry corresponds to a distinct
 /// point that polynomials are queried at.
 #[derive(Copy, Clone, Debug)]
@@ -556,8 +688,70 @@ impl<F: Field> ConstraintSystem<F> {
         tmp
     }
 
+    /// Compute the degree of the constraint system (the maximum degree of all
+    /// constraints).
+    pub fn degree(&self) -> usize {
+        // The permutation argument will serve alongside the gates, so must be
+        // accounted for.
+        let mut degree = self
+            .permutations
+            .iter()
+            .map(|p| p.required_degree())
+            .max()
+            .unwrap_or(1);
+
+        // The lookup argument also serves alongside the gates and must be accounted
+        // for.
+        degree = std::cmp::max(
+            degree,
+            self.lookups
+                .iter()
+                .map(|l| l.required_degree())
+                .max()
+                .unwrap_or(1),
+        );
+
+        // Account for each gate to ensure our quotient polynomial is the
+        // correct degree and that our extended domain is the right size.
+        for poly in self.gates.iter() {
+            degree = std::cmp::max(degree, poly.degree());
+        }
+
+        degree
+    }
+
     fn add_rotation(&mut self, at: Rotation) {
         let len = self.rotations.len();
         self.rotations.entry(at).or_insert(PointIndex(len));
     }
 }
+
+#[test]
+fn test_expression_display() {
+    use crate::arithmetic::FieldExt;
+    use crate::pasta::Fp;
+
+    let mut meta = ConstraintSystem::<Fp>::default();
+    let a = meta.advice_column();
+    let b = meta.advice_column();
+    let c = meta.advice_column();
+    let s = meta.fixed_column();
+
+    let a_cur = meta.query_advice(a, 0);
+    let b_next = meta.query_advice(b, 1);
+    let c_prev = meta.query_advice(c, -1);
+    let c_far = meta.query_advice(c, 3);
+    let s_cur = meta.query_fixed(s, 0);
+
+    let gate = a_cur.clone() * b_next + c_prev * (-Fp::one());
+    assert_eq!(
+        gate.display(&meta).to_string(),
+        "A0[cur] * A1[next] - A2[prev]"
+    );
+
+    let gate = s_cur * (a_cur + c_far * Fp::from_u64(3));
+    assert_eq!(
+        gate.display(&meta).to_string(),
+        "F0[cur] * (A0[cur] + A2[cur+3] * 3)"
+    );
+}
@@ -6,17 +6,18 @@ use super::{
 };
 use crate::arithmetic::{Curve, CurveAffine};
 use crate::poly::{
-    commitment::{Blind, Params},
-    EvaluationDomain, LagrangeCoeff, Polynomial, Rotation,
+    commitment::Blind, scheme::CommitmentScheme, EvaluationDomain, LagrangeCoeff, Polynomial,
+    Rotation,
 };
 
 /// Generate a `ProvingKey` from an instance of `Circuit`.
-pub fn keygen<C, ConcreteCircuit>(
-    params: &Params<C>,
+pub fn keygen<C, P, ConcreteCircuit>(
+    params: &P,
     circuit: &ConcreteCircuit,
 ) -> Result<ProvingKey<C>, Error>
 where
     C: CurveAffine,
+    P: CommitmentScheme<C>,
     ConcreteCircuit: Circuit<C::Scalar>,
 {
     struct Assembly<F: Field> {
@@ -71,33 +72,9 @@ where
     let mut cs = ConstraintSystem::default();
     let config = ConcreteCircuit::configure(&mut cs);
 
-    // The permutation argument will serve alongside the gates, so must be
-    // accounted for.
-    let mut degree = cs
-        .permutations
-        .iter()
-        .map(|p| p.required_degree())
-        .max()
-        .unwrap_or(1);
-
-    // The lookup argument also serves alongside the gates and must be accounted
-    // for.
-    degree = std::cmp::max(
-        degree,
-        cs.lookups
-            .iter()
-            .map(|l| l.required_degree())
-            .max()
-            .unwrap_or(1),
-    );
-
-    // Account for each gate to ensure our quotient polynomial is the
-    // correct degree and that our extended domain is the right size.
-    for poly in cs.gates.iter() {
-        degree = std::cmp::max(degree, poly.degree());
-    }
+    let degree = cs.degree();
 
-    let domain = EvaluationDomain::new(degree as u32, params.k);
+    let domain = EvaluationDomain::new(degree as u32, params.k());
 
     let mut assembly: Assembly<C::Scalar> = Assembly {
         fixed: vec![domain.empty_lagrange(); cs.num_fixed_columns],
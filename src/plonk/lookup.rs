@@ -1,7 +1,7 @@
 use super::circuit::{Any, Column};
 use crate::arithmetic::CurveAffine;
 
-mod prover;
+pub(in crate::plonk) mod prover;
 mod verifier;
 
 #[derive(Clone, Debug)]
@@ -40,11 +40,11 @@ impl Argument {
 
 #[derive(Clone, Debug)]
 pub(crate) struct Proof<C: CurveAffine> {
-    product_commitment: C,
+    pub(crate) product_commitment: C,
     product_eval: C::Scalar,
     product_inv_eval: C::Scalar,
-    permuted_input_commitment: C,
-    permuted_table_commitment: C,
+    pub(crate) permuted_input_commitment: C,
+    pub(crate) permuted_table_commitment: C,
     permuted_input_eval: C::Scalar,
     permuted_input_inv_eval: C::Scalar,
     permuted_table_eval: C::Scalar,
@@ -4,15 +4,15 @@ use super::super::{
 };
 use super::{Argument, Proof};
 use crate::{
-    arithmetic::{eval_polynomial, parallelize, BatchInvert, Curve, CurveAffine, FieldExt},
+    arithmetic::{eval_polynomial, parallelize, BatchInvert, Curve, CurveAffine},
     poly::{
-        commitment::{Blind, Params},
-        multiopen::ProverQuery,
-        Coeff, EvaluationDomain, ExtendedLagrangeCoeff, LagrangeCoeff, Polynomial, Rotation,
+        commitment::Blind, multiopen::ProverQuery, scheme::CommitmentScheme, Coeff,
+        EvaluationDomain, ExtendedLagrangeCoeff, LagrangeCoeff, Polynomial, Rotation,
     },
     transcript::{Hasher, Transcript},
 };
 use ff::Field;
+use rand::RngCore;
 use std::{collections::BTreeMap, iter};
 
 #[derive(Debug)]
@@ -80,10 +80,12 @@ impl Argument {
         C: CurveAffine,
         HBase: Hasher<C::Base>,
         HScalar: Hasher<C::Scalar>,
+        P: CommitmentScheme<C>,
+        R: RngCore,
     >(
         &self,
         pk: &ProvingKey<C>,
-        params: &Params<C>,
+        params: &P,
         domain: &EvaluationDomain<C::Scalar>,
         theta: ChallengeTheta<C::Scalar>,
         advice_values: &'a [Polynomial<C::Scalar, LagrangeCoeff>],
@@ -93,6 +95,7 @@ impl Argument {
         fixed_cosets: &'a [Polynomial<C::Scalar, ExtendedLagrangeCoeff>],
         aux_cosets: &'a [Polynomial<C::Scalar, ExtendedLagrangeCoeff>],
         transcript: &mut Transcript<C, HBase, HScalar>,
+        mut rng: R,
     ) -> Result<Permuted<'a, C>, Error> {
         // Closure to get values of columns and compress them
         let compress_columns = |columns: &[Column<Any>]| {
@@ -121,9 +124,9 @@ impl Argument {
         };
 
         // Closure to construct commitment to column of values
-        let commit_column = |column: &Polynomial<C::Scalar, LagrangeCoeff>| {
+        let mut commit_column = |column: &Polynomial<C::Scalar, LagrangeCoeff>| {
             let poly = pk.vk.domain.lagrange_to_coeff(column.clone());
-            let blind = Blind(C::Scalar::rand());
+            let blind = Blind(C::Scalar::random(&mut rng));
             let commitment = params.commit_lagrange(&column, blind).to_affine();
             (poly, blind, commitment)
         };
@@ -151,12 +154,12 @@ impl Argument {
         // Hash permuted input commitment
         transcript
             .absorb_point(&permuted_input_commitment)
-            .map_err(|_| Error::TranscriptError)?;
+            .map_err(|_| Error::InvalidPoint)?;
 
         // Hash permuted table commitment
         transcript
             .absorb_point(&permuted_table_commitment)
-            .map_err(|_| Error::TranscriptError)?;
+            .map_err(|_| Error::InvalidPoint)?;
 
         let permuted_input_coset = pk
             .vk
@@ -197,14 +200,20 @@ impl<'a, C: CurveAffine> Permuted<'a, C> {
     /// grand product polynomial over the lookup. The grand product polynomial
     /// is used to populate the Product<C> struct. The Product<C> struct is
     /// added to the Lookup and finally returned by the method.
-    pub(in crate::plonk) fn commit_product<HBase: Hasher<C::Base>, HScalar: Hasher<C::Scalar>>(
+    pub(in crate::plonk) fn commit_product<
+        HBase: Hasher<C::Base>,
+        HScalar: Hasher<C::Scalar>,
+        P: CommitmentScheme<C>,
+        R: RngCore,
+    >(
         self,
         pk: &ProvingKey<C>,
-        params: &Params<C>,
+        params: &P,
         theta: ChallengeTheta<C::Scalar>,
         beta: ChallengeBeta<C::Scalar>,
         gamma: ChallengeGamma<C::Scalar>,
         transcript: &mut Transcript<C, HBase, HScalar>,
+        mut rng: R,
     ) -> Result<Committed<'a, C>, Error> {
         // Goal is to compute the products of fractions
         //
@@ -217,7 +226,7 @@ impl<'a, C: CurveAffine> Permuted<'a, C> {
         // s_j(X) is the jth table column in this lookup,
         // s'(X) is the compression of the permuted table columns,
         // and i is the ith row of the column.
-        let mut lookup_product = vec![C::Scalar::zero(); params.n as usize];
+        let mut lookup_product = vec![C::Scalar::zero(); params.n() as usize];
         // Denominator uses the permuted input column and permuted table column
         parallelize(&mut lookup_product, |lookup_product, start| {
             for ((lookup_product, permuted_input_value), permuted_table_value) in lookup_product
@@ -243,19 +252,19 @@ impl<'a, C: CurveAffine> Permuted<'a, C> {
                 // Compress unpermuted input columns
                 let mut input_term = C::Scalar::zero();
                 for unpermuted_input_column in self.unpermuted_input_columns.iter() {
-                    input_term *= &theta;
+                    input_term *= *theta;
                     input_term += &unpermuted_input_column[i];
                 }
 
                 // Compress unpermuted table columns
                 let mut table_term = C::Scalar::zero();
                 for unpermuted_table_column in self.unpermuted_table_columns.iter() {
-                    table_term *= &theta;
+                    table_term *= *theta;
                     table_term += &unpermuted_table_column[i];
                 }
 
-                *product *= &(input_term + &beta);
-                *product *= &(table_term + &gamma);
+                *product *= &(input_term + *beta);
+                *product *= &(table_term + *gamma);
             }
         });
 
@@ -288,7 +297,7 @@ impl<'a, C: CurveAffine> Permuted<'a, C> {
         // It can be used for debugging purposes.
         {
             // While in Lagrange basis, check that product is correctly constructed
-            let n = params.n as usize;
+            let n = params.n() as usize;
 
             // z'(X) (a'(X) + \beta) (s'(X) + \gamma)
             // - z'(\omega^{-1} X) (\theta^{m-1} a_0(X) + ... + a_{m-1}(X) + \beta) (\theta^{m-1} s_0(X) + ... + s_{m-1}(X) + \gamma)
@@ -320,7 +329,7 @@ impl<'a, C: CurveAffine> Permuted<'a, C> {
             }
         }
 
-        let product_blind = Blind(C::Scalar::rand());
+        let product_blind = Blind(C::Scalar::random(&mut rng));
         let product_commitment = params.commit_lagrange(&z, product_blind).to_affine();
         let z = pk.vk.domain.lagrange_to_coeff(z);
         let product_coset = pk
@@ -332,7 +341,7 @@ impl<'a, C: CurveAffine> Permuted<'a, C> {
         // Hash product commitment
         transcript
             .absorb_point(&product_commitment)
-            .map_err(|_| Error::TranscriptError)?;
+            .map_err(|_| Error::InvalidPoint)?;
 
         Ok(Committed::<'a, C> {
             permuted: self,
@@ -396,20 +405,20 @@ impl<'a, C: CurveAffine> Committed<'a, C> {
                         // Compress the unpermuted input columns
                         let mut input_term = C::Scalar::zero();
                         for input in permuted.unpermuted_input_cosets.iter() {
-                            input_term *= &theta;
+                            input_term *= *theta;
                             input_term += &input[i];
                         }
 
                         // Compress the unpermuted table columns
                         let mut table_term = C::Scalar::zero();
                         for table in permuted.unpermuted_table_cosets.iter() {
-                            table_term *= &theta;
+                            table_term *= *theta;
                             table_term += &table[i];
                         }
 
                         // Add \beta and \gamma offsets
-                        *right *= &(input_term + &beta);
-                        *right *= &(table_term + &gamma);
+                        *right *= &(input_term + *beta);
+                        *right *= &(table_term + *gamma);
                     }
                 });
 
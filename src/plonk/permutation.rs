@@ -7,7 +7,7 @@ use crate::{
 };
 
 pub(crate) mod keygen;
-mod prover;
+pub(in crate::plonk) mod prover;
 mod verifier;
 
 /// A permutation argument.
@@ -22,6 +22,10 @@ impl Argument {
         Argument { columns }
     }
 
+    pub(crate) fn get_columns(&self) -> &[Column<Advice>] {
+        &self.columns
+    }
+
     pub(crate) fn required_degree(&self) -> usize {
         // The permutation argument will serve alongside the gates, so must be
         // accounted for. There are constraints of degree 2 regardless of the
@@ -42,7 +46,7 @@ impl Argument {
 /// The verifying key for a single permutation argument.
 #[derive(Debug)]
 pub(crate) struct VerifyingKey<C: CurveAffine> {
-    commitments: Vec<C>,
+    pub(crate) commitments: Vec<C>,
 }
 
 /// The proving key for a single permutation argument.
@@ -55,7 +59,7 @@ pub(crate) struct ProvingKey<C: CurveAffine> {
 
 #[derive(Debug, Clone)]
 pub(crate) struct Proof<C: CurveAffine> {
-    permutation_product_commitment: C,
+    pub(crate) permutation_product_commitment: C,
     permutation_product_eval: C::Scalar,
     permutation_product_inv_eval: C::Scalar,
     permutation_evals: Vec<C::Scalar>,
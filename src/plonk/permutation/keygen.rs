@@ -4,10 +4,7 @@ use super::{Argument, ProvingKey, VerifyingKey};
 use crate::{
     arithmetic::{Curve, CurveAffine, FieldExt},
     plonk::{circuit::ConstraintSystem, Error},
-    poly::{
-        commitment::{Blind, Params},
-        EvaluationDomain, Rotation,
-    },
+    poly::{commitment::Blind, scheme::CommitmentScheme, EvaluationDomain, Rotation},
 };
 
 pub(crate) struct AssemblyHelper<C: CurveAffine> {
@@ -21,13 +18,13 @@ pub(crate) struct Assembly {
 }
 
 impl Assembly {
-    pub(crate) fn new<C: CurveAffine>(params: &Params<C>, p: &Argument) -> Self {
+    pub(crate) fn new<C: CurveAffine, P: CommitmentScheme<C>>(params: &P, p: &Argument) -> Self {
         // Initialize the copy vector to keep track of copy constraints in all
         // the permutation arguments.
         let mut columns = vec![];
         for i in 0..p.columns.len() {
             // Computes [(i, 0), (i, 1), ..., (i, n - 1)]
-            columns.push((0..params.n).map(|j| (i, j as usize)).collect());
+            columns.push((0..params.n()).map(|j| (i, j as usize)).collect());
         }
 
         // Before any equality constraints are applied, every cell in the permutation is
@@ -36,7 +33,7 @@ impl Assembly {
         Assembly {
             mapping: columns.clone(),
             aux: columns,
-            sizes: vec![vec![1usize; params.n as usize]; p.columns.len()],
+            sizes: vec![vec![1usize; params.n() as usize]; p.columns.len()],
         }
     }
 
@@ -88,8 +85,8 @@ impl Assembly {
         Ok(())
     }
 
-    pub(crate) fn build_helper<C: CurveAffine>(
-        params: &Params<C>,
+    pub(crate) fn build_helper<C: CurveAffine, P: CommitmentScheme<C>>(
+        params: &P,
         cs: &ConstraintSystem<C::Scalar>,
         domain: &EvaluationDomain<C::Scalar>,
     ) -> AssemblyHelper<C> {
@@ -103,10 +100,10 @@ impl Assembly {
             .unwrap_or_default();
 
         // Compute [omega^0, omega^1, ..., omega^{params.n - 1}]
-        let mut omega_powers = Vec::with_capacity(params.n as usize);
+        let mut omega_powers = Vec::with_capacity(params.n() as usize);
         {
             let mut cur = C::Scalar::one();
-            for _ in 0..params.n {
+            for _ in 0..params.n() {
                 omega_powers.push(cur);
                 cur *= &domain.get_omega();
             }
@@ -131,9 +128,9 @@ impl Assembly {
         AssemblyHelper { deltaomega }
     }
 
-    pub(crate) fn build_keys<C: CurveAffine>(
+    pub(crate) fn build_keys<C: CurveAffine, P: CommitmentScheme<C>>(
         self,
-        params: &Params<C>,
+        params: &P,
         domain: &EvaluationDomain<C::Scalar>,
         helper: &AssemblyHelper<C>,
         p: &Argument,
@@ -1,4 +1,5 @@
 use ff::Field;
+use rand::RngCore;
 use std::iter;
 
 use super::{Argument, Proof, ProvingKey};
@@ -6,9 +7,8 @@ use crate::{
     arithmetic::{eval_polynomial, parallelize, BatchInvert, Curve, CurveAffine, FieldExt},
     plonk::{self, ChallengeBeta, ChallengeGamma, ChallengeX, Error},
     poly::{
-        commitment::{Blind, Params},
-        multiopen::ProverQuery,
-        Coeff, ExtendedLagrangeCoeff, LagrangeCoeff, Polynomial, Rotation,
+        commitment::Blind, multiopen::ProverQuery, scheme::CommitmentScheme, Coeff,
+        ExtendedLagrangeCoeff, LagrangeCoeff, Polynomial, Rotation,
     },
     transcript::{Hasher, Transcript},
 };
@@ -39,15 +39,18 @@ impl Argument {
         C: CurveAffine,
         HBase: Hasher<C::Base>,
         HScalar: Hasher<C::Scalar>,
+        P: CommitmentScheme<C>,
+        R: RngCore,
     >(
         &self,
-        params: &Params<C>,
+        params: &P,
         pk: &plonk::ProvingKey<C>,
         pkey: &ProvingKey<C>,
         advice: &[Polynomial<C::Scalar, LagrangeCoeff>],
         beta: ChallengeBeta<C::Scalar>,
         gamma: ChallengeGamma<C::Scalar>,
         transcript: &mut Transcript<C, HBase, HScalar>,
+        mut rng: R,
     ) -> Result<Committed<C>, Error> {
         let domain = &pk.vk.domain;
 
@@ -59,7 +62,7 @@ impl Argument {
         // where p_j(X) is the jth advice column in this permutation,
         // and i is the ith row of the column.
 
-        let mut modified_advice = vec![C::Scalar::one(); params.n as usize];
+        let mut modified_advice = vec![C::Scalar::one(); params.n() as usize];
 
         // Iterate over each column of the permutation
         for (&column, permuted_column_values) in self.columns.iter().zip(pkey.permutations.iter()) {
@@ -69,7 +72,7 @@ impl Argument {
                     .zip(advice[column.index()][start..].iter())
                     .zip(permuted_column_values[start..].iter())
                 {
-                    *modified_advice *= &(*beta * permuted_advice_value + &gamma + advice_value);
+                    *modified_advice *= &(*beta * permuted_advice_value + *gamma + advice_value);
                 }
             });
         }
@@ -89,7 +92,7 @@ impl Argument {
                     .zip(advice[column.index()][start..].iter())
                 {
                     // Multiply by p_j(\omega^i) + \delta^j \omega^i \beta
-                    *modified_advice *= &(deltaomega * &beta + &gamma + advice_value);
+                    *modified_advice *= &(deltaomega * *beta + *gamma + advice_value);
                     deltaomega *= &omega;
                 }
             });
@@ -108,7 +111,7 @@ impl Argument {
         // Compute the evaluations of the permutation product polynomial
         // over our domain, starting with z[0] = 1
         let mut z = vec![C::Scalar::one()];
-        for row in 1..(params.n as usize) {
+        for row in 1..(params.n() as usize) {
             let mut tmp = z[row - 1];
 
             tmp *= &modified_advice[row];
@@ -116,7 +119,7 @@ impl Argument {
         }
         let z = domain.lagrange_from_vec(z);
 
-        let blind = Blind(C::Scalar::rand());
+        let blind = Blind(C::Scalar::random(&mut rng));
 
         let permutation_product_commitment_projective = params.commit_lagrange(&z, blind);
         let permutation_product_blind = blind;
@@ -130,7 +133,7 @@ impl Argument {
         // Hash the permutation product commitment
         transcript
             .absorb_point(&permutation_product_commitment)
-            .map_err(|_| Error::TranscriptError)?;
+            .map_err(|_| Error::InvalidPoint)?;
 
         Ok(Committed {
             permutation_product_poly,
@@ -180,7 +183,7 @@ impl<C: CurveAffine> Committed<C> {
                             .zip(advice[start..].iter())
                             .zip(permutation[start..].iter())
                         {
-                            *left *= &(*advice + &(*beta * permutation) + &gamma);
+                            *left *= &(*advice + &(*beta * permutation) + *gamma);
                         }
                     });
                 }
@@ -197,7 +200,7 @@ impl<C: CurveAffine> Committed<C> {
                         let mut beta_term =
                             current_delta * &step.pow_vartime(&[start as u64, 0, 0, 0]);
                         for (right, advice) in right.iter_mut().zip(advice[start..].iter()) {
-                            *right *= &(*advice + &beta_term + &gamma);
+                            *right *= &(*advice + &beta_term + *gamma);
                             beta_term *= &step;
                         }
                     });
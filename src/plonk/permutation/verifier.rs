@@ -4,15 +4,17 @@ use std::iter;
 use super::{Argument, Proof, VerifyingKey};
 use crate::{
     arithmetic::{CurveAffine, FieldExt},
-    plonk::{self, ChallengeBeta, ChallengeGamma, ChallengeX, Error},
+    plonk::{self, ChallengeBeta, ChallengeGamma, ChallengeX, Error, ProofComponent},
     poly::{multiopen::VerifierQuery, Rotation},
     transcript::{Hasher, Transcript},
 };
 
 impl<C: CurveAffine> Proof<C> {
-    pub(crate) fn check_lengths(&self, p: &Argument) -> Result<(), Error> {
+    pub(crate) fn check_lengths(&self, index: usize, p: &Argument) -> Result<(), Error> {
         if self.permutation_evals.len() != p.columns.len() {
-            return Err(Error::IncompatibleParams);
+            return Err(Error::InvalidProofLength(ProofComponent::PermutationEvals(
+                index,
+            )));
         }
 
         Ok(())
@@ -24,7 +26,7 @@ impl<C: CurveAffine> Proof<C> {
     ) -> Result<(), Error> {
         transcript
             .absorb_point(&self.permutation_product_commitment)
-            .map_err(|_| Error::TranscriptError)
+            .map_err(|_| Error::InvalidPoint)
     }
 
     pub(in crate::plonk) fn expressions<'a>(
@@ -52,17 +54,17 @@ impl<C: CurveAffine> Proof<C> {
                     .map(|&column| advice_evals[vk.cs.get_advice_query_index(column, 0)])
                     .zip(self.permutation_evals.iter())
                 {
-                    left *= &(advice_eval + &(*beta * permutation_eval) + &gamma);
+                    left *= &(advice_eval + &(*beta * permutation_eval) + *gamma);
                 }
 
                 let mut right = self.permutation_product_inv_eval;
-                let mut current_delta = *beta * &x;
+                let mut current_delta = *beta * *x;
                 for advice_eval in p
                     .columns
                     .iter()
                     .map(|&column| advice_evals[vk.cs.get_advice_query_index(column, 0)])
                 {
-                    right *= &(advice_eval + &current_delta + &gamma);
+                    right *= &(advice_eval + &current_delta + *gamma);
                     current_delta *= &C::Scalar::DELTA;
                 }
 
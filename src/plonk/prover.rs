@@ -1,297 +1,421 @@
 use ff::Field;
+use rand::{CryptoRng, RngCore};
 use std::iter;
 
 use super::{
-    circuit::{Advice, Assignment, Circuit, Column, ConstraintSystem, Fixed},
-    vanishing, ChallengeBeta, ChallengeGamma, ChallengeTheta, ChallengeX, ChallengeY, Error, Proof,
-    ProvingKey,
+    circuit::Circuit, lookup, permutation, vanishing, ChallengeBeta, ChallengeGamma,
+    ChallengeTheta, ChallengeX, ChallengeY, CircuitProof, Error, Proof, ProvingKey, Witness,
 };
-use crate::arithmetic::{eval_polynomial, Curve, CurveAffine, FieldExt};
+use crate::arithmetic::{eval_polynomial, Curve, CurveAffine};
+use crate::multicore::ThreadPool;
 use crate::poly::{
-    commitment::{Blind, Params},
+    commitment::Blind,
     multiopen::{self, ProverQuery},
-    LagrangeCoeff, Polynomial,
+    scheme::CommitmentScheme,
+    Coeff, ExtendedLagrangeCoeff, LagrangeCoeff, Polynomial,
 };
 use crate::transcript::{Hasher, Transcript};
 
-impl<C: CurveAffine> Proof<C> {
-    /// This creates a proof for the provided `circuit` when given the public
-    /// parameters `params` and the proving key [`ProvingKey`] that was
-    /// generated previously for the same circuit.
+impl<C: CurveAffine, O> Proof<C, O> {
+    /// This creates a proof for the provided `circuits` when given the public
+    /// parameters `params` of the commitment scheme and the proving key
+    /// [`ProvingKey`] that was generated previously for the same circuit. Each
+    /// circuit is an instance of the circuit with its own aux columns in
+    /// `aux`. Blinding factors are sampled from `rng`, and the parallel work
+    /// runs on `pool`.
     pub fn create<
         HBase: Hasher<C::Base>,
         HScalar: Hasher<C::Scalar>,
         ConcreteCircuit: Circuit<C::Scalar>,
+        R: RngCore + CryptoRng,
+        P: CommitmentScheme<C, Proof = O>,
     >(
-        params: &Params<C>,
+        params: &P,
         pk: &ProvingKey<C>,
-        circuit: &ConcreteCircuit,
-        aux: &[Polynomial<C::Scalar, LagrangeCoeff>],
+        circuits: &[ConcreteCircuit],
+        aux: &[&[Polynomial<C::Scalar, LagrangeCoeff>]],
+        pool: &ThreadPool,
+        rng: R,
     ) -> Result<Self, Error> {
-        if aux.len() != pk.vk.cs.num_aux_columns {
-            return Err(Error::IncompatibleParams);
-        }
-
-        struct WitnessCollection<F: Field> {
-            advice: Vec<Polynomial<F, LagrangeCoeff>>,
-            _marker: std::marker::PhantomData<F>,
-        }
-
-        impl<F: Field> Assignment<F> for WitnessCollection<F> {
-            fn assign_advice(
-                &mut self,
-                column: Column<Advice>,
-                row: usize,
-                to: impl FnOnce() -> Result<F, Error>,
-            ) -> Result<(), Error> {
-                *self
-                    .advice
-                    .get_mut(column.index())
-                    .and_then(|v| v.get_mut(row))
-                    .ok_or(Error::BoundsFailure)? = to()?;
-
-                Ok(())
-            }
-
-            fn assign_fixed(
-                &mut self,
-                _: Column<Fixed>,
-                _: usize,
-                _: impl FnOnce() -> Result<F, Error>,
-            ) -> Result<(), Error> {
-                // We only care about advice columns here
+        pool.install(|| {
+            let witnesses = circuits
+                .iter()
+                .map(|circuit| Witness::synthesize(pk, circuit))
+                .collect::<Result<Vec<_>, _>>()?;
+            Self::create_from_witness::<HBase, HScalar, R, P>(
+                params, pk, &witnesses, aux, pool, rng,
+            )
+        })
+    }
 
-                Ok(())
-            }
+    /// This creates a proof from [`Witness`]es that were previously obtained
+    /// by synthesizing instances of a circuit, when given the public
+    /// parameters `params` and the proving key [`ProvingKey`] for the same
+    /// circuit. Blinding factors are sampled from `rng`, and the parallel work
+    /// runs on `pool`.
+    pub fn create_from_witness<
+        HBase: Hasher<C::Base>,
+        HScalar: Hasher<C::Scalar>,
+        R: RngCore + CryptoRng,
+        P: CommitmentScheme<C, Proof = O>,
+    >(
+        params: &P,
+        pk: &ProvingKey<C>,
+        witnesses: &[Witness<C::Scalar>],
+        aux: &[&[Polynomial<C::Scalar, LagrangeCoeff>]],
+        pool: &ThreadPool,
+        mut rng: R,
+    ) -> Result<Self, Error> {
+        pool.install(|| {
+            // Create a transcript for obtaining Fiat-Shamir challenges.
+            let mut transcript = Transcript::<C, HBase, HScalar>::new();
 
-            fn copy(
-                &mut self,
-                _: usize,
-                _: usize,
-                _: usize,
-                _: usize,
-                _: usize,
-            ) -> Result<(), Error> {
-                // We only care about advice columns here
-
-                Ok(())
-            }
-        }
+            let prover = CircuitProver::new(params, pk, witnesses, aux, &mut transcript, &mut rng)?;
 
-        let domain = &pk.vk.domain;
-        let mut meta = ConstraintSystem::default();
-        let config = ConcreteCircuit::configure(&mut meta);
+            let multiopening =
+                multiopen::Proof::create(params, &mut transcript, prover.queries(), &mut rng)
+                    .map_err(Error::Opening)?;
 
-        let mut witness = WitnessCollection {
-            advice: vec![domain.empty_lagrange(); meta.num_advice_columns],
-            _marker: std::marker::PhantomData,
-        };
+            Ok(Proof {
+                circuit: prover.build(),
+                multiopening,
+            })
+        })
+    }
+}
 
-        // Synthesize the circuit to obtain the witness and other information.
-        circuit.synthesize(&mut witness, config)?;
+struct AuxSingle<'a, C: CurveAffine> {
+    values: &'a [Polynomial<C::Scalar, LagrangeCoeff>],
+    polys: Vec<Polynomial<C::Scalar, Coeff>>,
+    cosets: Vec<Polynomial<C::Scalar, ExtendedLagrangeCoeff>>,
+}
 
-        let witness = witness;
+struct AdviceSingle<C: CurveAffine> {
+    values: Vec<Polynomial<C::Scalar, LagrangeCoeff>>,
+    polys: Vec<Polynomial<C::Scalar, Coeff>>,
+    cosets: Vec<Polynomial<C::Scalar, ExtendedLagrangeCoeff>>,
+    blinds: Vec<Blind<C::Scalar>>,
+}
 
-        // Create a transcript for obtaining Fiat-Shamir challenges.
-        let mut transcript = Transcript::<C, HBase, HScalar>::new();
+/// The prover's state for the instances of one circuit, after every round of
+/// the protocol except the multi-point opening.
+pub(super) struct CircuitProver<'a, C: CurveAffine> {
+    pk: &'a ProvingKey<C>,
+    x: ChallengeX<C::Scalar>,
+    aux: Vec<AuxSingle<'a, C>>,
+    advice: Vec<AdviceSingle<C>>,
+    advice_commitments: Vec<Vec<C>>,
+    advice_evals: Vec<Vec<C::Scalar>>,
+    aux_evals: Vec<Vec<C::Scalar>>,
+    fixed_evals: Vec<C::Scalar>,
+    vanishing: vanishing::prover::Evaluated<C>,
+    permutations: Vec<Vec<permutation::prover::Evaluated<C>>>,
+    lookups: Vec<Vec<lookup::prover::Evaluated<C>>>,
+}
 
-        // Compute commitments to aux column polynomials
-        let aux_commitments_projective: Vec<_> = aux
-            .iter()
-            .map(|poly| params.commit_lagrange(poly, Blind::default()))
-            .collect();
-        let mut aux_commitments = vec![C::zero(); aux_commitments_projective.len()];
-        C::Projective::batch_to_affine(&aux_commitments_projective, &mut aux_commitments);
-        let aux_commitments = aux_commitments;
-        drop(aux_commitments_projective);
-        metrics::counter!("aux_commitments", aux_commitments.len() as u64);
-
-        for commitment in &aux_commitments {
-            transcript
-                .absorb_point(commitment)
-                .map_err(|_| Error::TranscriptError)?;
+impl<'a, C: CurveAffine> CircuitProver<'a, C> {
+    /// Runs every round of the protocol but the multi-point opening for the
+    /// instances of the circuit described by `pk`, using `transcript`.
+    pub(super) fn new<
+        HBase: Hasher<C::Base>,
+        HScalar: Hasher<C::Scalar>,
+        P: CommitmentScheme<C>,
+        R: RngCore + CryptoRng,
+    >(
+        params: &P,
+        pk: &'a ProvingKey<C>,
+        witnesses: &[Witness<C::Scalar>],
+        aux: &[&'a [Polynomial<C::Scalar, LagrangeCoeff>]],
+        transcript: &mut Transcript<C, HBase, HScalar>,
+        mut rng: R,
+    ) -> Result<Self, Error> {
+        if witnesses.is_empty() || aux.len() != witnesses.len() {
+            return Err(Error::IncompatibleParams);
+        }
+        for aux in aux.iter() {
+            if aux.len() != pk.vk.cs.num_aux_columns {
+                return Err(Error::IncompatibleParams);
+            }
         }
 
-        let aux_polys: Vec<_> = aux
-            .iter()
-            .map(|poly| {
-                let lagrange_vec = domain.lagrange_from_vec(poly.to_vec());
-                domain.lagrange_to_coeff(lagrange_vec)
-            })
-            .collect();
+        let domain = &pk.vk.domain;
+        let meta = &pk.vk.cs;
 
-        let aux_cosets: Vec<_> = meta
-            .aux_queries
+        let aux: Vec<AuxSingle<C>> = aux
             .iter()
-            .map(|&(column, at)| {
-                let poly = aux_polys[column.index()].clone();
-                domain.coeff_to_extended(poly, at)
+            .map(|aux| -> Result<AuxSingle<C>, Error> {
+                // Compute commitments to aux column polynomials
+                let aux_commitments_projective: Vec<_> = aux
+                    .iter()
+                    .map(|poly| params.commit_lagrange(poly, Blind::default()))
+                    .collect();
+                let mut aux_commitments = vec![C::zero(); aux_commitments_projective.len()];
+                C::Projective::batch_to_affine(&aux_commitments_projective, &mut aux_commitments);
+                let aux_commitments = aux_commitments;
+                drop(aux_commitments_projective);
+                // metrics::counter!("aux_commitments", aux_commitments.len() as u64);
+
+                for commitment in &aux_commitments {
+                    transcript
+                        .absorb_point(commitment)
+                        .map_err(|_| Error::InvalidPoint)?;
+                }
+
+                let aux_polys: Vec<_> = aux
+                    .iter()
+                    .map(|poly| {
+                        let lagrange_vec = domain.lagrange_from_vec(poly.to_vec());
+                        domain.lagrange_to_coeff(lagrange_vec)
+                    })
+                    .collect();
+
+                let aux_cosets: Vec<_> = meta
+                    .aux_queries
+                    .iter()
+                    .map(|&(column, at)| {
+                        let poly = aux_polys[column.index()].clone();
+                        domain.coeff_to_extended(poly, at)
+                    })
+                    .collect();
+
+                Ok(AuxSingle {
+                    values: aux,
+                    polys: aux_polys,
+                    cosets: aux_cosets,
+                })
             })
-            .collect();
-
-        // Compute commitments to advice column polynomials
-        let advice_blinds: Vec<_> = witness
-            .advice
-            .iter()
-            .map(|_| Blind(C::Scalar::rand()))
-            .collect();
-        let advice_commitments_projective: Vec<_> = witness
-            .advice
-            .iter()
-            .zip(advice_blinds.iter())
-            .map(|(poly, blind)| params.commit_lagrange(poly, *blind))
-            .collect();
-        let mut advice_commitments = vec![C::zero(); advice_commitments_projective.len()];
-        C::Projective::batch_to_affine(&advice_commitments_projective, &mut advice_commitments);
-        let advice_commitments = advice_commitments;
-        drop(advice_commitments_projective);
-        metrics::counter!("advice_commitments", advice_commitments.len() as u64);
-
-        for commitment in &advice_commitments {
-            transcript
-                .absorb_point(commitment)
-                .map_err(|_| Error::TranscriptError)?;
-        }
-
-        let advice_polys: Vec<_> = witness
-            .advice
-            .clone()
-            .into_iter()
-            .map(|poly| domain.lagrange_to_coeff(poly))
-            .collect();
+            .collect::<Result<Vec<_>, _>>()?;
 
-        let advice_cosets: Vec<_> = meta
-            .advice_queries
+        let (advice, advice_commitments): (Vec<AdviceSingle<C>>, Vec<Vec<C>>) = witnesses
             .iter()
-            .map(|&(column, at)| {
-                let poly = advice_polys[column.index()].clone();
-                domain.coeff_to_extended(poly, at)
+            .map(|witness| -> Result<_, Error> {
+                let advice = witness.advice_polys(pk)?;
+
+                // Compute commitments to advice column polynomials
+                let advice_blinds: Vec<_> = (0..advice.len())
+                    .map(|index| match witness.precommitted_blind(index) {
+                        Some(blind) => blind,
+                        None => Blind(C::Scalar::random(&mut rng)),
+                    })
+                    .collect();
+                let advice_commitments_projective: Vec<_> = advice
+                    .iter()
+                    .zip(advice_blinds.iter())
+                    .map(|(poly, blind)| params.commit_lagrange(poly, *blind))
+                    .collect();
+                let mut advice_commitments = vec![C::zero(); advice_commitments_projective.len()];
+                C::Projective::batch_to_affine(
+                    &advice_commitments_projective,
+                    &mut advice_commitments,
+                );
+                let advice_commitments = advice_commitments;
+                drop(advice_commitments_projective);
+                // metrics::counter!("advice_commitments", advice_commitments.len() as u64);
+
+                for commitment in &advice_commitments {
+                    transcript
+                        .absorb_point(commitment)
+                        .map_err(|_| Error::InvalidPoint)?;
+                }
+
+                let advice_polys: Vec<_> = advice
+                    .clone()
+                    .into_iter()
+                    .map(|poly| domain.lagrange_to_coeff(poly))
+                    .collect();
+
+                let advice_cosets: Vec<_> = meta
+                    .advice_queries
+                    .iter()
+                    .map(|&(column, at)| {
+                        let poly = advice_polys[column.index()].clone();
+                        domain.coeff_to_extended(poly, at)
+                    })
+                    .collect();
+
+                Ok((
+                    AdviceSingle {
+                        values: advice,
+                        polys: advice_polys,
+                        cosets: advice_cosets,
+                        blinds: advice_blinds,
+                    },
+                    advice_commitments,
+                ))
             })
-            .collect();
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .unzip();
 
         // Sample theta challenge for keeping lookup columns linearly independent
-        let theta = ChallengeTheta::get(&mut transcript);
+        let theta = ChallengeTheta::get(transcript);
 
         // Construct and commit to permuted values for each lookup
-        let lookups = pk
-            .vk
-            .cs
-            .lookups
+        let lookups = advice
             .iter()
-            .map(|lookup| {
-                lookup.commit_permuted(
-                    &pk,
-                    &params,
-                    &domain,
-                    theta,
-                    &witness.advice,
-                    &pk.fixed_values,
-                    &aux,
-                    &advice_cosets,
-                    &pk.fixed_cosets,
-                    &aux_cosets,
-                    &mut transcript,
-                )
+            .zip(aux.iter())
+            .map(|(advice, aux)| {
+                pk.vk
+                    .cs
+                    .lookups
+                    .iter()
+                    .map(|lookup| {
+                        lookup.commit_permuted(
+                            &pk,
+                            params,
+                            &domain,
+                            theta,
+                            &advice.values,
+                            &pk.fixed_values,
+                            aux.values,
+                            &advice.cosets,
+                            &pk.fixed_cosets,
+                            &aux.cosets,
+                            transcript,
+                            &mut rng,
+                        )
+                    })
+                    .collect::<Result<Vec<_>, _>>()
             })
             .collect::<Result<Vec<_>, _>>()?;
 
         // Sample beta challenge
-        let beta = ChallengeBeta::get(&mut transcript);
+        let beta = ChallengeBeta::get(transcript);
 
         // Sample gamma challenge
-        let gamma = ChallengeGamma::get(&mut transcript);
+        let gamma = ChallengeGamma::get(transcript);
 
         // Commit to permutations, if any.
-        let permutations = pk
-            .vk
-            .cs
-            .permutations
+        let permutations = advice
             .iter()
-            .zip(pk.permutations.iter())
-            .map(|(p, pkey)| {
-                p.commit(
-                    params,
-                    pk,
-                    pkey,
-                    &witness.advice,
-                    beta,
-                    gamma,
-                    &mut transcript,
-                )
+            .map(|advice| {
+                pk.vk
+                    .cs
+                    .permutations
+                    .iter()
+                    .zip(pk.permutations.iter())
+                    .map(|(p, pkey)| {
+                        p.commit(
+                            params,
+                            pk,
+                            pkey,
+                            &advice.values,
+                            beta,
+                            gamma,
+                            transcript,
+                            &mut rng,
+                        )
+                    })
+                    .collect::<Result<Vec<_>, _>>()
             })
             .collect::<Result<Vec<_>, _>>()?;
 
         // Construct and commit to products for each lookup
         let lookups = lookups
             .into_iter()
-            .map(|lookup| lookup.commit_product(&pk, &params, theta, beta, gamma, &mut transcript))
+            .map(|lookups| {
+                lookups
+                    .into_iter()
+                    .map(|lookup| {
+                        lookup.commit_product(&pk, params, theta, beta, gamma, transcript, &mut rng)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
             .collect::<Result<Vec<_>, _>>()?;
 
         // Obtain challenge for keeping all separate gates linearly independent
-        let y = ChallengeY::get(&mut transcript);
+        let y = ChallengeY::get(transcript);
 
         // Evaluate the h(X) polynomial's constraint system expressions for the permutation constraints, if any.
-        let (permutations, permutation_expressions): (Vec<_>, Vec<_>) = {
-            let tmp = permutations
-                .into_iter()
-                .zip(pk.vk.cs.permutations.iter())
-                .zip(pk.permutations.iter())
-                .map(|((p, argument), pkey)| {
-                    p.construct(pk, argument, pkey, &advice_cosets, beta, gamma)
-                })
-                .collect::<Result<Vec<_>, _>>()?;
-
-            tmp.into_iter().unzip()
-        };
+        let (permutations, permutation_expressions): (Vec<_>, Vec<_>) = permutations
+            .into_iter()
+            .zip(advice.iter())
+            .map(|(permutations, advice)| {
+                let tmp = permutations
+                    .into_iter()
+                    .zip(pk.vk.cs.permutations.iter())
+                    .zip(pk.permutations.iter())
+                    .map(|((p, argument), pkey)| {
+                        p.construct(pk, argument, pkey, &advice.cosets, beta, gamma)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(tmp.into_iter().unzip())
+            })
+            .collect::<Result<Vec<(Vec<_>, Vec<_>)>, Error>>()?
+            .into_iter()
+            .unzip();
 
         // Evaluate the h(X) polynomial's constraint system expressions for the lookup constraints, if any.
-        let (lookups, lookup_expressions): (Vec<_>, Vec<_>) = {
-            let tmp = lookups
-                .into_iter()
-                .map(|p| p.construct(pk, theta, beta, gamma))
-                .collect::<Result<Vec<_>, _>>()?;
+        let (lookups, lookup_expressions): (Vec<_>, Vec<_>) = lookups
+            .into_iter()
+            .map(|lookups| {
+                let tmp = lookups
+                    .into_iter()
+                    .map(|p| p.construct(pk, theta, beta, gamma))
+                    .collect::<Result<Vec<_>, _>>()?;
 
-            tmp.into_iter().unzip()
-        };
+                Ok(tmp.into_iter().unzip())
+            })
+            .collect::<Result<Vec<(Vec<_>, Vec<_>)>, Error>>()?
+            .into_iter()
+            .unzip();
 
         // Evaluate the h(X) polynomial's constraint system expressions for the constraints provided
-        let expressions = iter::empty()
-            // Custom constraints
-            .chain(meta.gates.iter().map(|poly| {
-                poly.evaluate(
-                    &|index| pk.fixed_cosets[index].clone(),
-                    &|index| advice_cosets[index].clone(),
-                    &|index| aux_cosets[index].clone(),
-                    &|a, b| a + &b,
-                    &|a, b| a * &b,
-                    &|a, scalar| a * scalar,
-                )
-            }))
-            // Permutation constraints, if any.
-            .chain(permutation_expressions.into_iter().flatten())
-            // Lookup constraints, if any.
-            .chain(lookup_expressions.into_iter().flatten());
+        let expressions = advice
+            .iter()
+            .zip(aux.iter())
+            .zip(permutation_expressions.into_iter())
+            .zip(lookup_expressions.into_iter())
+            .flat_map(
+                |(((advice, aux), permutation_expressions), lookup_expressions)| {
+                    iter::empty()
+                        // Custom constraints
+                        .chain(meta.gates.iter().map(move |poly| {
+                            poly.evaluate(
+                                &|index| pk.fixed_cosets[index].clone(),
+                                &|index| advice.cosets[index].clone(),
+                                &|index| aux.cosets[index].clone(),
+                                &|a, b| a + &b,
+                                &|a, b| a * &b,
+                                &|a, scalar| a * scalar,
+                            )
+                        }))
+                        // Permutation constraints, if any.
+                        .chain(permutation_expressions.into_iter().flatten())
+                        // Lookup constraints, if any.
+                        .chain(lookup_expressions.into_iter().flatten())
+                },
+            );
 
         // Construct the vanishing argument
         let vanishing =
-            vanishing::Argument::construct(params, domain, expressions, y, &mut transcript)?;
+            vanishing::Argument::construct(params, domain, expressions, y, transcript, &mut rng)?;
 
-        let x = ChallengeX::get(&mut transcript);
+        let x = ChallengeX::get(transcript);
 
         // Evaluate polynomials at omega^i x
-        let advice_evals: Vec<_> = meta
-            .advice_queries
+        let advice_evals: Vec<Vec<_>> = advice
             .iter()
-            .map(|&(column, at)| {
-                eval_polynomial(&advice_polys[column.index()], domain.rotate_omega(*x, at))
+            .map(|advice| {
+                meta.advice_queries
+                    .iter()
+                    .map(|&(column, at)| {
+                        eval_polynomial(&advice.polys[column.index()], domain.rotate_omega(*x, at))
+                    })
+                    .collect()
             })
             .collect();
 
-        let aux_evals: Vec<_> = meta
-            .aux_queries
+        let aux_evals: Vec<Vec<_>> = aux
             .iter()
-            .map(|&(column, at)| {
-                eval_polynomial(&aux_polys[column.index()], domain.rotate_omega(*x, at))
+            .map(|aux| {
+                meta.aux_queries
+                    .iter()
+                    .map(|&(column, at)| {
+                        eval_polynomial(&aux.polys[column.index()], domain.rotate_omega(*x, at))
+                    })
+                    .collect()
             })
             .collect();
 
@@ -306,77 +430,128 @@ impl<C: CurveAffine> Proof<C> {
         // Hash each column evaluation
         for eval in advice_evals
             .iter()
-            .chain(aux_evals.iter())
+            .flatten()
+            .chain(aux_evals.iter().flatten())
             .chain(fixed_evals.iter())
         {
             transcript.absorb_scalar(*eval);
         }
 
-        let vanishing = vanishing.evaluate(x, &mut transcript);
+        let vanishing = vanishing.evaluate(x, transcript);
 
         // Evaluate the permutations, if any, at omega^i x.
-        let permutations = permutations
+        let permutations: Vec<Vec<_>> = permutations
             .into_iter()
-            .zip(pk.permutations.iter())
-            .map(|(p, pkey)| p.evaluate(pk, pkey, x, &mut transcript))
-            .collect::<Vec<_>>();
+            .map(|permutations| {
+                permutations
+                    .into_iter()
+                    .zip(pk.permutations.iter())
+                    .map(|(p, pkey)| p.evaluate(pk, pkey, x, transcript))
+                    .collect()
+            })
+            .collect();
 
         // Evaluate the lookups, if any, at omega^i x.
-        let lookups = lookups
+        let lookups: Vec<Vec<_>> = lookups
             .into_iter()
-            .map(|p| p.evaluate(pk, x, &mut transcript))
-            .collect::<Vec<_>>();
-
-        let instances =
-            iter::empty()
-                .chain(pk.vk.cs.advice_queries.iter().enumerate().map(
-                    |(query_index, &(column, at))| ProverQuery {
-                        point: domain.rotate_omega(*x, at),
-                        poly: &advice_polys[column.index()],
-                        blind: advice_blinds[column.index()],
-                        eval: advice_evals[query_index],
-                    },
-                ))
-                .chain(pk.vk.cs.aux_queries.iter().enumerate().map(
-                    |(query_index, &(column, at))| ProverQuery {
-                        point: domain.rotate_omega(*x, at),
-                        poly: &aux_polys[column.index()],
-                        blind: Blind::default(),
-                        eval: aux_evals[query_index],
-                    },
-                ))
-                .chain(pk.vk.cs.fixed_queries.iter().enumerate().map(
-                    |(query_index, &(column, at))| ProverQuery {
-                        point: domain.rotate_omega(*x, at),
-                        poly: &pk.fixed_polys[column.index()],
-                        blind: Blind::default(),
-                        eval: fixed_evals[query_index],
-                    },
-                ))
-                // We query the h(X) polynomial at x
-                .chain(vanishing.open(x))
-                .chain(
-                    permutations
-                        .iter()
-                        .zip(pk.permutations.iter())
-                        .map(|(p, pkey)| p.open(pk, pkey, x))
-                        .into_iter()
-                        .flatten(),
-                )
-                .chain(lookups.iter().map(|p| p.open(pk, x)).into_iter().flatten());
-
-        let multiopening = multiopen::Proof::create(params, &mut transcript, instances)
-            .map_err(|_| Error::OpeningError)?;
-
-        Ok(Proof {
+            .map(|lookups| {
+                lookups
+                    .into_iter()
+                    .map(|p| p.evaluate(pk, x, transcript))
+                    .collect()
+            })
+            .collect();
+
+        Ok(CircuitProver {
+            pk,
+            x,
+            aux,
+            advice,
             advice_commitments,
-            permutations: permutations.into_iter().map(|p| p.build()).collect(),
-            lookups: lookups.into_iter().map(|p| p.build()).collect(),
             advice_evals,
-            fixed_evals,
             aux_evals,
-            vanishing: vanishing.build(),
-            multiopening,
+            fixed_evals,
+            vanishing,
+            permutations,
+            lookups,
         })
     }
+
+    /// Returns the queries to open in the multi-point opening.
+    pub(super) fn queries(&self) -> impl Iterator<Item = ProverQuery<'_, C>> + Clone {
+        let pk = self.pk;
+        let domain = &pk.vk.domain;
+        let x = self.x;
+
+        iter::empty()
+            .chain(self.advice.iter().zip(self.advice_evals.iter()).flat_map(
+                move |(advice, advice_evals)| {
+                    pk.vk.cs.advice_queries.iter().enumerate().map(
+                        move |(query_index, &(column, at))| ProverQuery {
+                            point: domain.rotate_omega(*x, at),
+                            poly: &advice.polys[column.index()],
+                            blind: advice.blinds[column.index()],
+                            eval: advice_evals[query_index],
+                        },
+                    )
+                },
+            ))
+            .chain(
+                self.aux
+                    .iter()
+                    .zip(self.aux_evals.iter())
+                    .flat_map(move |(aux, aux_evals)| {
+                        pk.vk.cs.aux_queries.iter().enumerate().map(
+                            move |(query_index, &(column, at))| ProverQuery {
+                                point: domain.rotate_omega(*x, at),
+                                poly: &aux.polys[column.index()],
+                                blind: Blind::default(),
+                                eval: aux_evals[query_index],
+                            },
+                        )
+                    }),
+            )
+            .chain(pk.vk.cs.fixed_queries.iter().enumerate().map(
+                move |(query_index, &(column, at))| ProverQuery {
+                    point: domain.rotate_omega(*x, at),
+                    poly: &pk.fixed_polys[column.index()],
+                    blind: Blind::default(),
+                    eval: self.fixed_evals[query_index],
+                },
+            ))
+            // We query the h(X) polynomial at x
+            .chain(self.vanishing.open(x))
+            .chain(self.permutations.iter().flat_map(move |permutations| {
+                permutations
+                    .iter()
+                    .zip(pk.permutations.iter())
+                    .flat_map(move |(p, pkey)| p.open(pk, pkey, x))
+            }))
+            .chain(
+                self.lookups
+                    .iter()
+                    .flat_map(move |lookups| lookups.iter().flat_map(move |p| p.open(pk, x))),
+            )
+    }
+
+    /// Builds the parts of the proof for this circuit's instances.
+    pub(super) fn build(self) -> CircuitProof<C> {
+        CircuitProof {
+            advice_commitments: self.advice_commitments,
+            permutations: self
+                .permutations
+                .into_iter()
+                .map(|permutations| permutations.into_iter().map(|p| p.build()).collect())
+                .collect(),
+            lookups: self
+                .lookups
+                .into_iter()
+                .map(|lookups| lookups.into_iter().map(|p| p.build()).collect())
+                .collect(),
+            advice_evals: self.advice_evals,
+            fixed_evals: self.fixed_evals,
+            aux_evals: self.aux_evals,
+            vanishing: self.vanishing.build(),
+        }
+    }
 }
@@ -2,7 +2,7 @@ use std::marker::PhantomData;
 
 use crate::arithmetic::CurveAffine;
 
-mod prover;
+pub(in crate::plonk) mod prover;
 mod verifier;
 
 /// A vanishing argument.
@@ -12,6 +12,6 @@ pub(crate) struct Argument<C: CurveAffine> {
 
 #[derive(Debug, Clone)]
 pub(crate) struct Proof<C: CurveAffine> {
-    h_commitments: Vec<C>,
+    pub(crate) h_commitments: Vec<C>,
     h_evals: Vec<C::Scalar>,
 }
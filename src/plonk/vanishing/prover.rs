@@ -1,14 +1,15 @@
 use super::{Argument, Proof};
 use crate::{
-    arithmetic::{eval_polynomial, Curve, CurveAffine, FieldExt},
+    arithmetic::{eval_polynomial, Curve, CurveAffine},
     plonk::{ChallengeX, ChallengeY, Error},
     poly::{
-        commitment::{Blind, Params},
-        multiopen::ProverQuery,
-        Coeff, EvaluationDomain, ExtendedLagrangeCoeff, Polynomial,
+        commitment::Blind, multiopen::ProverQuery, scheme::CommitmentScheme, Coeff,
+        EvaluationDomain, ExtendedLagrangeCoeff, Polynomial,
     },
     transcript::{Hasher, Transcript},
 };
+use ff::Field;
+use rand::RngCore;
 
 pub(in crate::plonk) struct Constructed<C: CurveAffine> {
     h_pieces: Vec<Polynomial<C::Scalar, Coeff>>,
@@ -22,12 +23,18 @@ pub(in crate::plonk) struct Evaluated<C: CurveAffine> {
 }
 
 impl<C: CurveAffine> Argument<C> {
-    pub(in crate::plonk) fn construct<HBase: Hasher<C::Base>, HScalar: Hasher<C::Scalar>>(
-        params: &Params<C>,
+    pub(in crate::plonk) fn construct<
+        HBase: Hasher<C::Base>,
+        HScalar: Hasher<C::Scalar>,
+        P: CommitmentScheme<C>,
+        R: RngCore,
+    >(
+        params: &P,
         domain: &EvaluationDomain<C::Scalar>,
         expressions: impl Iterator<Item = Polynomial<C::Scalar, ExtendedLagrangeCoeff>>,
         y: ChallengeY<C::Scalar>,
         transcript: &mut Transcript<C, HBase, HScalar>,
+        mut rng: R,
     ) -> Result<Constructed<C>, Error> {
         // Evaluate the h(X) polynomial's constraint system expressions for the constraints provided
         let h_poly = expressions.fold(domain.empty_extended(), |h_poly, v| h_poly * *y + &v);
@@ -40,11 +47,14 @@ impl<C: CurveAffine> Argument<C> {
 
         // Split h(X) up into pieces
         let h_pieces = h_poly
-            .chunks_exact(params.n as usize)
+            .chunks_exact(params.n() as usize)
             .map(|v| domain.coeff_from_vec(v.to_vec()))
             .collect::<Vec<_>>();
         drop(h_poly);
-        let h_blinds: Vec<_> = h_pieces.iter().map(|_| Blind(C::Scalar::rand())).collect();
+        let h_blinds: Vec<_> = h_pieces
+            .iter()
+            .map(|_| Blind(C::Scalar::random(&mut rng)))
+            .collect();
 
         // Compute commitments to each h(X) piece
         let h_commitments_projective: Vec<_> = h_pieces
@@ -60,7 +70,7 @@ impl<C: CurveAffine> Argument<C> {
         for c in h_commitments.iter() {
             transcript
                 .absorb_point(c)
-                .map_err(|_| Error::TranscriptError)?;
+                .map_err(|_| Error::InvalidPoint)?;
         }
 
         Ok(Constructed {
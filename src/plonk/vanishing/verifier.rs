@@ -3,19 +3,17 @@ use ff::Field;
 use super::Proof;
 use crate::{
     arithmetic::CurveAffine,
-    plonk::{ChallengeX, ChallengeY, Error, VerifyingKey},
+    plonk::{ChallengeX, ChallengeY, Error, ProofComponent, VerifyingKey},
     poly::multiopen::VerifierQuery,
     transcript::{Hasher, Transcript},
 };
 
 impl<C: CurveAffine> Proof<C> {
     pub(in crate::plonk) fn check_lengths(&self, vk: &VerifyingKey<C>) -> Result<(), Error> {
-        if self.h_commitments.len() != self.h_evals.len() {
-            return Err(Error::IncompatibleParams);
-        }
-
-        if self.h_commitments.len() != vk.domain.get_quotient_poly_degree() {
-            return Err(Error::IncompatibleParams);
+        if self.h_commitments.len() != self.h_evals.len()
+            || self.h_commitments.len() != vk.domain.get_quotient_poly_degree()
+        {
+            return Err(Error::InvalidProofLength(ProofComponent::Vanishing));
         }
 
         Ok(())
@@ -32,7 +30,7 @@ impl<C: CurveAffine> Proof<C> {
         for c in &self.h_commitments {
             transcript
                 .absorb_point(c)
-                .map_err(|_| Error::TranscriptError)?;
+                .map_err(|_| Error::InvalidPoint)?;
         }
         Ok(())
     }
@@ -43,7 +41,7 @@ impl<C: CurveAffine> Proof<C> {
         y: ChallengeY<C::Scalar>,
         xn: C::Scalar,
     ) -> Result<(), Error> {
-        let expected_h_eval = expressions.fold(C::Scalar::zero(), |h_eval, v| h_eval * &y + &v);
+        let expected_h_eval = expressions.fold(C::Scalar::zero(), |h_eval, v| h_eval * *y + &v);
 
         // Compute h(x) from the prover
         let h_eval = self
@@ -54,7 +52,7 @@ impl<C: CurveAffine> Proof<C> {
 
         // Did the prover commit to the correct polynomial?
         if expected_h_eval != (h_eval * &(xn - &C::Scalar::one())) {
-            return Err(Error::ConstraintSystemFailure);
+            return Err(Error::VanishingCheckFailed);
         }
 
         Ok(())
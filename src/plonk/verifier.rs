@@ -2,198 +2,282 @@ use ff::Field;
 use std::iter;
 
 use super::{
-    ChallengeBeta, ChallengeGamma, ChallengeTheta, ChallengeX, ChallengeY, Error, Proof,
-    VerifyingKey,
+    circuit::{Advice, Column},
+    ChallengeBeta, ChallengeGamma, ChallengeTheta, ChallengeX, ChallengeY, CircuitProof, Error,
+    Proof, ProofComponent, VerifyingKey,
 };
 use crate::arithmetic::{CurveAffine, FieldExt};
-use crate::poly::{
-    commitment::{Guard, Params, MSM},
-    multiopen::VerifierQuery,
-};
+use crate::poly::{multiopen::VerifierQuery, scheme::CommitmentSchemeVerifier};
 use crate::transcript::{Hasher, Transcript};
 
-impl<'a, C: CurveAffine> Proof<C> {
-    /// Returns a boolean indicating whether or not the proof is valid
-    pub fn verify<HBase: Hasher<C::Base>, HScalar: Hasher<C::Scalar>>(
+mod batch;
+pub use batch::BatchVerifier;
+
+impl<'a, C: CurveAffine, O> Proof<C, O> {
+    /// Returns a boolean indicating whether or not the proof is valid for the
+    /// circuit instances whose aux columns commit to `aux_commitments`.
+    ///
+    /// Each entry `(instance, column, commitment)` of `precommitments` requires
+    /// the advice `column` of the circuit instance with index `instance` to be
+    /// committed to `commitment`, for example because it was
+    /// [precommitted](super::Witness::precommit) and is shared with other
+    /// proofs.
+    ///
+    /// The final check of the opening is deferred into the returned guard, to
+    /// be completed with [`CommitmentSchemeVerifier::finalize`].
+    pub fn verify<HBase, HScalar, P>(
         &'a self,
-        params: &'a Params<C>,
+        params: &'a P,
         vk: &'a VerifyingKey<C>,
-        msm: MSM<'a, C>,
-        aux_commitments: &'a [C],
-    ) -> Result<Guard<'a, C>, Error> {
-        self.check_lengths(vk, aux_commitments)?;
-
-        // Check that aux_commitments matches the expected number of aux_columns
-        // and self.aux_evals
-        if aux_commitments.len() != vk.cs.num_aux_columns
-            || self.aux_evals.len() != vk.cs.num_aux_columns
-        {
-            return Err(Error::IncompatibleParams);
-        }
-
+        msm: P::MSM,
+        aux_commitments: &'a [&'a [C]],
+        precommitments: &[(usize, Column<Advice>, C)],
+    ) -> Result<P::Guard, Error>
+    where
+        HBase: Hasher<C::Base>,
+        HScalar: Hasher<C::Scalar>,
+        P: CommitmentSchemeVerifier<'a, C, Proof = O>,
+    {
         // Create a transcript for obtaining Fiat-Shamir challenges.
         let mut transcript = Transcript::<C, HBase, HScalar>::new();
 
+        let queries =
+            self.circuit
+                .verify_circuit(vk, aux_commitments, precommitments, &mut transcript)?;
+
+        // We are now convinced the circuit is satisfied so long as the
+        // polynomial commitments open to the correct values.
+        self.multiopening
+            .verify(params, &mut transcript, queries, msm)
+            .map_err(Error::Opening)
+    }
+}
+
+impl<'a, C: CurveAffine> CircuitProof<C> {
+    /// Runs every check of the protocol but the multi-point opening for the
+    /// circuit instances whose aux columns commit to `aux_commitments`, using
+    /// `transcript`. Returns the queries to check in the multi-point opening.
+    pub(super) fn verify_circuit<HBase: Hasher<C::Base>, HScalar: Hasher<C::Scalar>>(
+        &'a self,
+        vk: &'a VerifyingKey<C>,
+        aux_commitments: &'a [&'a [C]],
+        precommitments: &[(usize, Column<Advice>, C)],
+        transcript: &mut Transcript<C, HBase, HScalar>,
+    ) -> Result<impl Iterator<Item = VerifierQuery<'a, C>> + Clone, Error> {
+        self.check_lengths(vk, aux_commitments)?;
+        self.check_precommitments(precommitments)?;
+
         // Hash the aux (external) commitments into the transcript
-        for commitment in aux_commitments {
+        for commitment in aux_commitments.iter().flat_map(|c| c.iter()) {
             transcript
                 .absorb_point(commitment)
-                .map_err(|_| Error::TranscriptError)?;
+                .map_err(|_| Error::InvalidPoint)?;
         }
 
         // Hash the prover's advice commitments into the transcript
-        for commitment in &self.advice_commitments {
+        for commitment in self.advice_commitments.iter().flatten() {
             transcript
                 .absorb_point(commitment)
-                .map_err(|_| Error::TranscriptError)?;
+                .map_err(|_| Error::InvalidPoint)?;
         }
 
         // Sample theta challenge for keeping lookup columns linearly independent
-        let theta = ChallengeTheta::get(&mut transcript);
+        let theta = ChallengeTheta::get(transcript);
 
         // Hash each lookup permuted commitment
-        for lookup in &self.lookups {
-            lookup.absorb_permuted_commitments(&mut transcript)?;
+        for lookup in self.lookups.iter().flatten() {
+            lookup.absorb_permuted_commitments(transcript)?;
         }
 
         // Sample beta challenge
-        let beta = ChallengeBeta::get(&mut transcript);
+        let beta = ChallengeBeta::get(transcript);
 
         // Sample gamma challenge
-        let gamma = ChallengeGamma::get(&mut transcript);
+        let gamma = ChallengeGamma::get(transcript);
 
         // Hash each permutation product commitment
-        for permutation in &self.permutations {
-            permutation.absorb_commitments(&mut transcript)?;
+        for permutation in self.permutations.iter().flatten() {
+            permutation.absorb_commitments(transcript)?;
         }
 
         // Hash each lookup product commitment
-        for lookup in &self.lookups {
-            lookup.absorb_product_commitment(&mut transcript)?;
+        for lookup in self.lookups.iter().flatten() {
+            lookup.absorb_product_commitment(transcript)?;
         }
 
         // Sample y challenge, which keeps the gates linearly independent.
-        let y = ChallengeY::get(&mut transcript);
+        let y = ChallengeY::get(transcript);
 
-        self.vanishing.absorb_commitments(&mut transcript)?;
+        self.vanishing.absorb_commitments(transcript)?;
 
         // Sample x challenge, which is used to ensure the circuit is
         // satisfied with high probability.
-        let x = ChallengeX::get(&mut transcript);
+        let x = ChallengeX::get(transcript);
 
         // This check ensures the circuit is satisfied so long as the polynomial
         // commitments open to the correct values.
-        self.check_hx(params, vk, theta, beta, gamma, y, x)?;
+        self.check_hx(vk, theta, beta, gamma, y, x)?;
 
-        for eval in self
-            .advice_evals
-            .iter()
-            .chain(self.aux_evals.iter())
-            .chain(self.fixed_evals.iter())
-            .chain(self.vanishing.evals())
-            .chain(
-                self.permutations
-                    .iter()
-                    .map(|p| p.evals())
-                    .into_iter()
-                    .flatten(),
-            )
-            .chain(self.lookups.iter().map(|p| p.evals()).into_iter().flatten())
-        {
+        for eval in self.evals() {
             transcript.absorb_scalar(*eval);
         }
 
         let queries =
             iter::empty()
-                .chain(vk.cs.advice_queries.iter().enumerate().map(
-                    |(query_index, &(column, at))| VerifierQuery {
-                        point: vk.domain.rotate_omega(*x, at),
-                        commitment: &self.advice_commitments[column.index()],
-                        eval: self.advice_evals[query_index],
-                    },
-                ))
                 .chain(
-                    vk.cs
-                        .aux_queries
+                    self.advice_commitments
                         .iter()
-                        .enumerate()
-                        .map(|(query_index, &(column, at))| VerifierQuery {
-                            point: vk.domain.rotate_omega(*x, at),
-                            commitment: &aux_commitments[column.index()],
-                            eval: self.aux_evals[query_index],
+                        .zip(self.advice_evals.iter())
+                        .flat_map(move |(advice_commitments, advice_evals)| {
+                            vk.cs.advice_queries.iter().enumerate().map(
+                                move |(query_index, &(column, at))| VerifierQuery {
+                                    point: vk.domain.rotate_omega(*x, at),
+                                    commitment: &advice_commitments[column.index()],
+                                    eval: advice_evals[query_index],
+                                },
+                            )
                         }),
                 )
+                .chain(aux_commitments.iter().zip(self.aux_evals.iter()).flat_map(
+                    move |(aux_commitments, aux_evals)| {
+                        vk.cs.aux_queries.iter().enumerate().map(
+                            move |(query_index, &(column, at))| VerifierQuery {
+                                point: vk.domain.rotate_omega(*x, at),
+                                commitment: &aux_commitments[column.index()],
+                                eval: aux_evals[query_index],
+                            },
+                        )
+                    },
+                ))
                 .chain(vk.cs.fixed_queries.iter().enumerate().map(
-                    |(query_index, &(column, at))| VerifierQuery {
+                    move |(query_index, &(column, at))| VerifierQuery {
                         point: vk.domain.rotate_omega(*x, at),
                         commitment: &vk.fixed_commitments[column.index()],
                         eval: self.fixed_evals[query_index],
                     },
                 ))
-                .chain(self.vanishing.queries(x));
+                .chain(self.vanishing.queries(x))
+                .chain(self.permutations.iter().flat_map(move |permutations| {
+                    permutations
+                        .iter()
+                        .zip(vk.permutations.iter())
+                        .flat_map(move |(p, vkey)| p.queries(vk, vkey, x))
+                }))
+                .chain(
+                    self.lookups.iter().flat_map(move |lookups| {
+                        lookups.iter().flat_map(move |p| p.queries(vk, x))
+                    }),
+                );
 
-        // We are now convinced the circuit is satisfied so long as the
-        // polynomial commitments open to the correct values.
-        self.multiopening
-            .verify(
-                params,
-                &mut transcript,
-                queries
-                    .chain(
-                        self.permutations
-                            .iter()
-                            .zip(vk.permutations.iter())
-                            .map(|(p, vkey)| p.queries(vk, vkey, x))
-                            .into_iter()
-                            .flatten(),
-                    )
-                    .chain(
-                        self.lookups
-                            .iter()
-                            .map(|p| p.queries(vk, x))
-                            .into_iter()
-                            .flatten(),
-                    ),
-                msm,
-            )
-            .map_err(|_| Error::OpeningError)
+        Ok(queries)
+    }
+
+    /// Returns the evaluations of the proof, in the order they are absorbed
+    /// into the transcript.
+    pub(crate) fn evals(&self) -> impl Iterator<Item = &C::Scalar> {
+        self.advice_evals
+            .iter()
+            .flatten()
+            .chain(self.aux_evals.iter().flatten())
+            .chain(self.fixed_evals.iter())
+            .chain(self.vanishing.evals())
+            .chain(self.permutations.iter().flatten().flat_map(|p| p.evals()))
+            .chain(self.lookups.iter().flatten().flat_map(|p| p.evals()))
+    }
+
+    /// Checks that each precommitted advice column is committed to the
+    /// expected value.
+    fn check_precommitments(
+        &self,
+        precommitments: &[(usize, Column<Advice>, C)],
+    ) -> Result<(), Error> {
+        for (instance, column, commitment) in precommitments {
+            let advice_commitment = self
+                .advice_commitments
+                .get(*instance)
+                .and_then(|advice_commitments| advice_commitments.get(column.index()))
+                .ok_or(Error::BoundsFailure)?;
+
+            if advice_commitment != commitment {
+                return Err(Error::PrecommitmentMismatch);
+            }
+        }
+
+        Ok(())
     }
 
     /// Checks that the lengths of vectors are consistent with the constraint
     /// system
-    fn check_lengths(&self, vk: &VerifyingKey<C>, aux_commitments: &[C]) -> Result<(), Error> {
+    pub(crate) fn check_lengths(
+        &self,
+        vk: &VerifyingKey<C>,
+        aux_commitments: &[&[C]],
+    ) -> Result<(), Error> {
         // Check that aux_commitments matches the expected number of aux_columns
-        // and self.aux_evals
-        if aux_commitments.len() != vk.cs.num_aux_columns
-            || self.aux_evals.len() != vk.cs.num_aux_columns
-        {
+        // for every instance
+        if aux_commitments.is_empty() {
             return Err(Error::IncompatibleParams);
         }
+        for aux_commitments in aux_commitments {
+            if aux_commitments.len() != vk.cs.num_aux_columns {
+                return Err(Error::IncompatibleParams);
+            }
+        }
 
-        if self.fixed_evals.len() != vk.cs.fixed_queries.len() {
-            return Err(Error::IncompatibleParams);
+        // Check that the proof has one of each per-instance part for every
+        // instance
+        let num_instances = aux_commitments.len();
+        if self.advice_commitments.len() != num_instances
+            || self.advice_evals.len() != num_instances
+            || self.aux_evals.len() != num_instances
+            || self.permutations.len() != num_instances
+            || self.lookups.len() != num_instances
+        {
+            return Err(Error::InvalidProofLength(ProofComponent::Instances));
         }
 
-        if self.advice_evals.len() != vk.cs.advice_queries.len() {
-            return Err(Error::IncompatibleParams);
+        for aux_evals in &self.aux_evals {
+            if aux_evals.len() != vk.cs.aux_queries.len() {
+                return Err(Error::InvalidProofLength(ProofComponent::AuxEvals));
+            }
         }
 
-        if self.permutations.len() != vk.cs.permutations.len() {
-            return Err(Error::IncompatibleParams);
+        if self.fixed_evals.len() != vk.cs.fixed_queries.len() {
+            return Err(Error::InvalidProofLength(ProofComponent::FixedEvals));
+        }
+
+        for advice_evals in &self.advice_evals {
+            if advice_evals.len() != vk.cs.advice_queries.len() {
+                return Err(Error::InvalidProofLength(ProofComponent::AdviceEvals));
+            }
         }
 
-        for (permutation, p) in self.permutations.iter().zip(vk.cs.permutations.iter()) {
-            permutation.check_lengths(p)?;
+        for permutations in &self.permutations {
+            if permutations.len() != vk.cs.permutations.len() {
+                return Err(Error::InvalidProofLength(ProofComponent::Permutations));
+            }
+
+            for (index, (permutation, p)) in permutations
+                .iter()
+                .zip(vk.cs.permutations.iter())
+                .enumerate()
+            {
+                permutation.check_lengths(index, p)?;
+            }
         }
 
         self.vanishing.check_lengths(vk)?;
 
-        if self.lookups.len() != vk.cs.lookups.len() {
-            return Err(Error::IncompatibleParams);
+        for lookups in &self.lookups {
+            if lookups.len() != vk.cs.lookups.len() {
+                return Err(Error::InvalidProofLength(ProofComponent::Lookups));
+            }
         }
 
-        if self.advice_commitments.len() != vk.cs.num_advice_columns {
-            return Err(Error::IncompatibleParams);
+        for advice_commitments in &self.advice_commitments {
+            if advice_commitments.len() != vk.cs.num_advice_columns {
+                return Err(Error::InvalidProofLength(ProofComponent::AdviceCommitments));
+            }
         }
 
         Ok(())
@@ -203,7 +287,6 @@ impl<'a, C: CurveAffine> Proof<C> {
     /// rules are satisfied.
     fn check_hx(
         &self,
-        params: &'a Params<C>,
         vk: &VerifyingKey<C>,
         theta: ChallengeTheta<C::Scalar>,
         beta: ChallengeBeta<C::Scalar>,
@@ -212,7 +295,7 @@ impl<'a, C: CurveAffine> Proof<C> {
         x: ChallengeX<C::Scalar>,
     ) -> Result<(), Error> {
         // x^n
-        let xn = x.pow(&[params.n as u64, 0, 0, 0]);
+        let xn = x.pow(&[vk.domain.get_n(), 0, 0, 0]);
 
         // TODO: bubble this error up
         // l_0(x)
@@ -220,49 +303,48 @@ impl<'a, C: CurveAffine> Proof<C> {
             * &(xn - &C::Scalar::one()) // (x^n - 1) / (x - 1)
             * &vk.domain.get_barycentric_weight(); // l_0(x)
 
-        // Compute the expected value of h(x)
-        let expressions = std::iter::empty()
-            // Evaluate the circuit using the custom gates provided
-            .chain(vk.cs.gates.iter().map(|poly| {
-                poly.evaluate(
-                    &|index| self.fixed_evals[index],
-                    &|index| self.advice_evals[index],
-                    &|index| self.aux_evals[index],
-                    &|a, b| a + &b,
-                    &|a, b| a * &b,
-                    &|a, scalar| a * &scalar,
-                )
-            }))
-            .chain(
-                self.permutations
-                    .iter()
-                    .zip(vk.cs.permutations.iter())
-                    .map(|(p, argument)| {
-                        p.expressions(vk, argument, &self.advice_evals, l_0, beta, gamma, x)
-                    })
-                    .into_iter()
-                    .flatten(),
-            )
-            .chain(
-                self.lookups
-                    .iter()
-                    .zip(vk.cs.lookups.iter())
-                    .map(|(p, argument)| {
-                        p.expressions(
-                            vk,
-                            l_0,
-                            argument,
-                            theta,
-                            beta,
-                            gamma,
-                            &self.advice_evals,
-                            &self.fixed_evals,
-                            &self.aux_evals,
+        // Compute the expected value of h(x), with each instance's constraints
+        // in turn
+        let expressions = self
+            .advice_evals
+            .iter()
+            .zip(self.aux_evals.iter())
+            .zip(self.permutations.iter())
+            .zip(self.lookups.iter())
+            .flat_map(|(((advice_evals, aux_evals), permutations), lookups)| {
+                iter::empty()
+                    // Evaluate the circuit using the custom gates provided
+                    .chain(vk.cs.gates.iter().map(move |poly| {
+                        poly.evaluate(
+                            &|index| self.fixed_evals[index],
+                            &|index| advice_evals[index],
+                            &|index| aux_evals[index],
+                            &|a, b| a + &b,
+                            &|a, b| a * &b,
+                            &|a, scalar| a * &scalar,
                         )
-                    })
-                    .into_iter()
-                    .flatten(),
-            );
+                    }))
+                    .chain(permutations.iter().zip(vk.cs.permutations.iter()).flat_map(
+                        move |(p, argument)| {
+                            p.expressions(vk, argument, advice_evals, l_0, beta, gamma, x)
+                        },
+                    ))
+                    .chain(lookups.iter().zip(vk.cs.lookups.iter()).flat_map(
+                        move |(p, argument)| {
+                            p.expressions(
+                                vk,
+                                l_0,
+                                argument,
+                                theta,
+                                beta,
+                                gamma,
+                                advice_evals,
+                                &self.fixed_evals,
+                                aux_evals,
+                            )
+                        },
+                    ))
+            });
 
         self.vanishing.verify(expressions, y, xn)
     }
@@ -0,0 +1,232 @@
+use ff::Field;
+use rand::{CryptoRng, RngCore};
+
+use super::super::{Advice, Column, Error, Proof, VerifyingKey};
+use crate::arithmetic::CurveAffine;
+use crate::poly::{
+    self, commitment,
+    scheme::{CommitmentSchemeVerifier, SchemeMSM},
+};
+use crate::transcript::Hasher;
+
+/// A proof queued in a [`BatchVerifier`].
+#[derive(Debug)]
+struct BatchItem<'a, C: CurveAffine, O> {
+    vk: &'a VerifyingKey<C>,
+    proof: &'a Proof<C, O>,
+    aux_commitments: &'a [&'a [C]],
+    precommitments: &'a [(usize, Column<Advice>, C)],
+}
+
+/// A verifier that checks many proofs at once.
+///
+/// Each proof is verified up to its final multiscalar multiplication, which is
+/// deferred. The deferred MSMs of all proofs are scaled by random factors and
+/// added together, so that the whole batch is checked with a single multiexp.
+/// The proofs may be for different circuits, but must share the same
+/// parameters of the commitment scheme, whose opening proof is `O`.
+#[derive(Debug, Default)]
+pub struct BatchVerifier<'a, C: CurveAffine, O = commitment::Proof<C>> {
+    items: Vec<BatchItem<'a, C, O>>,
+}
+
+impl<'a, C: CurveAffine, O> BatchVerifier<'a, C, O> {
+    /// Constructs a new, empty batch verifier.
+    pub fn new() -> Self {
+        BatchVerifier { items: vec![] }
+    }
+
+    /// Adds a proof for instances of the circuit described by `vk`, with the
+    /// given aux commitments for each instance and expected precommitments as
+    /// in [`Proof::verify`], to the batch.
+    pub fn add_proof(
+        &mut self,
+        vk: &'a VerifyingKey<C>,
+        proof: &'a Proof<C, O>,
+        aux_commitments: &'a [&'a [C]],
+        precommitments: &'a [(usize, Column<Advice>, C)],
+    ) {
+        self.items.push(BatchItem {
+            vk,
+            proof,
+            aux_commitments,
+            precommitments,
+        })
+    }
+
+    /// Returns the number of proofs in the batch.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if no proofs have been added to the batch.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Verifies every proof in the batch, sampling the random factors used to
+    /// combine them from `rng`.
+    ///
+    /// If the batch is invalid, each deferred MSM is checked on its own, and
+    /// the index (in the order the proofs were added) of every invalid proof
+    /// is returned along with the reason it is invalid.
+    pub fn finalize<HBase, HScalar, R, P>(
+        self,
+        params: &'a P,
+        mut rng: R,
+    ) -> Result<(), Vec<(usize, Error)>>
+    where
+        HBase: Hasher<C::Base>,
+        HScalar: Hasher<C::Scalar>,
+        R: RngCore + CryptoRng,
+        P: CommitmentSchemeVerifier<'a, C, Proof = O>,
+    {
+        let mut failures = vec![];
+        let mut msms = vec![];
+        for (index, item) in self.items.iter().enumerate() {
+            match item.proof.verify::<HBase, HScalar, P>(
+                params,
+                item.vk,
+                params.empty_msm(),
+                item.aux_commitments,
+                item.precommitments,
+            ) {
+                Ok(guard) => msms.push((index, P::finalize(guard))),
+                Err(error) => failures.push((index, error)),
+            }
+        }
+
+        let mut batch = params.empty_msm();
+        for (_, msm) in msms.iter() {
+            let mut msm = msm.clone();
+            msm.scale(C::Scalar::random(&mut rng));
+            batch.add_msm(&msm);
+        }
+
+        if !batch.eval() {
+            // Fall back to checking each proof individually to find the
+            // invalid ones.
+            failures.extend(msms.into_iter().filter_map(|(index, msm)| {
+                if msm.eval() {
+                    None
+                } else {
+                    Some((index, Error::Opening(poly::Error::OpeningFailed)))
+                }
+            }));
+            failures.sort_by_key(|(index, _)| *index);
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+}
+
+#[test]
+fn test_batch_verifier() {
+    use crate::arithmetic::FieldExt;
+    use crate::multicore::ThreadPool;
+    use crate::pasta::{EqAffine, Fp, Fq};
+    use crate::plonk::{keygen, Advice, Assignment, Circuit, Column, ConstraintSystem, Fixed};
+    use crate::transcript::DummyHash;
+    use rand::rngs::OsRng;
+
+    const K: u32 = 3;
+
+    // Constrains the advice column to equal the fixed column, which is filled
+    // with `value`.
+    #[derive(Clone)]
+    struct MyCircuit {
+        value: Fp,
+    }
+
+    impl Circuit<Fp> for MyCircuit {
+        type Config = (Column<Advice>, Column<Fixed>);
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let a = meta.advice_column();
+            let f = meta.fixed_column();
+
+            meta.create_gate(|meta| {
+                let a = meta.query_advice(a, 0);
+                let f = meta.query_fixed(f, 0);
+
+                f.clone() * a.clone() * (a + f * -Fp::one())
+            });
+
+            (a, f)
+        }
+
+        fn synthesize(
+            &self,
+            cs: &mut impl Assignment<Fp>,
+            (a, f): Self::Config,
+        ) -> Result<(), Error> {
+            for row in 0..(1 << K) {
+                cs.assign_advice(a, row, || Ok(self.value))?;
+                cs.assign_fixed(f, row, || Ok(self.value))?;
+            }
+            Ok(())
+        }
+    }
+
+    let params: commitment::Params<EqAffine> = commitment::Params::new(K);
+    let circuits = [
+        MyCircuit {
+            value: Fp::from_u64(2),
+        },
+        MyCircuit {
+            value: Fp::from_u64(3),
+        },
+    ];
+    let pks = circuits
+        .iter()
+        .map(|circuit| keygen(&params, circuit).expect("keygen should not fail"))
+        .collect::<Vec<_>>();
+    let proofs = circuits
+        .iter()
+        .zip(pks.iter())
+        .map(|(circuit, pk)| {
+            Proof::create::<DummyHash<Fq>, DummyHash<Fp>, _, _, _>(
+                &params,
+                pk,
+                &[circuit.clone()],
+                &[&[]],
+                &ThreadPool::global(),
+                OsRng,
+            )
+            .expect("proof generation should not fail")
+        })
+        .collect::<Vec<_>>();
+
+    // Proofs of different circuits can be verified together.
+    let mut batch = BatchVerifier::new();
+    for _ in 0..3 {
+        batch.add_proof(pks[0].get_vk(), &proofs[0], &[&[]], &[]);
+        batch.add_proof(pks[1].get_vk(), &proofs[1], &[&[]], &[]);
+    }
+    assert_eq!(batch.len(), 6);
+    assert_eq!(
+        batch.finalize::<DummyHash<Fq>, DummyHash<Fp>, _, _>(&params, OsRng),
+        Ok(())
+    );
+
+    // A proof checked against the wrong fixed commitments only fails in the
+    // deferred MSM, and a proof with a tampered evaluation fails before it.
+    let mut tampered = proofs[0].clone();
+    tampered.circuit.advice_evals[0][0] += &Fp::one();
+    let mut batch = BatchVerifier::new();
+    batch.add_proof(pks[0].get_vk(), &proofs[0], &[&[]], &[]);
+    batch.add_proof(pks[1].get_vk(), &proofs[0], &[&[]], &[]);
+    batch.add_proof(pks[1].get_vk(), &proofs[1], &[&[]], &[]);
+    batch.add_proof(pks[0].get_vk(), &tampered, &[&[]], &[]);
+    assert_eq!(
+        batch.finalize::<DummyHash<Fq>, DummyHash<Fp>, _, _>(&params, OsRng),
+        Err(vec![
+            (1, Error::Opening(poly::Error::OpeningFailed)),
+            (3, Error::VanishingCheckFailed),
+        ])
+    );
+}
@@ -0,0 +1,221 @@
+use ff::Field;
+use std::io;
+
+use super::{
+    circuit::{Advice, Assignment, Circuit, Column, ConstraintSystem, Fixed},
+    Error, ProvingKey,
+};
+use crate::arithmetic::{Curve, CurveAffine, FieldExt};
+use crate::poly::{commitment::Blind, scheme::CommitmentScheme, LagrangeCoeff, Polynomial};
+
+/// The advice assignment produced by synthesizing a circuit.
+///
+/// A witness can be serialized with [`Witness::write`] and later passed to
+/// [`Proof::create_from_witness`](super::Proof::create_from_witness), which
+/// creates a proof without needing the circuit that produced the witness.
+///
+/// Advice columns that were committed to ahead of time can be marked with
+/// [`Witness::precommit`], so that proofs created from the witness commit to
+/// them with the same blinding factor.
+#[derive(Debug, Clone)]
+pub struct Witness<F: FieldExt> {
+    advice: Vec<Vec<F>>,
+    precommitted: Vec<Option<Blind<F>>>,
+}
+
+struct WitnessCollection<F: Field> {
+    advice: Vec<Vec<F>>,
+}
+
+impl<F: Field> Assignment<F> for WitnessCollection<F> {
+    fn assign_advice(
+        &mut self,
+        column: Column<Advice>,
+        row: usize,
+        to: impl FnOnce() -> Result<F, Error>,
+    ) -> Result<(), Error> {
+        *self
+            .advice
+            .get_mut(column.index())
+            .and_then(|v| v.get_mut(row))
+            .ok_or(Error::BoundsFailure)? = to()?;
+
+        Ok(())
+    }
+
+    fn assign_fixed(
+        &mut self,
+        _: Column<Fixed>,
+        _: usize,
+        _: impl FnOnce() -> Result<F, Error>,
+    ) -> Result<(), Error> {
+        // We only care about advice columns here
+
+        Ok(())
+    }
+
+    fn copy(&mut self, _: usize, _: usize, _: usize, _: usize, _: usize) -> Result<(), Error> {
+        // We only care about advice columns here
+
+        Ok(())
+    }
+}
+
+impl<F: FieldExt> Witness<F> {
+    /// Synthesizes `circuit` to obtain its advice assignment for the circuit
+    /// described by the proving key `pk`.
+    pub fn synthesize<C: CurveAffine<Scalar = F>, ConcreteCircuit: Circuit<F>>(
+        pk: &ProvingKey<C>,
+        circuit: &ConcreteCircuit,
+    ) -> Result<Self, Error> {
+        let domain = &pk.vk.domain;
+        let mut meta = ConstraintSystem::default();
+        let config = ConcreteCircuit::configure(&mut meta);
+
+        let mut witness = WitnessCollection {
+            advice: vec![vec![F::zero(); domain.get_n() as usize]; meta.num_advice_columns],
+        };
+
+        // Synthesize the circuit to obtain the witness and other information.
+        circuit.synthesize(&mut witness, config)?;
+
+        Ok(Witness {
+            precommitted: vec![None; witness.advice.len()],
+            advice: witness.advice,
+        })
+    }
+
+    /// Writes this witness to a buffer.
+    ///
+    /// The encoding is the number of advice columns and the number of rows,
+    /// each as a little-endian `u32`. Then, for each column, a byte that is `1`
+    /// if the column is precommitted and `0` otherwise, followed in the first
+    /// case by the 32-byte encoding of its blinding factor. Last is the 32-byte
+    /// encoding of every cell in column-major order.
+    pub fn write<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        let rows = self.advice.first().map(|column| column.len()).unwrap_or(0);
+
+        writer.write_all(&(self.advice.len() as u32).to_le_bytes())?;
+        writer.write_all(&(rows as u32).to_le_bytes())?;
+        for blind in self.precommitted.iter() {
+            match blind {
+                Some(blind) => {
+                    writer.write_all(&[1])?;
+                    writer.write_all(&blind.0.to_bytes())?;
+                }
+                None => writer.write_all(&[0])?,
+            }
+        }
+        for column in self.advice.iter() {
+            for value in column.iter() {
+                writer.write_all(&value.to_bytes())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads a witness written by [`Witness::write`].
+    ///
+    /// Whether the witness matches a circuit is checked when it is used to
+    /// create a proof.
+    pub fn read<R: io::Read>(mut reader: R) -> io::Result<Self> {
+        let mut read_u32 = || -> io::Result<usize> {
+            let mut bytes = [0u8; 4];
+            reader.read_exact(&mut bytes)?;
+            Ok(u32::from_le_bytes(bytes) as usize)
+        };
+        let columns = read_u32()?;
+        let rows = read_u32()?;
+
+        fn read_field<F: FieldExt>(reader: &mut impl io::Read) -> io::Result<F> {
+            let mut bytes = [0u8; 32];
+            reader.read_exact(&mut bytes)?;
+            Option::from(F::from_bytes(&bytes))
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid field element"))
+        }
+
+        let precommitted = (0..columns)
+            .map(|_| {
+                let mut flag = [0u8; 1];
+                reader.read_exact(&mut flag)?;
+                match flag[0] {
+                    0 => Ok(None),
+                    1 => Ok(Some(Blind(read_field(&mut reader)?))),
+                    _ => Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "invalid precommitment flag",
+                    )),
+                }
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let advice = (0..columns)
+            .map(|_| (0..rows).map(|_| read_field(&mut reader)).collect())
+            .collect::<io::Result<Vec<Vec<_>>>>()?;
+
+        Ok(Witness {
+            advice,
+            precommitted,
+        })
+    }
+
+    /// Marks the advice `column` as precommitted with the blinding factor
+    /// `blind`, and returns the commitment to it with `params`. Every proof
+    /// created from this witness for the circuit described by `pk` commits to
+    /// the column with `blind`, so its advice commitment is the returned one,
+    /// which the verifier can require by passing it to
+    /// [`Proof::verify`](super::Proof::verify).
+    ///
+    /// Each proof opens the column at its own challenge points, so the
+    /// evaluations revealed by all of the proofs that use a commitment should
+    /// be considered together.
+    pub fn precommit<C: CurveAffine<Scalar = F>, P: CommitmentScheme<C>>(
+        &mut self,
+        params: &P,
+        pk: &ProvingKey<C>,
+        column: Column<Advice>,
+        blind: Blind<F>,
+    ) -> Result<C, Error> {
+        let values = self
+            .advice
+            .get(column.index())
+            .ok_or(Error::BoundsFailure)?;
+        if values.len() != pk.vk.domain.get_n() as usize || params.n() != pk.vk.domain.get_n() {
+            return Err(Error::IncompatibleParams);
+        }
+
+        let poly = pk.vk.domain.lagrange_from_vec(values.clone());
+        self.precommitted[column.index()] = Some(blind);
+        Ok(params.commit_lagrange(&poly, blind).to_affine())
+    }
+
+    /// Returns the blinding factor that the advice column with the given index
+    /// was precommitted with, if any.
+    pub(crate) fn precommitted_blind(&self, index: usize) -> Option<Blind<F>> {
+        self.precommitted.get(index).copied().flatten()
+    }
+
+    /// Returns the advice columns as polynomials over the domain of `pk`, or
+    /// an error if this witness has the wrong shape for the circuit.
+    pub(crate) fn advice_polys<C: CurveAffine<Scalar = F>>(
+        &self,
+        pk: &ProvingKey<C>,
+    ) -> Result<Vec<Polynomial<F, LagrangeCoeff>>, Error> {
+        let domain = &pk.vk.domain;
+        if self.advice.len() != pk.vk.cs.num_advice_columns
+            || self
+                .advice
+                .iter()
+                .any(|column| column.len() != domain.get_n() as usize)
+        {
+            return Err(Error::IncompatibleParams);
+        }
+
+        Ok(self
+            .advice
+            .iter()
+            .map(|column| domain.lagrange_from_vec(column.clone()))
+            .collect())
+    }
+}
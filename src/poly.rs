@@ -12,15 +12,26 @@ use std::ops::{Add, Deref, DerefMut, Index, IndexMut, Mul, RangeFrom, RangeFull,
 pub mod commitment;
 mod domain;
 pub mod multiopen;
+pub mod scheme;
 
 pub use domain::*;
 
-/// This is an error that could occur during proving or circuit synthesis.
-// TODO: these errors need to be cleaned up
-#[derive(Debug)]
+/// This is an error that could occur while creating or verifying an opening
+/// proof.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Error {
-    /// OpeningProof is not well-formed
-    OpeningError,
+    /// The opening proof does not have the number of elements required by the
+    /// parameters and the queries being opened.
+    InvalidProofLength,
+    /// The opening proof contains a point at infinity, which cannot be
+    /// absorbed into the transcript.
+    InvalidPoint,
+    /// The verifier sampled a challenge that the prover could not have
+    /// responded to, so the opening proof is malformed.
+    InvalidChallenge,
+    /// The final multiscalar multiplication of an opening proof did not
+    /// evaluate to zero.
+    OpeningFailed,
     /// Caller needs to re-sample a point
     SamplingError,
 }
@@ -3,19 +3,28 @@
 //!
 //! [halo]: https://eprint.iacr.org/2019/1021
 
-use super::{Coeff, LagrangeCoeff, Polynomial};
-use crate::arithmetic::{best_fft, best_multiexp, parallelize, Curve, CurveAffine, FieldExt};
-use crate::transcript::Hasher;
+use super::{
+    scheme::{CommitmentScheme, CommitmentSchemeVerifier, SchemeMSM},
+    Coeff, Error, LagrangeCoeff, Polynomial,
+};
+use crate::arithmetic::{
+    best_fft, best_multiexp, parallelize, Curve, CurveAffine, FieldExt, FixedBaseTable,
+};
+use crate::transcript::{Hasher, Transcript};
 
 use ff::{Field, PrimeField};
+use rand::{CryptoRng, RngCore};
 use std::ops::{Add, AddAssign, Mul, MulAssign};
 
+mod accumulator;
 mod msm;
 mod prover;
 mod verifier;
 
+pub use accumulator::{Accumulator, FoldedAccumulator};
 pub use msm::MSM;
-pub use verifier::{Accumulator, Guard};
+pub(crate) use verifier::compute_s;
+pub use verifier::Guard;
 
 /// These are the public parameters for the polynomial commitment scheme.
 #[derive(Debug)]
@@ -25,21 +34,23 @@ pub struct Params<C: CurveAffine> {
     pub(crate) g: Vec<C>,
     pub(crate) g_lagrange: Vec<C>,
     pub(crate) h: C,
+    pub(crate) g_table: Option<FixedBaseTable<C>>,
+    pub(crate) g_lagrange_table: Option<FixedBaseTable<C>>,
 }
 
 /// This is a proof object for the polynomial commitment scheme opening.
 #[derive(Debug, Clone)]
 pub struct Proof<C: CurveAffine> {
-    rounds: Vec<(C, C)>,
-    delta: C,
-    z1: C::Scalar,
-    z2: C::Scalar,
+    pub(crate) rounds: Vec<(C, C)>,
+    pub(crate) delta: C,
+    pub(crate) z1: C::Scalar,
+    pub(crate) z2: C::Scalar,
 }
 
 impl<C: CurveAffine> Params<C> {
-    /// Initializes parameters for the curve, given a random oracle to draw
-    /// points from.
-    pub fn new<H: Hasher<C::Base>>(k: u32) -> Self {
+    /// Initializes parameters for the curve, deriving the generators
+    /// deterministically by hashing to the curve.
+    pub fn new(k: u32) -> Self {
         // This is usually a limitation on the curve, but we also want 32-bit
         // architectures to be supported.
         assert!(k < 32);
@@ -48,64 +59,30 @@ impl<C: CurveAffine> Params<C> {
 
         let n: u64 = 1 << k;
 
+        // The generators g_i are the hashes of the byte 0 followed by i as a
+        // 32-bit little endian integer, and h is the hash of the byte 1.
         let g = {
-            let hasher = &H::init(C::Base::zero());
-
-            let mut g = Vec::with_capacity(n as usize);
-            g.resize(n as usize, C::zero());
-
+            let mut g = vec![C::Projective::zero(); n as usize];
             parallelize(&mut g, move |g, start| {
-                let mut cur_value = C::Base::from(start as u64);
-                for g in g.iter_mut() {
-                    let mut hasher = hasher.clone();
-                    hasher.absorb(cur_value);
-                    cur_value += &C::Base::one();
-                    loop {
-                        let x = hasher.squeeze().to_bytes();
-                        let p = C::from_bytes(&x);
-                        if bool::from(p.is_some()) {
-                            *g = p.unwrap();
-                            break;
-                        }
-                    }
+                let hasher = C::Projective::hash_to_curve("Halo2-Parameters");
+                for (i, g) in g.iter_mut().enumerate() {
+                    let i = (start + i) as u32;
+                    let mut message = [0u8; 5];
+                    message[1..5].copy_from_slice(&i.to_le_bytes());
+                    *g = hasher(&message);
                 }
             });
 
-            g
+            let mut g_affine = vec![C::zero(); n as usize];
+            C::Projective::batch_to_affine(&g, &mut g_affine);
+            g_affine
         };
 
-        // Let's evaluate all of the Lagrange basis polynomials
-        // using an inverse FFT.
-        let mut alpha_inv = C::Scalar::ROOT_OF_UNITY_INV;
-        for _ in k..C::Scalar::S {
-            alpha_inv = alpha_inv.square();
-        }
-        let mut g_lagrange_projective = g.iter().map(|g| g.to_projective()).collect::<Vec<_>>();
-        best_fft(&mut g_lagrange_projective, alpha_inv, k);
-        let minv = C::Scalar::TWO_INV.pow_vartime(&[k as u64, 0, 0, 0]);
-        parallelize(&mut g_lagrange_projective, |g, _| {
-            for g in g.iter_mut() {
-                *g *= minv;
-            }
-        });
-
-        let g_lagrange = {
-            let mut g_lagrange = vec![C::zero(); n as usize];
-            parallelize(&mut g_lagrange, |g_lagrange, starts| {
-                C::Projective::batch_to_affine(
-                    &g_lagrange_projective[starts..(starts + g_lagrange.len())],
-                    g_lagrange,
-                );
-            });
-            drop(g_lagrange_projective);
-            g_lagrange
-        };
+        let g_lagrange = g_to_lagrange(&g, k);
 
         let h = {
-            let mut hasher = H::init(C::Base::zero());
-            let x = hasher.squeeze().to_bytes();
-            let p = C::from_bytes(&x);
-            p.unwrap()
+            let hasher = C::Projective::hash_to_curve("Halo2-Parameters");
+            hasher(&[1]).to_affine()
         };
 
         Params {
@@ -114,9 +91,26 @@ impl<C: CurveAffine> Params<C> {
             g,
             g_lagrange,
             h,
+            g_table: None,
+            g_lagrange_table: None,
         }
     }
 
+    /// Precomputes tables of multiples of the generators, after which
+    /// `commit` and `commit_lagrange` use fixed-base multi-exponentiation
+    /// instead of a variable-base one.
+    ///
+    /// The window of the tables is chosen from $n$, and is larger than that of
+    /// a variable-base multi-exponentiation of the same size, which cuts the
+    /// number of additions of a commitment by a third for $k = 10$ down to a
+    /// fifth for $k = 20$. In exchange, the tables hold about
+    /// $(256 / c + 1) \cdot 2^{k + 1}$ points for a window of $c$ bits, which
+    /// is between 13 and 24 times the size of the generators.
+    pub fn precompute(&mut self) {
+        self.g_table = Some(FixedBaseTable::for_bases(&self.g));
+        self.g_lagrange_table = Some(FixedBaseTable::for_bases(&self.g_lagrange));
+    }
+
     /// This computes a commitment to a polynomial described by the provided
     /// slice of coefficients. The commitment will be blinded by the blinding
     /// factor `r`.
@@ -125,7 +119,11 @@ impl<C: CurveAffine> Params<C> {
         poly: &Polynomial<C::Scalar, Coeff>,
         r: Blind<C::Scalar>,
     ) -> C::Projective {
-        metrics::increment_counter!("multiexp", "size" => format!("{}", poly.len() + 1), "fn" => "commit");
+        // metrics::increment_counter!("multiexp", "size" => format!("{}", poly.len() + 1), "fn" => "commit");
+        if let Some(table) = &self.g_table {
+            return table.multiexp(poly) + self.h * r.0;
+        }
+
         let mut tmp_scalars = Vec::with_capacity(poly.len() + 1);
         let mut tmp_bases = Vec::with_capacity(poly.len() + 1);
 
@@ -146,7 +144,11 @@ impl<C: CurveAffine> Params<C> {
         poly: &Polynomial<C::Scalar, LagrangeCoeff>,
         r: Blind<C::Scalar>,
     ) -> C::Projective {
-        metrics::increment_counter!("multiexp", "size" => format!("{}", poly.len() + 1), "fn" => "commit_lagrange");
+        // metrics::increment_counter!("multiexp", "size" => format!("{}", poly.len() + 1), "fn" => "commit_lagrange");
+        if let Some(table) = &self.g_lagrange_table {
+            return table.multiexp(poly) + self.h * r.0;
+        }
+
         let mut tmp_scalars = Vec::with_capacity(poly.len() + 1);
         let mut tmp_bases = Vec::with_capacity(poly.len() + 1);
 
@@ -169,6 +171,158 @@ impl<C: CurveAffine> Params<C> {
     pub fn get_g(&self) -> Vec<C> {
         self.g.clone()
     }
+
+    /// Getter for the h generator, used for blinding
+    pub fn get_h(&self) -> C {
+        self.h
+    }
+
+    /// Returns the parameters for polynomials of degree less than $2^k$,
+    /// which share their generators with these parameters. A commitment made
+    /// with the returned parameters is also a commitment to the same
+    /// polynomial with these parameters.
+    ///
+    /// The returned parameters have no precomputed tables.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is larger than the `k` of these parameters.
+    pub fn downsize(&self, k: u32) -> Self {
+        assert!(k <= self.k);
+
+        let n = 1 << k;
+        let g = self.g[..n as usize].to_vec();
+        let g_lagrange = g_to_lagrange(&g, k);
+
+        Params {
+            k,
+            n,
+            g,
+            g_lagrange,
+            h: self.h,
+            g_table: None,
+            g_lagrange_table: None,
+        }
+    }
+}
+
+impl<C: CurveAffine> CommitmentScheme<C> for Params<C> {
+    type Proof = Proof<C>;
+
+    fn n(&self) -> u64 {
+        self.n
+    }
+
+    fn k(&self) -> u32 {
+        self.k
+    }
+
+    fn is_downsized_from(&self, params: &Self) -> bool {
+        self.k <= params.k && self.h == params.h && self.g[..] == params.g[..self.g.len()]
+    }
+
+    fn commit(&self, poly: &Polynomial<C::Scalar, Coeff>, r: Blind<C::Scalar>) -> C::Projective {
+        Params::commit(self, poly, r)
+    }
+
+    fn commit_lagrange(
+        &self,
+        poly: &Polynomial<C::Scalar, LagrangeCoeff>,
+        r: Blind<C::Scalar>,
+    ) -> C::Projective {
+        Params::commit_lagrange(self, poly, r)
+    }
+
+    fn open<HBase, HScalar, R>(
+        &self,
+        transcript: &mut Transcript<C, HBase, HScalar>,
+        px: &Polynomial<C::Scalar, Coeff>,
+        blind: Blind<C::Scalar>,
+        x: C::Scalar,
+        rng: R,
+    ) -> Result<Proof<C>, Error>
+    where
+        HBase: Hasher<C::Base>,
+        HScalar: Hasher<C::Scalar>,
+        R: RngCore + CryptoRng,
+    {
+        Proof::create(self, transcript, px, blind, x, rng)
+    }
+}
+
+impl<'a, C: CurveAffine> CommitmentSchemeVerifier<'a, C> for Params<C> {
+    type MSM = MSM<'a, C>;
+    type Guard = Guard<'a, C>;
+
+    fn empty_msm(&'a self) -> MSM<'a, C> {
+        MSM::new(self)
+    }
+
+    fn finalize(guard: Guard<'a, C>) -> MSM<'a, C> {
+        guard.use_challenges()
+    }
+
+    fn verify_opening<HBase, HScalar>(
+        &'a self,
+        proof: &Proof<C>,
+        msm: MSM<'a, C>,
+        transcript: &mut Transcript<C, HBase, HScalar>,
+        x: C::Scalar,
+        commitment: MSM<'a, C>,
+        v: C::Scalar,
+    ) -> Result<Guard<'a, C>, Error>
+    where
+        HBase: Hasher<C::Base>,
+        HScalar: Hasher<C::Scalar>,
+    {
+        proof.verify(self, msm, transcript, x, commitment, v)
+    }
+}
+
+impl<'a, C: CurveAffine> SchemeMSM<C> for MSM<'a, C> {
+    fn append_term(&mut self, scalar: C::Scalar, point: C) {
+        MSM::append_term(self, scalar, point)
+    }
+
+    fn add_msm(&mut self, other: &Self) {
+        MSM::add_msm(self, other)
+    }
+
+    fn scale(&mut self, factor: C::Scalar) {
+        MSM::scale(self, factor)
+    }
+
+    fn eval(self) -> bool {
+        MSM::eval(self)
+    }
+}
+
+/// Computes the commitments to the Lagrange basis polynomials over the $2^k$
+/// size evaluation domain, given the generators `g` for the coefficients.
+pub(crate) fn g_to_lagrange<C: CurveAffine>(g: &[C], k: u32) -> Vec<C> {
+    // Let's evaluate all of the Lagrange basis polynomials
+    // using an inverse FFT.
+    let mut alpha_inv = C::Scalar::ROOT_OF_UNITY_INV;
+    for _ in k..C::Scalar::S {
+        alpha_inv = alpha_inv.square();
+    }
+    let mut g_lagrange_projective = g.iter().map(|g| g.to_projective()).collect::<Vec<_>>();
+    best_fft(&mut g_lagrange_projective, alpha_inv, k);
+    let minv = C::Scalar::TWO_INV.pow_vartime(&[k as u64, 0, 0, 0]);
+    parallelize(&mut g_lagrange_projective, |g, _| {
+        for g in g.iter_mut() {
+            *g *= minv;
+        }
+    });
+
+    let mut g_lagrange = vec![C::zero(); g.len()];
+    parallelize(&mut g_lagrange, |g_lagrange, starts| {
+        C::Projective::batch_to_affine(
+            &g_lagrange_projective[starts..(starts + g_lagrange.len())],
+            g_lagrange,
+        );
+    });
+    g_lagrange
 }
 
 /// Wrapper type around a blinding factor.
@@ -225,9 +379,8 @@ impl<F: FieldExt> MulAssign<F> for Blind<F> {
 fn test_commit_lagrange() {
     const K: u32 = 6;
 
-    use crate::pasta::{EpAffine, Fp, Fq};
-    use crate::transcript::DummyHash;
-    let params = Params::<EpAffine>::new::<DummyHash<Fp>>(K);
+    use crate::pasta::{EpAffine, Fq};
+    let params = Params::<EpAffine>::new(K);
     let domain = super::EvaluationDomain::new(1, K);
 
     let mut a = domain.empty_lagrange();
@@ -243,6 +396,62 @@ fn test_commit_lagrange() {
     assert_eq!(params.commit(&b, alpha), params.commit_lagrange(&a, alpha));
 }
 
+#[test]
+fn test_downsize() {
+    const K: u32 = 6;
+
+    use crate::pasta::{EpAffine, Fq};
+    let params = Params::<EpAffine>::new(K);
+    let small = params.downsize(K - 2);
+    let domain = super::EvaluationDomain::new(1, K - 2);
+
+    // The downsized parameters are those of the smaller size.
+    let expected = Params::<EpAffine>::new(K - 2);
+    assert_eq!(small.g, expected.g);
+    assert_eq!(small.g_lagrange, expected.g_lagrange);
+    assert_eq!(small.h, expected.h);
+    assert!(small.is_downsized_from(&params));
+    assert!(!params.is_downsized_from(&small));
+
+    // A commitment with the downsized parameters is a commitment to the same
+    // polynomial with the original parameters.
+    let mut a = domain.empty_lagrange();
+    for (i, a) in a.iter_mut().enumerate() {
+        *a = Fq::from(i as u64);
+    }
+    let mut b = domain.lagrange_to_coeff(a.clone());
+    b.values.resize(params.n as usize, Fq::zero());
+
+    let alpha = Blind(Fq::rand());
+
+    assert_eq!(small.commit_lagrange(&a, alpha), params.commit(&b, alpha));
+}
+
+#[test]
+fn test_precompute() {
+    const K: u32 = 6;
+
+    use crate::pasta::{EpAffine, Fq};
+    let params = Params::<EpAffine>::new(K);
+    let mut precomputed = Params::<EpAffine>::new(K);
+    precomputed.precompute();
+    let domain = super::EvaluationDomain::new(1, K);
+
+    let mut a = domain.empty_lagrange();
+    for a in a.iter_mut() {
+        *a = Fq::rand();
+    }
+    let b = domain.lagrange_to_coeff(a.clone());
+
+    let alpha = Blind(Fq::rand());
+
+    assert_eq!(precomputed.commit(&b, alpha), params.commit(&b, alpha));
+    assert_eq!(
+        precomputed.commit_lagrange(&a, alpha),
+        params.commit_lagrange(&a, alpha)
+    );
+}
+
 #[test]
 fn test_opening_proof() {
     const K: u32 = 6;
@@ -256,8 +465,9 @@ fn test_opening_proof() {
     use crate::arithmetic::{eval_polynomial, Curve, FieldExt};
     use crate::pasta::{EpAffine, Fp, Fq};
     use crate::transcript::{ChallengeScalar, DummyHash, Transcript};
+    use rand::rngs::OsRng;
 
-    let params = Params::<EpAffine>::new::<DummyHash<Fp>>(K);
+    let params = Params::<EpAffine>::new(K);
     let domain = EvaluationDomain::new(1, K);
 
     let mut px = domain.empty_coeff();
@@ -281,7 +491,7 @@ fn test_opening_proof() {
     loop {
         let mut transcript_dup = transcript.clone();
 
-        let opening_proof = Proof::create(&params, &mut transcript, &px, blind, *x);
+        let opening_proof = Proof::create(&params, &mut transcript, &px, blind, *x, OsRng);
         if let Ok(opening_proof) = opening_proof {
             // Verify the opening proof
             let mut commitment_msm = params.empty_msm();
@@ -0,0 +1,216 @@
+use ff::Field;
+use rand::{CryptoRng, RngCore};
+
+use super::super::Error;
+use super::verifier::compute_s;
+use super::Params;
+use crate::arithmetic::{Curve, CurveAffine, FieldExt};
+use crate::transcript::{Challenge, ChallengeScalar};
+
+/// An accumulator instance consisting of an evaluation claim and a proof.
+#[derive(Debug, Clone)]
+pub struct Accumulator<C: CurveAffine> {
+    /// The claimed output of the linear-time polycommit opening protocol
+    pub g: C,
+
+    /// A vector of 128-bit challenges sampled by the verifier, to be used in
+    /// computing g.
+    pub challenges_sq_packed: Vec<Challenge>,
+}
+
+impl<C: CurveAffine> Accumulator<C> {
+    /// Checks that `g` is the commitment to the polynomial defined by the
+    /// challenges, which requires a multiexp of size `params.n`.
+    pub fn decide(&self, params: &Params<C>) -> Result<(), Error> {
+        FoldedAccumulator::from(self.clone()).decide(params)
+    }
+}
+
+/// A random linear combination of [`Accumulator`]s.
+///
+/// Folding accumulators is cheap: it combines their G claims into a single
+/// point, and keeps the challenges of each accumulator along with the factor
+/// its challenge polynomial was scaled by. All of the claims are then checked
+/// at once by [`FoldedAccumulator::decide`], with a single multiexp of size
+/// `params.n` no matter how many accumulators were folded.
+#[derive(Debug, Clone)]
+pub struct FoldedAccumulator<C: CurveAffine> {
+    g: C,
+    terms: Vec<(C::Scalar, Vec<Challenge>)>,
+}
+
+impl<C: CurveAffine> From<Accumulator<C>> for FoldedAccumulator<C> {
+    fn from(accumulator: Accumulator<C>) -> Self {
+        FoldedAccumulator {
+            g: accumulator.g,
+            terms: vec![(C::Scalar::one(), accumulator.challenges_sq_packed)],
+        }
+    }
+}
+
+impl<C: CurveAffine> FoldedAccumulator<C> {
+    /// Folds a sequence of accumulators into one, sampling the factors of the
+    /// random linear combination from `rng`.
+    pub fn fold<R: RngCore + CryptoRng>(
+        accumulators: impl IntoIterator<Item = Accumulator<C>>,
+        mut rng: R,
+    ) -> Self {
+        let mut folded = FoldedAccumulator {
+            g: C::zero(),
+            terms: vec![],
+        };
+        for accumulator in accumulators {
+            folded.merge(accumulator.into(), &mut rng);
+        }
+        folded
+    }
+
+    /// Merges `other` into this accumulator, scaling it by a random factor
+    /// sampled from `rng`.
+    pub fn merge<R: RngCore + CryptoRng>(&mut self, other: Self, mut rng: R) {
+        let factor = C::Scalar::random(&mut rng);
+
+        // Neither G nor the factor is secret, so this need not be constant
+        // time.
+        let mut g = other.g.to_projective().mul_vartime(&factor);
+        g += self.g;
+        self.g = g.to_affine();
+
+        self.terms.extend(
+            other
+                .terms
+                .into_iter()
+                .map(|(coeff, challenges)| (coeff * &factor, challenges)),
+        );
+    }
+
+    /// Returns the number of accumulators that have been folded into this one.
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Returns `true` if no accumulators have been folded into this one.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Checks that G is the commitment to the folded challenge polynomials.
+    /// This costs a single multiexp of size `params.n`, plus `params.n` field
+    /// multiplications per folded accumulator.
+    pub fn decide(&self, params: &Params<C>) -> Result<(), Error> {
+        let mut msm = params.empty_msm();
+
+        for (coeff, challenges_sq_packed) in &self.terms {
+            if challenges_sq_packed.len() != params.k as usize {
+                return Err(Error::InvalidProofLength);
+            }
+
+            let challenges_sq: Vec<C::Scalar> = challenges_sq_packed
+                .iter()
+                .map(|challenge| *ChallengeScalar::<_, ()>::from(*challenge))
+                .collect();
+
+            // Recover the inverse of the product of the challenges, taking the
+            // same square roots as the verifier.
+            let mut allinv = C::Scalar::one();
+            for challenge_sq in &challenges_sq {
+                let challenge = challenge_sq
+                    .deterministic_sqrt()
+                    .ok_or(Error::InvalidChallenge)?;
+                let challenge_inv = challenge.invert();
+                if bool::from(challenge_inv.is_none()) {
+                    return Err(Error::InvalidChallenge);
+                }
+                allinv *= &challenge_inv.unwrap();
+            }
+
+            // G = ⟨s, params.g⟩ + H for each accumulator
+            msm.add_to_g_scalars(&compute_s(&challenges_sq, allinv * coeff));
+            msm.add_to_h_scalar(*coeff);
+        }
+
+        msm.append_term(-C::Scalar::one(), self.g);
+        msm.check()
+    }
+}
+
+#[test]
+fn test_folded_accumulator() {
+    const K: u32 = 4;
+
+    use super::{Blind, Proof};
+    use crate::arithmetic::eval_polynomial;
+    use crate::pasta::{EpAffine, Fq};
+    use crate::poly::EvaluationDomain;
+    use crate::transcript::{DummyHash, Transcript};
+    use rand::rngs::OsRng;
+
+    let params = Params::<EpAffine>::new(K);
+    let domain = EvaluationDomain::new(1, K);
+
+    // Opens a random polynomial at a random point, and returns the accumulator
+    // of the opening.
+    let accumulate = || loop {
+        let mut px = domain.empty_coeff();
+        for a in px.iter_mut() {
+            *a = Fq::random(OsRng);
+        }
+        let blind = Blind(Fq::random(OsRng));
+        let p = params.commit(&px, blind).to_affine();
+
+        let mut transcript = Transcript::<_, DummyHash<_>, DummyHash<_>>::new();
+        transcript.absorb_point(&p).unwrap();
+        let x = ChallengeScalar::<_, ()>::get(&mut transcript);
+        let v = eval_polynomial(&px, *x);
+        let mut transcript_dup = transcript.clone();
+
+        if let Ok(opening_proof) = Proof::create(&params, &mut transcript, &px, blind, *x, OsRng) {
+            let mut commitment_msm = params.empty_msm();
+            commitment_msm.append_term(Field::one(), p);
+            let guard = opening_proof
+                .verify(
+                    &params,
+                    params.empty_msm(),
+                    &mut transcript_dup,
+                    *x,
+                    commitment_msm,
+                    v,
+                )
+                .unwrap();
+            let g = guard.compute_g();
+            let (msm, accumulator) = guard.use_g(g);
+            assert!(msm.eval());
+            return accumulator;
+        }
+    };
+
+    let accumulators: Vec<_> = (0..3).map(|_| accumulate()).collect();
+    for accumulator in accumulators.iter() {
+        assert_eq!(accumulator.decide(&params), Ok(()));
+    }
+
+    let mut folded = FoldedAccumulator::fold(accumulators.clone(), OsRng);
+    assert_eq!(folded.len(), 3);
+    assert_eq!(folded.decide(&params), Ok(()));
+
+    // Folds can themselves be merged.
+    folded.merge(FoldedAccumulator::fold(vec![accumulate()], OsRng), OsRng);
+    assert_eq!(folded.len(), 4);
+    assert_eq!(folded.decide(&params), Ok(()));
+
+    // A single bad claim is caught by the folded check.
+    let mut bad = accumulators[1].clone();
+    bad.g = accumulators[0].g;
+    assert_eq!(bad.decide(&params), Err(Error::OpeningFailed));
+    let mut accumulators = accumulators;
+    accumulators.push(bad);
+    assert_eq!(
+        FoldedAccumulator::fold(accumulators, OsRng).decide(&params),
+        Err(Error::OpeningFailed)
+    );
+
+    assert_eq!(
+        FoldedAccumulator::<EpAffine>::fold(vec![], OsRng).decide(&params),
+        Ok(())
+    );
+}
@@ -1,3 +1,4 @@
+use super::super::Error;
 use super::Params;
 use crate::arithmetic::{best_multiexp, parallelize, Curve, CurveAffine};
 
@@ -112,7 +113,17 @@ impl<'a, C: CurveAffine> MSM<'a, C> {
 
         assert_eq!(scalars.len(), len);
 
-        metrics::increment_counter!("multiexp", "size" => format!("{}", len), "fn" => "MSM::eval");
+        // metrics::increment_counter!("multiexp", "size" => format!("{}", len), "fn" => "MSM::eval");
         bool::from(best_multiexp(&scalars, &bases).is_zero())
     }
+
+    /// Perform multiexp and check that it results in zero, returning
+    /// [`Error::OpeningFailed`] if it does not.
+    pub fn check(self) -> Result<(), Error> {
+        if self.eval() {
+            Ok(())
+        } else {
+            Err(Error::OpeningFailed)
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use ff::Field;
+use rand::{CryptoRng, RngCore};
 
 use super::super::{Coeff, Error, Polynomial};
 use super::{Blind, Params, Proof};
@@ -21,16 +22,18 @@ impl<C: CurveAffine> Proof<C> {
     /// opening v, and the point x. It's probably also nice for the transcript
     /// to have seen the elliptic curve description and the SRS, if you want to
     /// be rigorous.
-    pub fn create<HBase, HScalar>(
+    pub fn create<HBase, HScalar, R>(
         params: &Params<C>,
         transcript: &mut Transcript<C, HBase, HScalar>,
         px: &Polynomial<C::Scalar, Coeff>,
         blind: Blind<C::Scalar>,
         x: C::Scalar,
+        mut rng: R,
     ) -> Result<Self, Error>
     where
         HBase: Hasher<C::Base>,
         HScalar: Hasher<C::Scalar>,
+        R: RngCore + CryptoRng,
     {
         let mut blind = blind.0;
 
@@ -79,14 +82,14 @@ impl<C: CurveAffine> Proof<C> {
             //
             // TODO: If we modify multiexp to take "extra" bases, we could speed
             // this piece up a bit by combining the multiexps.
-            metrics::counter!("multiexp", 2, "val" => "l/r", "size" => format!("{}", half));
+            // metrics::counter!("multiexp", 2, "val" => "l/r", "size" => format!("{}", half));
             let l = best_multiexp(&a[0..half], &g[half..]);
             let r = best_multiexp(&a[half..], &g[0..half]);
             let value_l = compute_inner_product(&a[0..half], &b[half..]);
             let value_r = compute_inner_product(&a[half..], &b[0..half]);
-            let mut l_randomness = C::Scalar::rand();
-            let r_randomness = C::Scalar::rand();
-            metrics::counter!("multiexp", 2, "val" => "l/r", "size" => "2");
+            let mut l_randomness = C::Scalar::random(&mut rng);
+            let r_randomness = C::Scalar::random(&mut rng);
+            // metrics::counter!("multiexp", 2, "val" => "l/r", "size" => "2");
             let l = l + &best_multiexp(&[value_l, l_randomness], &[u, params.h]);
             let r = r + &best_multiexp(&[value_r, r_randomness], &[u, params.h]);
             let mut l = l.to_affine();
@@ -138,7 +141,7 @@ impl<C: CurveAffine> Proof<C> {
             // And obtain the challenge, even though we already have it, since
             // squeezing affects the transcript.
             {
-                let challenge_sq_expected = ChallengeScalar::<_, ()>::get(transcript);
+                let challenge_sq_expected = ChallengeScalar::<C::Scalar, ()>::get(transcript);
                 assert_eq!(challenge_sq, *challenge_sq_expected);
             }
 
@@ -172,10 +175,10 @@ impl<C: CurveAffine> Proof<C> {
         let g = g[0];
 
         // Random nonces for the zero-knowledge opening
-        let d = C::Scalar::rand();
-        let s = C::Scalar::rand();
+        let d = C::Scalar::random(&mut rng);
+        let s = C::Scalar::random(&mut rng);
 
-        metrics::increment_counter!("multiexp", "val" => "delta", "size" => "3");
+        // metrics::increment_counter!("multiexp", "val" => "delta", "size" => "3");
         let delta = best_multiexp(&[d, d * &b, s], &[g, u, params.h]).to_affine();
 
         // Feed delta into the transcript
@@ -184,10 +187,10 @@ impl<C: CurveAffine> Proof<C> {
             .map_err(|_| Error::SamplingError)?;
 
         // Obtain the challenge c.
-        let c = ChallengeScalar::<_, ()>::get(transcript);
+        let c = ChallengeScalar::<C::Scalar, ()>::get(transcript);
 
         // Compute z1 and z2 as described in the Halo paper.
-        let z1 = a * &c + &d;
+        let z1 = a * *c + &d;
         let z2 = *c * &blind + &s;
 
         Ok(Proof {
@@ -206,7 +209,7 @@ fn parallel_generator_collapse<C: CurveAffine>(
 ) {
     let len = g.len() / 2;
     let (mut g_lo, g_hi) = g.split_at_mut(len);
-    metrics::counter!("multiexp", len as u64, "size" => "2", "fn" => "parallel_generator_collapse");
+    // metrics::counter!("multiexp", len as u64, "size" => "2", "fn" => "parallel_generator_collapse");
 
     parallelize(&mut g_lo, |g_lo, start| {
         let g_hi = &g_hi[start..];
@@ -1,7 +1,7 @@
 use ff::Field;
 
 use super::super::Error;
-use super::{Params, Proof, MSM};
+use super::{Accumulator, Params, Proof, MSM};
 use crate::transcript::{Challenge, ChallengeScalar, Hasher, Transcript};
 
 use crate::arithmetic::{best_multiexp, Curve, CurveAffine, FieldExt};
@@ -16,17 +16,6 @@ pub struct Guard<'a, C: CurveAffine> {
     challenges_sq_packed: Vec<Challenge>,
 }
 
-/// An accumulator instance consisting of an evaluation claim and a proof.
-#[derive(Debug, Clone)]
-pub struct Accumulator<C: CurveAffine> {
-    /// The claimed output of the linear-time polycommit opening protocol
-    pub g: C,
-
-    /// A vector of 128-bit challenges sampled by the verifier, to be used in
-    /// computing g.
-    pub challenges_sq_packed: Vec<Challenge>,
-}
-
 impl<'a, C: CurveAffine> Guard<'a, C> {
     /// Lets caller supply the challenges and obtain an MSM with updated
     /// scalars and points.
@@ -51,11 +40,17 @@ impl<'a, C: CurveAffine> Guard<'a, C> {
         (self.msm, accumulator)
     }
 
+    /// Returns the vector s, where G = ⟨s, params.g⟩ is the point computed
+    /// by [`Guard::compute_g`] before adding H.
+    pub fn g_scalars(&self) -> Vec<C::Scalar> {
+        compute_s(&self.challenges_sq, self.allinv)
+    }
+
     /// Computes G + H, where G = ⟨s, params.g⟩ and H is used for blinding
     pub fn compute_g(&self) -> C {
-        let s = compute_s(&self.challenges_sq, self.allinv);
+        let s = self.g_scalars();
 
-        metrics::increment_counter!("multiexp", "size" => format!("{}", s.len()), "fn" => "compute_g");
+        // metrics::increment_counter!("multiexp", "size" => format!("{}", s.len()), "fn" => "compute_g");
         let mut tmp = best_multiexp(&s, &self.msm.params.g);
         tmp += self.msm.params.h;
         tmp.to_affine()
@@ -81,7 +76,7 @@ impl<C: CurveAffine> Proof<C> {
     {
         // Check for well-formedness
         if self.rounds.len() != params.k as usize {
-            return Err(Error::OpeningError);
+            return Err(Error::InvalidProofLength);
         }
 
         // Compute U
@@ -91,7 +86,9 @@ impl<C: CurveAffine> Proof<C> {
             let u_y2 = u_x.square() * &u_x + &C::b();
             let u_y = u_y2.deterministic_sqrt();
             if u_y.is_none() {
-                return Err(Error::OpeningError);
+                // The prover could not have opened against this U, so the
+                // proof is invalid rather than in need of re-sampling.
+                return Err(Error::InvalidChallenge);
             }
             let u_y = u_y.unwrap();
 
@@ -114,17 +111,17 @@ impl<C: CurveAffine> Proof<C> {
             let r = round.1;
             transcript
                 .absorb_point(&l)
-                .map_err(|_| Error::OpeningError)?;
+                .map_err(|_| Error::InvalidPoint)?;
             transcript
                 .absorb_point(&r)
-                .map_err(|_| Error::OpeningError)?;
+                .map_err(|_| Error::InvalidPoint)?;
             let challenge_sq_packed = Challenge::get(transcript);
             let challenge_sq: C::Scalar = *ChallengeScalar::<_, ()>::from(challenge_sq_packed);
 
             let challenge = challenge_sq.deterministic_sqrt();
             if challenge.is_none() {
                 // We didn't sample a square.
-                return Err(Error::OpeningError);
+                return Err(Error::InvalidChallenge);
             }
             let challenge = challenge.unwrap();
 
@@ -132,7 +129,7 @@ impl<C: CurveAffine> Proof<C> {
             if bool::from(challenge_inv.is_none()) {
                 // We sampled zero for some reason, unlikely to happen by
                 // chance.
-                return Err(Error::OpeningError);
+                return Err(Error::InvalidChallenge);
             }
             let challenge_inv = challenge_inv.unwrap();
             allinv *= &challenge_inv;
@@ -153,7 +150,7 @@ impl<C: CurveAffine> Proof<C> {
         // Feed delta into the transcript
         transcript
             .absorb_point(&self.delta)
-            .map_err(|_| Error::OpeningError)?;
+            .map_err(|_| Error::InvalidPoint)?;
 
         // Get the challenge `c`
         let c = ChallengeScalar::<_, ()>::get(transcript);
@@ -218,7 +215,7 @@ fn compute_b<F: Field>(x: F, challenges: &[F], challenges_inv: &[F]) -> F {
 }
 
 // TODO: parallelize
-fn compute_s<F: Field>(challenges_sq: &[F], allinv: F) -> Vec<F> {
+pub(crate) fn compute_s<F: Field>(challenges_sq: &[F], allinv: F) -> Vec<F> {
     let lg_n = challenges_sq.len();
     let n = 1 << lg_n;
 
@@ -1,7 +1,7 @@
 //! Contains utilities for performing polynomial arithmetic over an evaluation
 //! domain that is of a suitable size for the application.
 
-use crate::arithmetic::{best_fft, parallelize, BatchInvert, FieldExt, Group};
+use crate::arithmetic::{fft, parallelize, BatchInvert, FftTwiddles, FieldExt, Group};
 
 use super::{Coeff, ExtendedLagrangeCoeff, LagrangeCoeff, Polynomial};
 
@@ -30,7 +30,7 @@ pub struct EvaluationDomain<G: Group> {
     omega: G::Scalar,
     omega_inv: G::Scalar,
     extended_omega: G::Scalar,
-    extended_omega_inv: G::Scalar,
+    extended_twiddles: FftTwiddles<G::Scalar>,
     g_coset: G::Scalar,
     g_coset_inv: G::Scalar,
     quotient_poly_degree: u64,
@@ -43,6 +43,11 @@ pub struct EvaluationDomain<G: Group> {
 impl<G: Group> EvaluationDomain<G> {
     /// This constructs a new evaluation domain object based on the provided
     /// values $j, k$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scalar field has no $2^{k'}$-th root of unity, where
+    /// $2^{k'}$ is the size of the extended domain.
     pub fn new(j: u32, k: u32) -> Self {
         // quotient_poly_degree * params.n - 1 is the degree of the quotient polynomial
         let quotient_poly_degree = (j - 1) as u64;
@@ -57,6 +62,11 @@ impl<G: Group> EvaluationDomain<G> {
         while (1 << extended_k) < (n * quotient_poly_degree) {
             extended_k += 1;
         }
+        assert!(
+            extended_k <= G::Scalar::S,
+            "the scalar field has no 2^{}-th root of unity",
+            extended_k
+        );
 
         let mut extended_omega = G::Scalar::ROOT_OF_UNITY;
 
@@ -67,7 +77,10 @@ impl<G: Group> EvaluationDomain<G> {
             extended_omega = extended_omega.square();
         }
         let extended_omega = extended_omega;
-        let mut extended_omega_inv = extended_omega; // Inversion computed later
+
+        // The powers of extended_omega also give those of omega, and the
+        // inverse transformations, so they are the only ones we need.
+        let extended_twiddles = FftTwiddles::new(extended_omega, extended_k);
 
         // Get omega, the 2^{k}'th root of unity (i.e. n'th root of unity)
         // The loop computes omega = extended_omega ^ {2 ^ (extended_k - k)}
@@ -126,7 +139,6 @@ impl<G: Group> EvaluationDomain<G> {
             .chain(Some(&mut ifft_divisor))
             .chain(Some(&mut extended_ifft_divisor))
             .chain(Some(&mut barycentric_weight))
-            .chain(Some(&mut extended_omega_inv))
             .chain(Some(&mut omega_inv))
             .batch_invert();
 
@@ -137,7 +149,7 @@ impl<G: Group> EvaluationDomain<G> {
             omega,
             omega_inv,
             extended_omega,
-            extended_omega_inv,
+            extended_twiddles,
             g_coset,
             g_coset_inv,
             quotient_poly_degree,
@@ -205,8 +217,8 @@ impl<G: Group> EvaluationDomain<G> {
         assert_eq!(a.values.len(), 1 << self.k);
 
         // Perform inverse FFT to obtain the polynomial in coefficient form
-        metrics::increment_counter!("ifft", "size" => format!("{}", a.len()), "fn" => "lagrange_to_coeff");
-        Self::ifft(&mut a.values, self.omega_inv, self.k, self.ifft_divisor);
+        // metrics::increment_counter!("ifft", "size" => format!("{}", a.len()), "fn" => "lagrange_to_coeff");
+        self.ifft(&mut a.values, self.ifft_divisor);
 
         Polynomial {
             values: a.values,
@@ -240,8 +252,8 @@ impl<G: Group> EvaluationDomain<G> {
             Self::distribute_powers(&mut a.values, g);
         }
         a.values.resize(self.extended_len(), G::group_zero());
-        metrics::increment_counter!("fft", "size" => format!("{}", self.extended_len()), "fn" => "coeff_to_extended");
-        best_fft(&mut a.values, self.extended_omega, self.extended_k);
+        // metrics::increment_counter!("fft", "size" => format!("{}", self.extended_len()), "fn" => "coeff_to_extended");
+        fft(&mut a.values, &self.extended_twiddles);
 
         Polynomial {
             values: a.values,
@@ -259,13 +271,8 @@ impl<G: Group> EvaluationDomain<G> {
         assert_eq!(a.values.len(), self.extended_len());
 
         // Inverse FFT
-        metrics::increment_counter!("ifft", "size" => format!("{}", a.len()), "fn" => "extended_to_coeff");
-        Self::ifft(
-            &mut a.values,
-            self.extended_omega_inv,
-            self.extended_k,
-            self.extended_ifft_divisor,
-        );
+        // metrics::increment_counter!("ifft", "size" => format!("{}", a.len()), "fn" => "extended_to_coeff");
+        self.ifft(&mut a.values, self.extended_ifft_divisor);
 
         // Distribute powers to move from coset; opposite from the
         // transformation we performed earlier.
@@ -333,8 +340,11 @@ impl<G: Group> EvaluationDomain<G> {
         });
     }
 
-    fn ifft(a: &mut [G], omega_inv: G::Scalar, log_n: u32, divisor: G::Scalar) {
-        best_fft(a, omega_inv, log_n);
+    // Performs an inverse FFT over the domain whose size is that of `a`, where
+    // `divisor` is the inverse of that size.
+    fn ifft(&self, a: &mut [G], divisor: G::Scalar) {
+        fft(a, &self.extended_twiddles);
+        a[1..].reverse();
         parallelize(a, |a, _| {
             for a in a {
                 // Finish iFFT
@@ -343,6 +353,11 @@ impl<G: Group> EvaluationDomain<G> {
         });
     }
 
+    /// Get $n = 2^k$, the size of the domain
+    pub fn get_n(&self) -> u64 {
+        self.n
+    }
+
     /// Get the size of the extended domain
     pub fn extended_len(&self) -> usize {
         1 << self.extended_k
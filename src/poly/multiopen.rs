@@ -1,9 +1,13 @@
 //! This module contains an optimisation of the polynomial commitment opening
 //! scheme described in the [Halo][halo] paper.
 //!
+//! The multi-point opening reduces every query to the opening of a single
+//! polynomial at a single point, so it can be built on any
+//! [`CommitmentScheme`](super::scheme::CommitmentScheme). By default it uses
+//! the inner product argument in [`commitment`].
+//!
 //! [halo]: https://eprint.iacr.org/2019/1021
 
-use ff::Field;
 use std::collections::{BTreeMap, BTreeSet};
 
 use super::*;
@@ -36,17 +40,18 @@ struct X4 {}
 /// together.
 type ChallengeX4<F> = ChallengeScalar<F, X4>;
 
-/// This is a multi-point opening proof used in the polynomial commitment scheme opening.
+/// This is a multi-point opening proof used in the polynomial commitment scheme
+/// opening, where `O` is the opening proof of the underlying commitment scheme.
 #[derive(Debug, Clone)]
-pub struct Proof<C: CurveAffine> {
+pub struct Proof<C: CurveAffine, O = commitment::Proof<C>> {
     // A vector of evaluations at each set of query points
-    q_evals: Vec<C::Scalar>,
+    pub(crate) q_evals: Vec<C::Scalar>,
 
     // Commitment to final polynomial
-    f_commitment: C,
+    pub(crate) f_commitment: C,
 
     // Commitment proof
-    opening: commitment::Proof<C>,
+    pub(crate) opening: O,
 }
 
 /// A polynomial query at a point
@@ -73,14 +78,16 @@ pub struct VerifierQuery<'a, C: CurveAffine> {
     pub eval: C::Scalar,
 }
 
-struct CommitmentData<F: Field, T: PartialEq> {
-    commitment: T,
-    set_index: usize,
-    point_indices: Vec<usize>,
-    evals: Vec<F>,
+/// A commitment of a set of queries, with the point set it is opened at and
+/// its evaluations at those points.
+pub(crate) struct CommitmentData<E, T: PartialEq> {
+    pub(crate) commitment: T,
+    pub(crate) set_index: usize,
+    pub(crate) point_indices: Vec<usize>,
+    pub(crate) evals: Vec<E>,
 }
 
-impl<F: Field, T: PartialEq> CommitmentData<F, T> {
+impl<E, T: PartialEq> CommitmentData<E, T> {
     fn new(commitment: T) -> Self {
         CommitmentData {
             commitment,
@@ -91,23 +98,37 @@ impl<F: Field, T: PartialEq> CommitmentData<F, T> {
     }
 }
 
-trait Query<F>: Sized {
+/// A query of a commitment at a point. The evaluation is opaque to
+/// [`construct_intermediate_sets`], so it can also refer to a value held
+/// elsewhere, such as in a circuit.
+pub(crate) trait Query<F>: Sized {
     type Commitment: PartialEq + Copy;
+    type Eval: Copy + Default;
 
     fn get_point(&self) -> F;
-    fn get_eval(&self) -> F;
+    fn get_eval(&self) -> Self::Eval;
     fn get_commitment(&self) -> Self::Commitment;
 }
 
-fn construct_intermediate_sets<F: FieldExt, I, Q: Query<F>>(
+/// The commitments of some queries, and the sets of points they are queried
+/// at, as returned by [`construct_intermediate_sets`].
+type IntermediateSets<F, Q> = (
+    Vec<CommitmentData<<Q as Query<F>>::Eval, <Q as Query<F>>::Commitment>>,
+    Vec<Vec<F>>,
+);
+
+/// Groups `queries` by commitment, and the commitments by the set of points
+/// they are queried at. Returns the commitments in the order they are first
+/// queried, and the point sets in the order they are first used.
+pub(crate) fn construct_intermediate_sets<F: FieldExt, I, Q: Query<F>>(
     queries: I,
-) -> (Vec<CommitmentData<F, Q::Commitment>>, Vec<Vec<F>>)
+) -> IntermediateSets<F, Q>
 where
     I: IntoIterator<Item = Q> + Clone,
 {
     // Construct sets of unique commitments and corresponding information about
     // their queries.
-    let mut commitment_map: Vec<CommitmentData<F, Q::Commitment>> = vec![];
+    let mut commitment_map: Vec<CommitmentData<Q::Eval, Q::Commitment>> = vec![];
 
     // Also construct mapping from a unique point to a point_index. This defines
     // an ordering on the points.
@@ -161,7 +182,7 @@ where
     // Initialise empty evals vec for each unique commitment
     for commitment_data in commitment_map.iter_mut() {
         let len = commitment_data.point_indices.len();
-        commitment_data.evals = vec![F::zero(); len];
+        commitment_data.evals = vec![Q::Eval::default(); len];
     }
 
     // Populate set_index, evals and points for each commitment using point_idx_sets
@@ -226,13 +247,14 @@ mod tests {
         eval: F,
     }
 
-    impl<F: Copy> Query<F> for MyQuery<F> {
+    impl<F: Copy + Default> Query<F> for MyQuery<F> {
         type Commitment = usize;
+        type Eval = F;
 
         fn get_point(&self) -> F {
             self.point
         }
-        fn get_eval(&self) -> F {
+        fn get_eval(&self) -> Self::Eval {
             self.eval
         }
         fn get_commitment(&self) -> Self::Commitment {
@@ -1,5 +1,6 @@
 use super::super::{
-    commitment::{self, Blind, Params},
+    commitment::{self, Blind},
+    scheme::CommitmentScheme,
     Coeff, Error, Polynomial,
 };
 use super::{
@@ -7,31 +8,25 @@ use super::{
     ProverQuery, Query,
 };
 
-use crate::arithmetic::{
-    eval_polynomial, kate_division, lagrange_interpolate, Curve, CurveAffine, FieldExt,
-};
+use crate::arithmetic::{eval_polynomial, kate_division, lagrange_interpolate, Curve, CurveAffine};
 use crate::transcript::{Hasher, Transcript};
 
 use ff::Field;
+use rand::{CryptoRng, RngCore};
 use std::marker::PhantomData;
 
-#[derive(Debug, Clone)]
-struct CommitmentData<C: CurveAffine> {
-    set_index: usize,
-    blind: Blind<C::Scalar>,
-    point_indices: Vec<usize>,
-    evals: Vec<C::Scalar>,
-}
-
-impl<C: CurveAffine> Proof<C> {
-    /// Create a multi-opening proof
-    pub fn create<'a, I, HBase: Hasher<C::Base>, HScalar: Hasher<C::Scalar>>(
-        params: &Params<C>,
+impl<C: CurveAffine, O> Proof<C, O> {
+    /// Create a multi-opening proof with the commitment scheme `params`
+    pub fn create<'a, P, I, HBase: Hasher<C::Base>, HScalar: Hasher<C::Scalar>, R>(
+        params: &P,
         transcript: &mut Transcript<C, HBase, HScalar>,
         queries: I,
+        mut rng: R,
     ) -> Result<Self, Error>
     where
+        P: CommitmentScheme<C, Proof = O>,
         I: IntoIterator<Item = ProverQuery<'a, C>> + Clone,
+        R: RngCore + CryptoRng,
     {
         let x_1 = ChallengeX1::get(transcript);
         let x_2 = ChallengeX2::get(transcript);
@@ -56,7 +51,15 @@ impl<C: CurveAffine> Proof<C> {
                                   blind: Blind<C::Scalar>,
                                   evals: Vec<C::Scalar>| {
                 if let Some(poly) = &q_polys[set_idx] {
-                    q_polys[set_idx] = Some(poly.clone() * *x_1 + new_poly);
+                    let mut poly = poly.clone() * *x_1;
+                    // Polynomials from circuits over smaller domains are shorter.
+                    if poly.len() < new_poly.len() {
+                        poly.values.resize(new_poly.len(), C::Scalar::zero());
+                    }
+                    for (p, new) in poly.iter_mut().zip(new_poly.iter()) {
+                        *p += new;
+                    }
+                    q_polys[set_idx] = Some(poly);
                 } else {
                     q_polys[set_idx] = Some(new_poly.clone());
                 }
@@ -65,7 +68,7 @@ impl<C: CurveAffine> Proof<C> {
                 // Each polynomial is evaluated at a set of points. For each set,
                 // we collapse each polynomial's evals pointwise.
                 for (eval, set_eval) in evals.iter().zip(q_eval_sets[set_idx].iter_mut()) {
-                    *set_eval *= &x_1;
+                    *set_eval *= *x_1;
                     *set_eval += eval;
                 }
             };
@@ -80,6 +83,11 @@ impl<C: CurveAffine> Proof<C> {
             }
         }
 
+        // Bring every collapsed polynomial up to the size of the opening.
+        for poly in q_polys.iter_mut().flatten() {
+            poly.values.resize(params.n() as usize, C::Scalar::zero());
+        }
+
         let f_poly = point_sets
             .iter()
             .zip(q_eval_sets.iter())
@@ -93,7 +101,7 @@ impl<C: CurveAffine> Proof<C> {
                 let mut poly = points
                     .iter()
                     .fold(poly, |poly, point| kate_division(&poly, *point));
-                poly.resize(params.n as usize, C::Scalar::zero());
+                poly.resize(params.n() as usize, C::Scalar::zero());
                 let poly = Polynomial {
                     values: poly,
                     _marker: PhantomData,
@@ -107,7 +115,7 @@ impl<C: CurveAffine> Proof<C> {
             })
             .unwrap();
 
-        let mut f_blind = Blind(C::Scalar::rand());
+        let mut f_blind = Blind(C::Scalar::random(&mut rng));
         let mut f_commitment = params.commit(&f_poly, f_blind).to_affine();
 
         let (opening, q_evals) = loop {
@@ -129,23 +137,23 @@ impl<C: CurveAffine> Proof<C> {
 
             let x_4 = ChallengeX4::get(&mut transcript);
 
-            let (f_poly, f_blind_try) = q_polys.iter().zip(q_blinds.iter()).fold(
+            let (f_poly_try, f_blind_try) = q_polys.iter().zip(q_blinds.iter()).fold(
                 (f_poly.clone(), f_blind),
                 |(f_poly, f_blind), (poly, blind)| {
                     (
                         f_poly * *x_4 + poly.as_ref().unwrap(),
-                        Blind((f_blind.0 * &x_4) + &blind.0),
+                        Blind((f_blind.0 * *x_4) + &blind.0),
                     )
                 },
             );
 
             if let Ok(opening) =
-                commitment::Proof::create(&params, &mut transcript, &f_poly, f_blind_try, *x_3)
+                params.open(&mut transcript, &f_poly_try, f_blind_try, *x_3, &mut rng)
             {
                 break (opening, q_evals);
             } else {
                 f_blind += C::Scalar::one();
-                f_commitment = (f_commitment + params.h).to_affine();
+                f_commitment = params.commit(&f_poly, f_blind).to_affine();
             }
         };
 
@@ -172,11 +180,12 @@ impl<'a, C: CurveAffine> PartialEq for PolynomialPointer<'a, C> {
 
 impl<'a, C: CurveAffine> Query<C::Scalar> for ProverQuery<'a, C> {
     type Commitment = PolynomialPointer<'a, C>;
+    type Eval = C::Scalar;
 
     fn get_point(&self) -> C::Scalar {
         self.point
     }
-    fn get_eval(&self) -> C::Scalar {
+    fn get_eval(&self) -> Self::Eval {
         self.eval
     }
     fn get_commitment(&self) -> Self::Commitment {
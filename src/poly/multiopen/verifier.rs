@@ -1,7 +1,7 @@
 use ff::Field;
 
 use super::super::{
-    commitment::{Guard, Params, MSM},
+    scheme::{CommitmentSchemeVerifier, SchemeMSM},
     Error,
 };
 use super::{
@@ -11,23 +11,17 @@ use super::{
 use crate::arithmetic::{eval_polynomial, lagrange_interpolate, CurveAffine, FieldExt};
 use crate::transcript::{Hasher, Transcript};
 
-#[derive(Debug, Clone)]
-struct CommitmentData<C: CurveAffine> {
-    set_index: usize,
-    point_indices: Vec<usize>,
-    evals: Vec<C::Scalar>,
-}
-
-impl<C: CurveAffine> Proof<C> {
-    /// Verify a multi-opening proof
-    pub fn verify<'a, I, HBase: Hasher<C::Base>, HScalar: Hasher<C::Scalar>>(
+impl<C: CurveAffine, O> Proof<C, O> {
+    /// Verify a multi-opening proof with the commitment scheme `params`
+    pub fn verify<'a, P, I, HBase: Hasher<C::Base>, HScalar: Hasher<C::Scalar>>(
         &self,
-        params: &'a Params<C>,
+        params: &'a P,
         transcript: &mut Transcript<C, HBase, HScalar>,
         queries: I,
-        mut msm: MSM<'a, C>,
-    ) -> Result<Guard<'a, C>, Error>
+        mut msm: P::MSM,
+    ) -> Result<P::Guard, Error>
     where
+        P: CommitmentSchemeVerifier<'a, C, Proof = O>,
         I: IntoIterator<Item = VerifierQuery<'a, C>> + Clone,
     {
         // Scale the MSM by a random factor to ensure that if the existing MSM
@@ -40,10 +34,15 @@ impl<C: CurveAffine> Proof<C> {
 
         // Sample a challenge x_2 for keeping the multi-point quotient
         // polynomial terms linearly independent.
-        let x_2 = ChallengeX2::get(transcript);
+        let x_2: ChallengeX2<C::Scalar> = ChallengeX2::get(transcript);
 
         let (commitment_map, point_sets) = construct_intermediate_sets(queries);
 
+        // There must be one q_eval for each point set
+        if self.q_evals.len() != point_sets.len() {
+            return Err(Error::InvalidProofLength);
+        }
+
         // Compress the commitments and expected evaluations at x together.
         // using the challenge x_1
         let mut q_commitments: Vec<_> = vec![params.empty_msm(); point_sets.len()];
@@ -59,7 +58,7 @@ impl<C: CurveAffine> Proof<C> {
                 q_commitments[set_idx].scale(*x_1);
                 q_commitments[set_idx].append_term(C::Scalar::one(), new_commitment);
                 for (eval, set_eval) in evals.iter().zip(q_eval_sets[set_idx].iter_mut()) {
-                    *set_eval *= &x_1;
+                    *set_eval *= *x_1;
                     *set_eval += eval;
                 }
             };
@@ -78,7 +77,7 @@ impl<C: CurveAffine> Proof<C> {
         // Obtain the commitment to the multi-point quotient polynomial f(X).
         transcript
             .absorb_point(&self.f_commitment)
-            .map_err(|_| Error::SamplingError)?;
+            .map_err(|_| Error::InvalidPoint)?;
 
         // Sample a challenge x_3 for checking that f(X) was committed to
         // correctly.
@@ -102,7 +101,7 @@ impl<C: CurveAffine> Proof<C> {
                     let eval = points.iter().fold(*proof_eval - &r_eval, |eval, point| {
                         eval * &(*x_3 - point).invert().unwrap()
                     });
-                    msm_eval * &x_2 + &eval
+                    msm_eval * *x_2 + &eval
                 },
             );
 
@@ -118,13 +117,19 @@ impl<C: CurveAffine> Proof<C> {
             |(mut commitment_msm, msm_eval), (q_commitment, q_eval)| {
                 commitment_msm.scale(*x_4);
                 commitment_msm.add_msm(&q_commitment);
-                (commitment_msm, msm_eval * &x_4 + q_eval)
+                (commitment_msm, msm_eval * *x_4 + q_eval)
             },
         );
 
         // Verify the opening proof
-        self.opening
-            .verify(params, msm, transcript, *x_3, commitment_msm, msm_eval)
+        params.verify_opening(
+            &self.opening,
+            msm,
+            transcript,
+            *x_3,
+            commitment_msm,
+            msm_eval,
+        )
     }
 }
 
@@ -140,11 +145,12 @@ impl<'a, C> PartialEq for CommitmentPointer<'a, C> {
 
 impl<'a, C: CurveAffine> Query<C::Scalar> for VerifierQuery<'a, C> {
     type Commitment = CommitmentPointer<'a, C>;
+    type Eval = C::Scalar;
 
     fn get_point(&self) -> C::Scalar {
         self.point
     }
-    fn get_eval(&self) -> C::Scalar {
+    fn get_eval(&self) -> Self::Eval {
         self.eval
     }
     fn get_commitment(&self) -> Self::Commitment {
@@ -0,0 +1,171 @@
+//! This module contains traits abstracting over the polynomial commitment
+//! scheme, so that the [multi-point opening](super::multiopen) and
+//! (Turbo)PLONK can be built on schemes other than the inner product argument
+//! in [`commitment`](super::commitment), such as [KZG](crate::bn254::kzg).
+
+use std::fmt::Debug;
+
+use rand::{CryptoRng, RngCore};
+
+use super::{commitment::Blind, Coeff, Error, LagrangeCoeff, Polynomial};
+use crate::arithmetic::CurveAffine;
+use crate::transcript::{Hasher, Transcript};
+
+/// The public parameters of a polynomial commitment scheme whose commitments
+/// are points of `C`, along with everything the prover does with them.
+pub trait CommitmentScheme<C: CurveAffine> {
+    /// A proof that a committed polynomial evaluates to a value at a point.
+    type Proof: Clone + Debug;
+
+    /// Returns the maximum number of coefficients of a committed polynomial.
+    fn n(&self) -> u64;
+
+    /// Returns `k`, where `n() = 2^k`.
+    fn k(&self) -> u32;
+
+    /// Returns `true` if a commitment made with these parameters is also the
+    /// commitment to the same polynomial with `params`, which may be for
+    /// larger polynomials.
+    fn is_downsized_from(&self, params: &Self) -> bool;
+
+    /// Commits to a polynomial in coefficient form using the blinding factor
+    /// `r`.
+    fn commit(&self, poly: &Polynomial<C::Scalar, Coeff>, r: Blind<C::Scalar>) -> C::Projective;
+
+    /// Commits to a polynomial in Lagrange form using the blinding factor
+    /// `r`.
+    fn commit_lagrange(
+        &self,
+        poly: &Polynomial<C::Scalar, LagrangeCoeff>,
+        r: Blind<C::Scalar>,
+    ) -> C::Projective;
+
+    /// Creates a proof that the polynomial `px`, committed to with the
+    /// blinding factor `blind`, evaluates to `px(x)` at `x`.
+    ///
+    /// The `transcript` must already have seen the commitment to `px`, the
+    /// point `x` and the claimed evaluation.
+    fn open<HBase, HScalar, R>(
+        &self,
+        transcript: &mut Transcript<C, HBase, HScalar>,
+        px: &Polynomial<C::Scalar, Coeff>,
+        blind: Blind<C::Scalar>,
+        x: C::Scalar,
+        rng: R,
+    ) -> Result<Self::Proof, Error>
+    where
+        HBase: Hasher<C::Base>,
+        HScalar: Hasher<C::Scalar>,
+        R: RngCore + CryptoRng;
+}
+
+/// The verifier side of a [`CommitmentScheme`]. Opening proofs are checked by
+/// deferring their final check into an accumulator that borrows from the
+/// parameters for `'a`, so that many checks can be decided at once.
+pub trait CommitmentSchemeVerifier<'a, C: CurveAffine>: CommitmentScheme<C> {
+    /// A linear combination of commitments, which also accumulates the
+    /// deferred checks of opening proofs.
+    type MSM: SchemeMSM<C>;
+
+    /// The deferred check of an opening proof, returned by
+    /// [`CommitmentSchemeVerifier::verify_opening`].
+    type Guard;
+
+    /// Returns an empty linear combination.
+    fn empty_msm(&'a self) -> Self::MSM;
+
+    /// Completes the deferred check in `guard`, returning the accumulator
+    /// that holds it, to be decided with [`SchemeMSM::eval`].
+    fn finalize(guard: Self::Guard) -> Self::MSM;
+
+    /// Checks that the opening `proof` shows that the commitment
+    /// `commitment` opens to `v` at `x`, given the current `transcript`. The
+    /// check is deferred into `msm`, which may already hold other checks.
+    fn verify_opening<HBase, HScalar>(
+        &'a self,
+        proof: &Self::Proof,
+        msm: Self::MSM,
+        transcript: &mut Transcript<C, HBase, HScalar>,
+        x: C::Scalar,
+        commitment: Self::MSM,
+        v: C::Scalar,
+    ) -> Result<Self::Guard, Error>
+    where
+        HBase: Hasher<C::Base>,
+        HScalar: Hasher<C::Scalar>;
+}
+
+/// A linear combination of points of `C`, used both to combine commitments
+/// and to accumulate deferred checks.
+pub trait SchemeMSM<C: CurveAffine>: Clone {
+    /// Adds the term `[scalar] point`.
+    fn append_term(&mut self, scalar: C::Scalar, point: C);
+
+    /// Adds every term of `other`.
+    fn add_msm(&mut self, other: &Self);
+
+    /// Scales every term by `factor`.
+    fn scale(&mut self, factor: C::Scalar);
+
+    /// Decides every deferred check, returning `true` if all of them hold.
+    fn eval(self) -> bool;
+}
+
+#[test]
+fn test_multiopen_over_scheme() {
+    use super::{multiopen, multiopen::ProverQuery, multiopen::VerifierQuery, EvaluationDomain};
+    use crate::arithmetic::{eval_polynomial, Curve, FieldExt};
+    use crate::pasta::{EpAffine, Fp, Fq};
+    use crate::poly::commitment::Params;
+    use crate::transcript::DummyHash;
+    use rand::rngs::OsRng;
+
+    // Commits to a polynomial, opens it at two points and checks the opening,
+    // using only the commitment scheme traits.
+    fn open_and_check<P>(params: &P, k: u32) -> bool
+    where
+        P: for<'a> CommitmentSchemeVerifier<'a, EpAffine>,
+    {
+        let domain = EvaluationDomain::<Fq>::new(1, k);
+        let mut px = domain.empty_coeff();
+        for (i, a) in px.iter_mut().enumerate() {
+            *a = Fq::from_u64(i as u64);
+        }
+        let blind = Blind(Fq::rand());
+        let commitment = params.commit(&px, blind).to_affine();
+        let points = [Fq::from_u64(3), Fq::from_u64(5)];
+
+        let mut transcript = Transcript::<_, DummyHash<Fp>, DummyHash<Fq>>::new();
+        let proof = multiopen::Proof::create(
+            params,
+            &mut transcript,
+            points.iter().map(|&point| ProverQuery {
+                point,
+                poly: &px,
+                blind,
+                eval: eval_polynomial(&px, point),
+            }),
+            OsRng,
+        )
+        .expect("opening should not fail");
+
+        let mut transcript = Transcript::<_, DummyHash<Fp>, DummyHash<Fq>>::new();
+        proof
+            .verify(
+                params,
+                &mut transcript,
+                points.iter().map(|&point| VerifierQuery {
+                    point,
+                    commitment: &commitment,
+                    eval: eval_polynomial(&px, point),
+                }),
+                params.empty_msm(),
+            )
+            .map(|guard| P::finalize(guard).eval())
+            .unwrap_or(false)
+    }
+
+    const K: u32 = 4;
+    let params = Params::<EpAffine>::new(K);
+    assert!(open_and_check(&params, K));
+}
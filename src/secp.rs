@@ -0,0 +1,42 @@
+//! This module contains implementations for the secp256k1 elliptic curve
+//! group, as used by Bitcoin and Ethereum signatures, and for secq256k1, the
+//! curve that forms a cycle with it.
+//!
+//! Both curves are $y^2 = x^3 + 7$, over $\mathbb{F}_p$ and $\mathbb{F}_q$
+//! respectively, where $p$ and $q$ are the orders of secq256k1 and secp256k1.
+//! The multiplicative groups of these fields have 2-adicity only 1 and 6, so
+//! an [`EvaluationDomain`](crate::poly::EvaluationDomain) over them holds at
+//! most $2^6$ points: they are meant for arithmetic on the curves, such as
+//! verifying signatures inside a circuit, not for use as the proving curve.
+
+mod curves;
+mod fields;
+
+pub mod secp256k1;
+pub mod secq256k1;
+
+pub use curves::*;
+pub use fields::*;
+
+#[test]
+fn test_endo_consistency() {
+    use crate::arithmetic::{Curve, FieldExt};
+
+    let a = secp256k1::Point::one();
+    assert_eq!(a * secp256k1::Scalar::ZETA, a.endo());
+    let a = secq256k1::Point::one();
+    assert_eq!(a * secq256k1::Scalar::ZETA, a.endo());
+}
+
+#[test]
+fn test_curves() {
+    crate::arithmetic::test_curve::<Secp256k1>();
+    crate::arithmetic::test_curve::<Secq256k1>();
+}
+
+#[test]
+#[should_panic]
+fn test_domain_too_large() {
+    // Fq has 2-adicity 6, so there is no domain of size 2^7.
+    crate::poly::EvaluationDomain::<Fq>::new(1, 7);
+}
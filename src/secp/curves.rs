@@ -0,0 +1,82 @@
+//! This module contains implementations for the secp256k1 and secq256k1
+//! elliptic curve groups.
+
+use core::cmp;
+use core::fmt::Debug;
+use core::ops::{Add, Mul, Neg, Sub};
+use ff::Field;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use super::{Fp, Fq};
+use crate::arithmetic::{Curve, CurveAffine, FieldExt, Group};
+
+/// The compressed encoding of a secp256k1 or secq256k1 point. As the base
+/// fields of these curves are 256 bits wide, the sign of $y$ does not fit in
+/// the 32 bytes of $x$ and is kept in a 33rd byte.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Compressed(pub [u8; 33]);
+
+impl Default for Compressed {
+    fn default() -> Self {
+        Compressed([0; 33])
+    }
+}
+
+impl AsRef<[u8]> for Compressed {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsMut<[u8]> for Compressed {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+new_curve_impl!(
+    Secp256k1,
+    Secp256k1Affine,
+    Fp,
+    Fq,
+    Compressed,
+    "secp256k1",
+    // b = 7
+    Fp::from_raw([7, 0, 0, 0]),
+    // The standard generator of secp256k1.
+    (
+        Fp::from_raw([
+            0x59f2815b16f81798,
+            0x029bfcdb2dce28d9,
+            0x55a06295ce870b07,
+            0x79be667ef9dcbbac,
+        ]),
+        Fp::from_raw([
+            0x9c47d08ffb10d4b8,
+            0xfd17b448a6855419,
+            0x5da4fbfc0e1108a8,
+            0x483ada7726a3c465,
+        ])
+    )
+);
+
+new_curve_impl!(
+    Secq256k1,
+    Secq256k1Affine,
+    Fq,
+    Fp,
+    Compressed,
+    "secq256k1",
+    // b = 7
+    Fq::from_raw([7, 0, 0, 0]),
+    // (1, y) is a point of the curve, taking the smaller square root y of 8.
+    (
+        Fq::one(),
+        Fq::from_raw([
+            0xc1b800d1ffc278c0,
+            0x48916189041f2af0,
+            0x4909abdf82c9bd02,
+            0x0c7c97045a207463,
+        ])
+    )
+);
@@ -0,0 +1,14 @@
+//! The secp256k1 elliptic curve group.
+
+/// A secp256k1 point in the projective coordinate space.
+pub type Point = super::Secp256k1;
+
+/// A secp256k1 point in the affine coordinate space (or the point at
+/// infinity).
+pub type Affine = super::Secp256k1Affine;
+
+/// The base field of the secp256k1 group.
+pub type Base = super::Fp;
+
+/// The scalar field of the secp256k1 group.
+pub type Scalar = super::Fq;
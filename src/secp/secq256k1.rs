@@ -0,0 +1,14 @@
+//! The secq256k1 elliptic curve group.
+
+/// A secq256k1 point in the projective coordinate space.
+pub type Point = super::Secq256k1;
+
+/// A secq256k1 point in the affine coordinate space (or the point at
+/// infinity).
+pub type Affine = super::Secq256k1Affine;
+
+/// The base field of the secq256k1 group.
+pub type Base = super::Fq;
+
+/// The scalar field of the secq256k1 group.
+pub type Scalar = super::Fp;